@@ -0,0 +1,211 @@
+#!/usr/bin/env python3
+"""Regenerate the X.509 fixtures used by the verifier unit tests.
+
+Requires the `cryptography` package. Run from this directory:
+
+    python3 generate.py
+"""
+import base64
+import datetime
+import hashlib
+import json
+import struct
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.x509 import ocsp
+from cryptography.x509.oid import NameOID
+
+NOT_BEFORE = datetime.datetime(2024, 1, 1, tzinfo=datetime.timezone.utc)
+NOT_AFTER = datetime.datetime(2124, 1, 1, tzinfo=datetime.timezone.utc)
+
+
+def name(common_name, org="Proof of Anchor Test PKI"):
+    return x509.Name([
+        x509.NameAttribute(NameOID.COUNTRY_NAME, "US"),
+        x509.NameAttribute(NameOID.ORGANIZATION_NAME, org),
+        x509.NameAttribute(NameOID.COMMON_NAME, common_name),
+    ])
+
+
+def build(subject, issuer, public_key, signing_key, serial, ca, sans=None, extra=(),
+          not_before=NOT_BEFORE, not_after=NOT_AFTER):
+    builder = (
+        x509.CertificateBuilder()
+        .subject_name(subject)
+        .issuer_name(issuer)
+        .public_key(public_key)
+        .serial_number(serial)
+        .not_valid_before(not_before)
+        .not_valid_after(not_after)
+        .add_extension(x509.BasicConstraints(ca=ca, path_length=None), critical=True)
+    )
+    if sans:
+        builder = builder.add_extension(
+            x509.SubjectAlternativeName([x509.DNSName(s) for s in sans]), critical=False
+        )
+    for ext in extra:
+        builder = builder.add_extension(ext, critical=False)
+    return builder.sign(signing_key, hashes.SHA256())
+
+
+def der_octet_string(data):
+    assert len(data) < 0x10000
+    if len(data) < 0x80:
+        return bytes([0x04, len(data)]) + data
+    if len(data) < 0x100:
+        return bytes([0x04, 0x81, len(data)]) + data
+    return bytes([0x04, 0x82]) + struct.pack(">H", len(data)) + data
+
+
+def spki_der(public_key):
+    return public_key.public_bytes(serialization.Encoding.DER,
+                                   serialization.PublicFormat.SubjectPublicKeyInfo)
+
+
+def embedded_sct(log_key, issuer_public_key, precert_tbs, timestamp_ms):
+    """RFC 6962 v1 SCT over a precertificate entry, as a SignedCertificateTimestampList extension."""
+    issuer_key_hash = hashlib.sha256(spki_der(issuer_public_key)).digest()
+    entry = issuer_key_hash + struct.pack(">I", len(precert_tbs))[1:] + precert_tbs
+    signed = struct.pack(">BBQH", 0, 0, timestamp_ms, 1) + entry + struct.pack(">H", 0)
+    signature = log_key.sign(signed, ec.ECDSA(hashes.SHA256()))
+    log_id = hashlib.sha256(spki_der(log_key.public_key())).digest()
+    sct = (struct.pack(">B", 0) + log_id + struct.pack(">Q", timestamp_ms) + struct.pack(">H", 0)
+           + struct.pack(">BBH", 4, 3, len(signature)) + signature)
+    sct_list = struct.pack(">H", len(sct) + 2) + struct.pack(">H", len(sct)) + sct
+    return x509.UnrecognizedExtension(SCT_LIST_OID, der_octet_string(sct_list))
+
+
+SCT_LIST_OID = x509.ObjectIdentifier("1.3.6.1.4.1.11129.2.4.2")
+REVOKED_AT = datetime.datetime(2024, 6, 1, tzinfo=datetime.timezone.utc)
+
+
+def ocsp_response(cert, issuer, issuer_key, status):
+    builder = ocsp.OCSPResponseBuilder().add_response(
+        cert=cert, issuer=issuer, algorithm=hashes.SHA1(), cert_status=status,
+        this_update=NOT_BEFORE, next_update=NOT_AFTER,
+        revocation_time=REVOKED_AT if status == ocsp.OCSPCertStatus.REVOKED else None,
+        revocation_reason=(x509.ReasonFlags.key_compromise
+                           if status == ocsp.OCSPCertStatus.REVOKED else None),
+    ).responder_id(ocsp.OCSPResponderEncoding.HASH, issuer)
+    return builder.sign(issuer_key, hashes.SHA256()).public_bytes(serialization.Encoding.DER)
+
+
+def delegated_ocsp_response(cert, issuer, responder, responder_key, status):
+    """A response signed by a responder certificate the CA issued, which the response carries."""
+    builder = ocsp.OCSPResponseBuilder().add_response(
+        cert=cert, issuer=issuer, algorithm=hashes.SHA1(), cert_status=status,
+        this_update=NOT_BEFORE, next_update=NOT_AFTER, revocation_time=None, revocation_reason=None,
+    ).responder_id(ocsp.OCSPResponderEncoding.HASH, responder).certificates([responder])
+    return builder.sign(responder_key, hashes.SHA256()).public_bytes(serialization.Encoding.DER)
+
+
+def delegated_ocsp():
+    """A separate CA whose responses come from delegated responders, with and without id-kp-OCSPSigning."""
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+    ca = build(name("PoA Delegating CA"), name("PoA Delegating CA"),
+               ca_key.public_key(), ca_key, 0x3003, ca=True)
+    leaf_key = ec.generate_private_key(ec.SECP256R1())
+    leaf = build(name("delegated.example-project.com", org="Example Project"), ca.subject,
+                 leaf_key.public_key(), ca_key, 0x3103, ca=False,
+                 sans=["delegated.example-project.com"])
+    responder_key = ec.generate_private_key(ec.SECP256R1())
+    responder = build(name("PoA OCSP Responder"), ca.subject, responder_key.public_key(), ca_key,
+                      0x3203, ca=False,
+                      extra=[x509.ExtendedKeyUsage([x509.oid.ExtendedKeyUsageOID.OCSP_SIGNING])])
+    # Any other certificate from the same CA, e.g. a customer's server certificate
+    other_key = ec.generate_private_key(ec.SECP256R1())
+    other = build(name("other.example", org="Another Customer"), ca.subject, other_key.public_key(),
+                  ca_key, 0x3303, ca=False, sans=["other.example"])
+
+    write("delegated_chain.pem", pem(leaf) + pem(ca))
+    write("ocsp_delegated_good.der",
+          delegated_ocsp_response(leaf, ca, responder, responder_key, ocsp.OCSPCertStatus.GOOD))
+    write("ocsp_forged_good.der",
+          delegated_ocsp_response(leaf, ca, other, other_key, ocsp.OCSPCertStatus.GOOD))
+
+
+def crl(issuer, issuer_key, revoked_serials):
+    builder = (x509.CertificateRevocationListBuilder()
+               .issuer_name(issuer.subject).last_update(NOT_BEFORE).next_update(NOT_AFTER))
+    for serial in revoked_serials:
+        builder = builder.add_revoked_certificate(
+            x509.RevokedCertificateBuilder().serial_number(serial).revocation_date(REVOKED_AT).build())
+    return builder.sign(issuer_key, hashes.SHA256()).public_bytes(serialization.Encoding.PEM)
+
+
+def write(path, data):
+    with open(path, "wb") as f:
+        f.write(data)
+
+
+def pem(cert):
+    return cert.public_bytes(serialization.Encoding.PEM)
+
+
+def main():
+    root_key = ec.generate_private_key(ec.SECP256R1())
+    inter_key = ec.generate_private_key(ec.SECP256R1())
+    leaf_key = ec.generate_private_key(ec.SECP256R1())
+
+    root = build(name("PoA Test Root CA"), name("PoA Test Root CA"),
+                 root_key.public_key(), root_key, 0x1001, ca=True)
+    inter = build(name("PoA Test Intermediate CA"), root.subject,
+                  inter_key.public_key(), root_key, 0x2002, ca=True)
+    log_key = ec.generate_private_key(ec.SECP256R1())
+
+    leaf_args = (name("example-project.com", org="Example Project"), inter.subject,
+                 leaf_key.public_key(), inter_key, 0x0A0B0C0D0E0F)
+    leaf_sans = ["example-project.com", "www.example-project.com"]
+    precert = build(*leaf_args, ca=False, sans=leaf_sans)
+    sct = embedded_sct(log_key, inter_key.public_key(), precert.tbs_certificate_bytes,
+                       1704067200000)
+    leaf = build(*leaf_args, ca=False, sans=leaf_sans, extra=[sct])
+
+    write("root.pem", pem(root))
+    write("leaf.der", leaf.public_bytes(serialization.Encoding.DER))
+    write("chain.pem", pem(leaf) + pem(inter))
+
+    write("ocsp_good.der", ocsp_response(leaf, inter, inter_key, ocsp.OCSPCertStatus.GOOD))
+    write("ocsp_revoked.der", ocsp_response(leaf, inter, inter_key, ocsp.OCSPCertStatus.REVOKED))
+    write("intermediate.crl.pem", crl(inter, inter_key, [0x0DEAD]))
+    write("revoked.crl.pem", crl(inter, inter_key, [0x0DEAD, leaf.serial_number]))
+
+    log_spki = spki_der(log_key.public_key())
+    log_list = {"operators": [{"name": "PoA Test Operator", "logs": [{
+        "description": "PoA Test Log 2024",
+        "log_id": base64.b64encode(hashlib.sha256(log_spki).digest()).decode(),
+        "key": base64.b64encode(log_spki).decode(),
+        "url": "https://ct.example.invalid/2024/",
+    }]}]}
+    write("ct_log_list.json", (json.dumps(log_list, indent=2) + "\n").encode())
+
+    rogue_key = ec.generate_private_key(ec.SECP256R1())
+    rogue_name = name("example-project.com", org="Example Project")
+    self_signed = build(rogue_name, rogue_name, rogue_key.public_key(), rogue_key,
+                        0x0BAD, ca=False, sans=["example-project.com"])
+    write("self_signed.pem", pem(self_signed))
+
+    wildcard_name = name("*.example-project.com", org="Example Project")
+    wildcard = build(wildcard_name, wildcard_name, rogue_key.public_key(), rogue_key,
+                     0x0CA7, ca=False, sans=["*.example-project.com"])
+    write("wildcard.pem", pem(wildcard))
+
+    expired = build(rogue_name, rogue_name, rogue_key.public_key(), rogue_key,
+                    0x0DEAD0, ca=False, sans=["example-project.com"],
+                    not_after=datetime.datetime(2024, 4, 1, tzinfo=datetime.timezone.utc))
+    write("expired.pem", pem(expired))
+
+    # An SCT list extension whose list claims more bytes than it holds
+    broken_sct = x509.UnrecognizedExtension(
+        x509.ObjectIdentifier("1.3.6.1.4.1.11129.2.4.2"), der_octet_string(b"\x00\x05\x01"))
+    malformed_sct = build(rogue_name, rogue_name, rogue_key.public_key(), rogue_key,
+                          0x0BADC7, ca=False, sans=["example-project.com"], extra=[broken_sct])
+    write("malformed_sct.pem", pem(malformed_sct))
+
+    delegated_ocsp()
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,77 @@
+#!/usr/bin/env python3
+"""Regenerate the PDF fixtures used by the tokenomics and audit registry tests.
+
+Uses only the standard library. Run from this directory:
+
+    python3 generate_pdfs.py
+"""
+import zlib
+
+WHITEPAPER = [
+    "Example Project Whitepaper",
+    "4. Token Distribution",
+    "Total supply: 500,000,000 EXP",
+    ["Team", "20%"],
+    "Investors: 10%, 6-month cliff then 18-month linear release",
+    ["Ecosystem fund", "40%"],
+    ["Community rewards", "30%"],
+    "Team tokens vest over 36 months after a 12-month cliff.",
+    "Token mint address: 5yVfL3mW8qKj2nR4tZ9cXbH6pA1sD7gE3hN8uT2kYwQe",
+]
+
+AUDIT_REPORT = [
+    "Example Project smart contract security assessment",
+    "Prepared by PoA Test Auditors, 2024-03-01",
+    "Scope: staking and token contracts at commit 4f2a9c1",
+    "Findings: 0 critical, 1 high (resolved), 3 low",
+]
+
+
+def escape(text):
+    return text.replace("\\", "\\\\").replace("(", "\\(").replace(")", "\\)")
+
+
+def content(lines):
+    ops = ["BT", "/F1 11 Tf", "72 720 Td"]
+    for line in lines:
+        if isinstance(line, list):
+            # Table cells drawn as one TJ array with a wide gap between them
+            ops.append("[(%s) -4000 (%s)] TJ" % (escape(line[0]), escape(line[1])))
+        else:
+            ops.append("(%s) Tj" % escape(line))
+        ops.append("0 -16 Td")
+    ops.append("ET")
+    return "\n".join(ops).encode()
+
+
+def write(path, lines):
+    stream = zlib.compress(content(lines))
+    objects = [
+        b"<< /Type /Catalog /Pages 2 0 R >>",
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+        b"<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] "
+        b"/Resources << /Font << /F1 4 0 R >> >> /Contents 5 0 R >>",
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>",
+        b"<< /Length %d /Filter /FlateDecode >>\nstream\n" % len(stream) + stream + b"\nendstream",
+    ]
+    out = bytearray(b"%PDF-1.4\n")
+    offsets = []
+    for number, body in enumerate(objects, start=1):
+        offsets.append(len(out))
+        out += b"%d 0 obj\n" % number + body + b"\nendobj\n"
+    xref = len(out)
+    out += b"xref\n0 %d\n0000000000 65535 f \n" % (len(objects) + 1)
+    for offset in offsets:
+        out += b"%010d 00000 n \n" % offset
+    out += b"trailer\n<< /Size %d /Root 1 0 R >>\nstartxref\n%d\n%%%%EOF\n" % (len(objects) + 1, xref)
+    with open(path, "wb") as f:
+        f.write(out)
+
+
+def main():
+    write("whitepaper.pdf", WHITEPAPER)
+    write("audit_report.pdf", AUDIT_REPORT)
+
+
+if __name__ == "__main__":
+    main()
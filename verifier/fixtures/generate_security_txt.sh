@@ -0,0 +1,35 @@
+#!/bin/sh
+# Regenerate the PGP-signed security.txt fixture and its signing key.
+#
+# Requires GnuPG 2.1+. Run from this directory:
+#
+#     sh generate_security_txt.sh
+set -e
+
+export GNUPGHOME="$(mktemp -d)"
+trap 'rm -rf "$GNUPGHOME"' EXIT
+TIME=20240101T000000
+
+gpg --batch --passphrase '' --faked-system-time "$TIME" \
+    --quick-gen-key "Example Project Security <security@example-project.com>" ed25519 sign never
+
+cat > "$GNUPGHOME/security.txt" <<'EOF'
+# Security contact for example-project.com
+Contact: mailto:security@example-project.com
+Contact: https://example-project.com/security/report
+Expires: 2124-01-01T00:00:00.000Z
+Encryption: https://example-project.com/.well-known/pgp-key.asc
+Acknowledgments: https://example-project.com/security/hall-of-fame
+Policy: https://example-project.com/security/policy
+Preferred-Languages: en, de
+Canonical: https://example-project.com/.well-known/security.txt
+EOF
+
+gpg --batch --yes --faked-system-time "$TIME" --digest-algo SHA256 \
+    --clearsign -o security_txt_signed.txt "$GNUPGHOME/security.txt"
+gpg --armor --export security@example-project.com > security_txt_key.asc
+
+# A key the file does not advertise, for signatures by an unknown signer
+gpg --batch --passphrase '' --faked-system-time "$TIME" \
+    --quick-gen-key "Unrelated Signer <signer@unrelated.example>" ed25519 sign never
+gpg --armor --export signer@unrelated.example > security_txt_other_key.asc
@@ -0,0 +1,323 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+use std::time::Duration;
+use url::Url;
+
+use crate::public_suffix;
+
+/// Registry used when no `--audit-registry` file is given: audit firms and the sites they publish on
+const DEFAULT_REGISTRY: &str = include_str!("../data/audit_registry.json");
+/// Audit documents downloaded per domain
+const MAX_AUDIT_DOCUMENTS: usize = 10;
+/// Larger downloads are abandoned; real reports are a few megabytes
+const MAX_DOCUMENT_BYTES: usize = 25 * 1024 * 1024;
+
+/// A report an auditor published, as recorded in the local registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditPublication {
+    pub auditor: String,
+    /// Domain of the audited project; its subdomains count as the same project
+    pub project: String,
+    pub date: String,
+    /// SHA-256 of the report file, hex encoded
+    pub sha256: String,
+    /// Where the auditor publishes the report, for reports read on the auditor's own site
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// An audit firm and the domains it publishes reports on; their subdomains count too
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Auditor {
+    pub name: String,
+    pub domains: Vec<String>,
+}
+
+/// Either a bare array of publications or an object listing auditors as well
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RegistryFile {
+    Publications(Vec<AuditPublication>),
+    Full {
+        #[serde(default)]
+        auditors: Vec<Auditor>,
+        #[serde(default)]
+        publications: Vec<AuditPublication>,
+    },
+}
+
+/// Known audit firms and their publications
+#[derive(Debug, Clone)]
+pub struct AuditRegistry {
+    auditors: Vec<Auditor>,
+    publications: Vec<AuditPublication>,
+}
+
+impl Default for AuditRegistry {
+    fn default() -> Self {
+        Self::parse(DEFAULT_REGISTRY).expect("built-in audit registry is valid")
+    }
+}
+
+impl AuditRegistry {
+    pub fn from_file(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read audit registry {}", path))?;
+        Self::parse(&content).with_context(|| format!("Invalid audit registry {}", path))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let (mut auditors, mut publications) = match serde_json::from_str(content)? {
+            RegistryFile::Publications(publications) => (Vec::new(), publications),
+            RegistryFile::Full { auditors, publications } => (auditors, publications),
+        };
+        for auditor in &mut auditors {
+            for domain in &mut auditor.domains {
+                *domain = domain.trim().trim_end_matches('.').to_ascii_lowercase();
+            }
+        }
+        for publication in &mut publications {
+            publication.sha256 = publication.sha256.to_ascii_lowercase();
+            publication.project = publication.project.trim().trim_end_matches('.').to_ascii_lowercase();
+        }
+        Ok(Self { auditors, publications })
+    }
+
+    /// Add the auditors and publications of another registry to this one
+    pub fn extend(&mut self, other: AuditRegistry) {
+        self.auditors.extend(other.auditors);
+        self.publications.extend(other.publications);
+    }
+
+    /// Audit firm publishing on `url`'s host, by its listed domains or the URLs of its publications
+    pub fn auditor_at(&self, url: &str) -> Option<&str> {
+        let host = Url::parse(url).ok()?.host_str()?.trim_end_matches('.').to_ascii_lowercase();
+        let on = |domain: &str| host == domain || host.ends_with(&format!(".{}", domain));
+        self.auditors.iter()
+            .find(|a| a.domains.iter().any(|d| on(d)))
+            .map(|a| a.name.as_str())
+            .or_else(|| self.publications.iter()
+                .find(|p| p.url.as_deref().and_then(|u| Url::parse(u).ok()).is_some_and(|u| u.host_str() == Some(host.as_str())))
+                .map(|p| p.auditor.as_str()))
+    }
+
+    /// Publication with this report hash, or published at this URL
+    pub fn find(&self, sha256: Option<&str>, url: &str) -> Option<&AuditPublication> {
+        self.publications.iter().find(|p| {
+            sha256.is_some_and(|hash| p.sha256 == hash) || p.url.as_deref() == Some(url)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditStatus {
+    /// The document is a registered report for this project
+    Verified { auditor: String, date: String },
+    /// A registered report, but for a different project
+    OtherProject { auditor: String, project: String },
+    /// A page on an audit firm's own site that no registered publication matches; only a page naming
+    /// the project's domain is tied to the project at all
+    AuditorSiteUnregistered { auditor: String, names_project: bool },
+    /// The document exists but no auditor publication matches it
+    SelfHostedUnverified,
+    /// An audit is claimed, but the linked report could not be retrieved or no report is linked
+    ClaimedButMissing,
+}
+
+impl std::fmt::Display for AuditStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditStatus::Verified { auditor, date } => write!(f, "verified report by {} ({})", auditor, date),
+            AuditStatus::OtherProject { auditor, project } => write!(f, "report by {} for {}, not this project", auditor, project),
+            AuditStatus::AuditorSiteUnregistered { auditor, names_project: true } => {
+                write!(f, "on {}'s site naming this project, not in the auditor registry", auditor)
+            }
+            AuditStatus::AuditorSiteUnregistered { auditor, names_project: false } => {
+                write!(f, "on {}'s site without naming this project, not in the auditor registry", auditor)
+            }
+            AuditStatus::SelfHostedUnverified => f.write_str("self-hosted, not in the auditor registry"),
+            AuditStatus::ClaimedButMissing => f.write_str("claimed but missing"),
+        }
+    }
+}
+
+/// Verification result for one claimed audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditReportCheck {
+    pub url: String,
+    /// SHA-256 of the downloaded document, hex encoded
+    pub sha256: Option<String>,
+    pub status: AuditStatus,
+}
+
+impl std::fmt::Display for AuditReportCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.url, self.status)
+    }
+}
+
+/// Match a downloaded document (or a failed download) against the registry
+pub fn classify(registry: &AuditRegistry, domain: &str, url: &str, document: Option<&[u8]>) -> AuditReportCheck {
+    let domain = domain.trim().trim_end_matches('.').to_ascii_lowercase();
+    let sha256 = document.map(|body| hex::encode(Sha256::digest(body)));
+    let status = match registry.find(sha256.as_deref(), url) {
+        Some(p) if domain == p.project || domain.ends_with(&format!(".{}", p.project)) => {
+            AuditStatus::Verified { auditor: p.auditor.clone(), date: p.date.clone() }
+        }
+        Some(p) => AuditStatus::OtherProject { auditor: p.auditor.clone(), project: p.project.clone() },
+        None => match (document, registry.auditor_at(url)) {
+            (Some(body), Some(auditor)) => AuditStatus::AuditorSiteUnregistered {
+                auditor: auditor.to_string(),
+                names_project: names_domain(body, &public_suffix::site_domain(&domain)),
+            },
+            (Some(_), None) => AuditStatus::SelfHostedUnverified,
+            (None, _) => AuditStatus::ClaimedButMissing,
+        },
+    };
+    AuditReportCheck { url: url.to_string(), sha256, status }
+}
+
+/// Download each linked audit document, hash it and match it against the registry
+pub async fn verify_audit_documents(registry: &AuditRegistry, domain: &str, urls: &[String]) -> Vec<AuditReportCheck> {
+    let Ok(client) = reqwest::Client::builder()
+        .timeout(Duration::from_secs(20))
+        .build()
+    else {
+        return Vec::new();
+    };
+
+    let mut checks = Vec::new();
+    for url in urls.iter().take(MAX_AUDIT_DOCUMENTS) {
+        let document = match download(&client, url).await {
+            Ok(body) => Some(body),
+            Err(e) => {
+                println!("⚠️ Could not retrieve audit document {}: {}", url, e);
+                None
+            }
+        };
+        checks.push(classify(registry, domain, url, document.as_deref()));
+    }
+    checks
+}
+
+/// PDF or HTML body of `url`, up to `MAX_DOCUMENT_BYTES`
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let mut response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("{} returned {}", url, response.status()));
+    }
+    let content_type = response.headers().get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        if body.len() > MAX_DOCUMENT_BYTES {
+            return Err(anyhow::anyhow!("{} is larger than {} bytes", url, MAX_DOCUMENT_BYTES));
+        }
+    }
+    // Servers often label PDFs application/octet-stream; trust the magic number instead
+    if body.starts_with(b"%PDF-") {
+        if !is_complete_pdf(&body) {
+            return Err(anyhow::anyhow!("{} starts like a PDF but has no pages", url));
+        }
+    } else if !content_type.contains("html") {
+        return Err(anyhow::anyhow!("{} is neither a PDF nor an HTML page", url));
+    }
+    Ok(body)
+}
+
+/// A page object and an end-of-file marker; a badge image renamed to .pdf or "%PDF-" text has neither
+fn is_complete_pdf(body: &[u8]) -> bool {
+    static PAGE: OnceLock<regex::bytes::Regex> = OnceLock::new();
+    let page = PAGE.get_or_init(|| regex::bytes::Regex::new(r"/Type\s*/Page\b").expect("static regex is valid"));
+    page.is_match(body) && body.windows(5).any(|window| window == b"%%EOF")
+}
+
+/// `domain` appears in the document, in any case
+fn names_domain(body: &[u8], domain: &str) -> bool {
+    let domain = domain.as_bytes();
+    !domain.is_empty() && body.windows(domain.len()).any(|window| window.eq_ignore_ascii_case(domain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const REGISTRY: &str = include_str!("../fixtures/audit_registry.json");
+    const REPORT: &[u8] = include_bytes!("../fixtures/audit_report.pdf");
+
+    #[test]
+    fn test_classify_against_registry() {
+        let registry = AuditRegistry::parse(REGISTRY).unwrap();
+        assert_eq!(registry.publications.len(), 2);
+
+        let check = classify(&registry, "app.example-project.com", "https://example-project.com/audit.pdf", Some(REPORT));
+        assert_eq!(check.status, AuditStatus::Verified { auditor: "PoA Test Auditors".to_string(), date: "2024-03-01".to_string() });
+
+        // The same file on a copycat site is someone else's audit
+        let check = classify(&registry, "example-project.io", "https://example-project.io/audit.pdf", Some(REPORT));
+        assert_eq!(check.status, AuditStatus::OtherProject {
+            auditor: "PoA Test Auditors".to_string(),
+            project: "example-project.com".to_string(),
+        });
+
+        // Reports read on the auditor's site match by URL
+        let url = "https://audits.poa-test-auditors.example/another-protocol-2023.html";
+        let check = classify(&registry, "another-protocol.org", url, Some(b"<h1>Another Protocol</h1>"));
+        assert!(matches!(check.status, AuditStatus::Verified { .. }));
+
+        // An unregistered page on the auditor's site ties to the project only by naming its domain
+        let url = "https://audits.poa-test-auditors.example/example-project-2024.html";
+        let check = classify(&registry, "app.example-project.com", url, Some(b"<p>Scope: Example-Project.com contracts</p>"));
+        assert_eq!(check.status, AuditStatus::AuditorSiteUnregistered { auditor: "PoA Test Auditors".to_string(), names_project: true });
+        let check = classify(&registry, "example-project.com", url, Some(b"<p>Scope: Another Protocol contracts</p>"));
+        assert_eq!(check.status, AuditStatus::AuditorSiteUnregistered { auditor: "PoA Test Auditors".to_string(), names_project: false });
+
+        let check = classify(&registry, "example-project.com", "https://example-project.com/badge.pdf", Some(b"badge"));
+        assert_eq!(check.status, AuditStatus::SelfHostedUnverified);
+        let check = classify(&registry, "example-project.com", "https://example-project.com/missing.pdf", None);
+        assert_eq!(check.status, AuditStatus::ClaimedButMissing);
+    }
+
+    #[test]
+    fn test_default_registry_knows_audit_firms() {
+        let registry = AuditRegistry::default();
+        assert_eq!(registry.auditor_at("https://skynet.certik.com/projects/example-project"), Some("CertiK"));
+        assert_eq!(registry.auditor_at("https://certik.com.example-project.com/audit"), None);
+
+        // A bare array of publications is still a registry, and extends the built-in one
+        let mut registry = AuditRegistry::default();
+        let hash = hex::encode_upper(Sha256::digest(REPORT));
+        registry.extend(AuditRegistry::parse(&format!(r#"[{{"auditor": "CertiK", "project": "example-project.com", "date": "2024-05-01", "sha256": "{}"}}]"#, hash)).unwrap());
+        let check = classify(&registry, "example-project.com", "https://example-project.com/audit.pdf", Some(REPORT));
+        assert_eq!(check.status, AuditStatus::Verified { auditor: "CertiK".to_string(), date: "2024-05-01".to_string() });
+    }
+
+    #[tokio::test]
+    async fn test_verify_audit_documents() {
+        let server = test_http::serve(vec![
+            ("/audits/2024.pdf", MockResponse::ok(REPORT).with_header("Content-Type", "application/octet-stream")),
+            ("/audits/badge.pdf", MockResponse::ok("%PDF-1.4 Audited by a trusted firm")),
+            ("/audits/fake.pdf", MockResponse::ok("{\"error\": \"not found\"}").with_header("Content-Type", "application/json")),
+        ]).await;
+        let registry = AuditRegistry::parse(REGISTRY).unwrap();
+        let urls: Vec<String> = ["/audits/2024.pdf", "/audits/badge.pdf", "/audits/fake.pdf", "/audits/gone.pdf"]
+            .iter()
+            .map(|path| format!("{}{}", server.base_url, path))
+            .collect();
+
+        let checks = verify_audit_documents(&registry, "example-project.com", &urls).await;
+        let statuses: Vec<&AuditStatus> = checks.iter().map(|c| &c.status).collect();
+        assert!(matches!(statuses[0], AuditStatus::Verified { .. }));
+        // "%PDF-" followed by a sales pitch is not a report
+        assert_eq!(statuses[1], &AuditStatus::ClaimedButMissing);
+        assert_eq!(statuses[2], &AuditStatus::ClaimedButMissing);
+        assert_eq!(statuses[3], &AuditStatus::ClaimedButMissing);
+        assert_eq!(checks[0].sha256.as_deref(), Some("63bc85ba680e933be6eb7476bc5557de9a0144561d725364c4a30c189714496e"));
+    }
+}
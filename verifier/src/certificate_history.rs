@@ -0,0 +1,272 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::public_suffix;
+use crate::real_zk_tls::{CertificateProvenance, RealTlsCertificate};
+
+/// Reissues closer together than this are flagged; routine renewals are weeks apart
+const RAPID_REISSUE_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// One distinct certificate seen for a domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateObservation {
+    /// SHA-256 of the leaf certificate DER, hex encoded
+    pub fingerprint: String,
+    /// SHA-256 of the leaf SubjectPublicKeyInfo DER, hex encoded
+    pub spki_hash: String,
+    pub issuer: String,
+    pub serial: String,
+    pub not_before: u64,
+    pub not_after: u64,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub provenance: CertificateProvenance,
+    /// Hex-encoded DER chain as presented, leaf first, so the observation can be replayed
+    pub chain: Vec<String>,
+}
+
+impl CertificateObservation {
+    pub fn from_certificate(cert: &RealTlsCertificate) -> Self {
+        let leaf = cert.presented_chain.first().map(|c| c.as_slice()).unwrap_or_default();
+        Self {
+            fingerprint: hex::encode(Sha256::digest(leaf)),
+            spki_hash: hex::encode(Sha256::digest(&cert.public_key)),
+            issuer: cert.issuer.clone(),
+            serial: hex::encode(&cert.serial_number),
+            not_before: cert.not_before,
+            not_after: cert.not_after,
+            first_seen: cert.verification_timestamp,
+            last_seen: cert.verification_timestamp,
+            provenance: cert.provenance,
+            chain: cert.presented_chain.iter().map(hex::encode).collect(),
+        }
+    }
+
+    /// Decode the stored chain back into DER certificates
+    pub fn chain_der(&self) -> Result<Vec<Vec<u8>>> {
+        self.chain.iter()
+            .map(|c| hex::decode(c).context("Corrupt certificate in history"))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CertificateAnomaly {
+    /// The issuing organization differs from the previous certificate
+    IssuerChanged { previous: String, current: String },
+    /// The same public key was seen on certificates for unrelated domains
+    KeyReusedAcrossDomains { domains: Vec<String> },
+    /// A new certificate replaced the previous one unusually quickly
+    RapidReissue { gap_secs: u64 },
+}
+
+impl std::fmt::Display for CertificateAnomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CertificateAnomaly::IssuerChanged { previous, current } => {
+                write!(f, "issuer changed from '{}' to '{}'", previous, current)
+            }
+            CertificateAnomaly::KeyReusedAcrossDomains { domains } => {
+                write!(f, "public key also used by {}", domains.join(", "))
+            }
+            CertificateAnomaly::RapidReissue { gap_secs } => {
+                write!(f, "certificate reissued after only {}h", gap_secs / 3600)
+            }
+        }
+    }
+}
+
+/// Local store of every certificate observed, keyed by domain
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CertificateHistory {
+    #[serde(skip)]
+    path: PathBuf,
+    /// Observations were recorded since the last save
+    #[serde(skip)]
+    dirty: bool,
+    domains: BTreeMap<String, Vec<CertificateObservation>>,
+}
+
+impl CertificateHistory {
+    /// Open the store at `path`, starting empty if it does not exist yet
+    pub fn load(path: &str) -> Result<Self> {
+        let mut history = if Path::new(path).exists() {
+            let data = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read certificate history: {}", path))?;
+            serde_json::from_str::<Self>(&data)
+                .with_context(|| format!("Failed to parse certificate history: {}", path))?
+        } else {
+            Self::default()
+        };
+        history.path = PathBuf::from(path);
+        Ok(history)
+    }
+
+    /// The file contents to write, or `None` when nothing changed
+    fn take_snapshot(&mut self) -> Result<Option<(PathBuf, String)>> {
+        if !self.dirty {
+            return Ok(None);
+        }
+        self.dirty = false;
+        Ok(Some((self.path.clone(), serde_json::to_string_pretty(self)?)))
+    }
+
+    /// Most recently observed certificate for `domain`
+    pub fn latest(&self, domain: &str) -> Option<&CertificateObservation> {
+        self.domains.get(domain).and_then(|observations| observations.last())
+    }
+
+    /// Compare an observation with the history, then add it
+    pub fn record(&mut self, domain: &str, observation: CertificateObservation) -> Vec<CertificateAnomaly> {
+        let mut anomalies = Vec::new();
+        self.dirty = true;
+
+        // A certificate seen before, e.g. one of several behind a load balancer, becomes the latest again
+        let observations = self.domains.entry(domain.to_string()).or_default();
+        if let Some(index) = observations.iter().position(|o| o.fingerprint == observation.fingerprint) {
+            let mut known = observations.remove(index);
+            known.last_seen = known.last_seen.max(observation.last_seen);
+            observations.push(known);
+            return anomalies;
+        }
+
+        if let Some(previous) = self.latest(domain) {
+            let (old_org, new_org) = (issuer_organization(&previous.issuer), issuer_organization(&observation.issuer));
+            if old_org != new_org {
+                anomalies.push(CertificateAnomaly::IssuerChanged {
+                    previous: previous.issuer.clone(),
+                    current: observation.issuer.clone(),
+                });
+            }
+            let gap_secs = observation.not_before.saturating_sub(previous.not_before);
+            if observation.not_before >= previous.not_before && gap_secs < RAPID_REISSUE_SECS {
+                anomalies.push(CertificateAnomaly::RapidReissue { gap_secs });
+            }
+        }
+
+        let reused: Vec<String> = self.domains.iter()
+            .filter(|(other, _)| !domains_related(domain, other))
+            .filter(|(_, observations)| observations.iter().any(|o| o.spki_hash == observation.spki_hash))
+            .map(|(other, _)| other.clone())
+            .collect();
+        if !reused.is_empty() {
+            anomalies.push(CertificateAnomaly::KeyReusedAcrossDomains { domains: reused });
+        }
+
+        self.domains.entry(domain.to_string()).or_default().push(observation);
+        anomalies
+    }
+}
+
+/// The `O=` attribute of an issuer DN, which stays stable across a CA's intermediates
+fn issuer_organization(issuer: &str) -> &str {
+    issuer.split(", ")
+        .find_map(|rdn| rdn.strip_prefix("O="))
+        .unwrap_or(issuer)
+}
+
+/// Same registrable domain under the public suffix list, so `a.co.uk` and `b.co.uk` are unrelated
+fn domains_related(a: &str, b: &str) -> bool {
+    public_suffix::registered_domain(a) == public_suffix::registered_domain(b)
+}
+
+/// Write the store if it changed, without holding the lock or blocking the runtime during the write
+pub async fn save(history: &Mutex<CertificateHistory>) -> Result<()> {
+    let Some((path, data)) = history.lock().unwrap().take_snapshot()? else { return Ok(()) };
+    tokio::task::spawn_blocking(move || {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, data).with_context(|| format!("Failed to write certificate history: {}", path.display()))
+    }).await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn observation(fingerprint: &str, spki_hash: &str, issuer: &str, not_before: u64) -> CertificateObservation {
+        CertificateObservation {
+            fingerprint: fingerprint.to_string(),
+            spki_hash: spki_hash.to_string(),
+            issuer: issuer.to_string(),
+            serial: "01".to_string(),
+            not_before,
+            not_after: not_before + 90 * DAY,
+            first_seen: not_before,
+            last_seen: not_before,
+            provenance: CertificateProvenance::Observed,
+            chain: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_routine_renewal_is_not_flagged() {
+        let mut history = CertificateHistory::default();
+        assert!(history.record("example.com", observation("a", "k1", "C=US, O=Let's Encrypt, CN=R3", 0)).is_empty());
+        // Same fingerprint again only refreshes last_seen
+        assert!(history.record("example.com", observation("a", "k1", "C=US, O=Let's Encrypt, CN=R3", 0)).is_empty());
+        // New intermediate of the same CA, renewed 60 days later
+        let anomalies = history.record("example.com", observation("b", "k2", "C=US, O=Let's Encrypt, CN=R10", 60 * DAY));
+        assert!(anomalies.is_empty());
+        assert_eq!(history.domains["example.com"].len(), 2);
+
+        // Alternating between two known certificates is not churn
+        assert!(history.record("example.com", observation("a", "k1", "C=US, O=Let's Encrypt, CN=R3", 0)).is_empty());
+        assert_eq!(history.domains["example.com"].len(), 2);
+        assert_eq!(history.latest("example.com").unwrap().fingerprint, "a");
+    }
+
+    #[test]
+    fn test_issuer_switch_and_rapid_reissue() {
+        let mut history = CertificateHistory::default();
+        history.record("example.com", observation("a", "k1", "C=US, O=Let's Encrypt, CN=R3", 10 * DAY));
+        let anomalies = history.record("example.com", observation("b", "k2", "C=PL, O=Cheap CA, CN=DV", 11 * DAY));
+
+        assert_eq!(anomalies, vec![
+            CertificateAnomaly::IssuerChanged {
+                previous: "C=US, O=Let's Encrypt, CN=R3".to_string(),
+                current: "C=PL, O=Cheap CA, CN=DV".to_string(),
+            },
+            CertificateAnomaly::RapidReissue { gap_secs: DAY },
+        ]);
+    }
+
+    #[test]
+    fn test_key_reuse_across_unrelated_domains() {
+        let mut history = CertificateHistory::default();
+        history.record("example.com", observation("a", "shared", "O=CA", 0));
+        assert!(history.record("www.example.com", observation("b", "shared", "O=CA", 0)).is_empty());
+
+        let anomalies = history.record("examp1e-wallet.io", observation("c", "shared", "O=CA", 0));
+        assert_eq!(anomalies, vec![CertificateAnomaly::KeyReusedAcrossDomains {
+            domains: vec!["example.com".to_string(), "www.example.com".to_string()],
+        }]);
+
+        // Neighbours under a multi-label public suffix belong to different owners
+        history.record("shop-a.co.uk", observation("d", "uk-key", "O=CA", 0));
+        let anomalies = history.record("shop-b.co.uk", observation("e", "uk-key", "O=CA", 0));
+        assert_eq!(anomalies, vec![CertificateAnomaly::KeyReusedAcrossDomains { domains: vec!["shop-a.co.uk".to_string()] }]);
+    }
+
+    #[tokio::test]
+    async fn test_history_persists() {
+        let path = std::env::temp_dir().join(format!("poa_history_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let history = Mutex::new(CertificateHistory::load(path).unwrap());
+        history.lock().unwrap().record("example.com", observation("a", "k1", "O=CA", 0));
+        save(&history).await.unwrap();
+
+        let reloaded = CertificateHistory::load(path).unwrap();
+        assert_eq!(reloaded.latest("example.com").unwrap().fingerprint, "a");
+        fs::remove_file(path).unwrap();
+    }
+}
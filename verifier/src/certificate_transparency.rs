@@ -0,0 +1,403 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use ring::signature::{self, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use x509_parser::extensions::ParsedExtension;
+use x509_parser::extensions::{parse_ct_signed_certificate_timestamp, parse_ct_signed_certificate_timestamp_list, SignedCertificateTimestamp};
+use x509_parser::prelude::{FromDer, SubjectPublicKeyInfo, X509Certificate};
+
+use crate::der::{self, TAG_SEQUENCE};
+use crate::ocsp;
+
+/// DER content of the embedded SCT list extension OID (1.3.6.1.4.1.11129.2.4.2)
+const OID_SCT_LIST: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0xd6, 0x79, 0x02, 0x04, 0x02];
+/// DER content of the OCSP SCT list extension OID (1.3.6.1.4.1.11129.2.4.5)
+const OID_OCSP_SCT_LIST: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0xd6, 0x79, 0x02, 0x04, 0x05];
+
+/// A Certificate Transparency log from a log list file
+#[derive(Debug, Clone)]
+pub struct CtLog {
+    pub description: String,
+    pub operator: String,
+    pub log_id: Vec<u8>,
+    /// DER-encoded SubjectPublicKeyInfo of the log
+    pub key: Vec<u8>,
+}
+
+/// CT logs loaded from a JSON log list (the `log_list.json` v3 schema)
+#[derive(Debug, Clone)]
+pub struct CtLogList {
+    pub source: String,
+    pub logs: Vec<CtLog>,
+}
+
+#[derive(Deserialize)]
+struct LogListFile {
+    operators: Vec<LogListOperator>,
+}
+
+#[derive(Deserialize)]
+struct LogListOperator {
+    name: String,
+    #[serde(default)]
+    logs: Vec<LogListEntry>,
+}
+
+#[derive(Deserialize)]
+struct LogListEntry {
+    description: String,
+    log_id: String,
+    key: String,
+}
+
+impl CtLogList {
+    pub fn from_file(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read CT log list: {}", path))?;
+        let file: LogListFile = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse CT log list: {}", path))?;
+
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let mut logs = Vec::new();
+        for operator in file.operators {
+            for log in operator.logs {
+                logs.push(CtLog {
+                    description: log.description,
+                    operator: operator.name.clone(),
+                    log_id: b64.decode(&log.log_id).with_context(|| format!("Invalid log_id in {}", path))?,
+                    key: b64.decode(&log.key).with_context(|| format!("Invalid key in {}", path))?,
+                });
+            }
+        }
+        Ok(Self { source: path.to_string(), logs })
+    }
+
+    fn find(&self, log_id: &[u8]) -> Option<&CtLog> {
+        self.logs.iter().find(|log| log.log_id == log_id)
+    }
+}
+
+/// Where an SCT was delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SctSource {
+    Embedded,
+    TlsExtension,
+    OcspStaple,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SctStatus {
+    /// Signature verified with the key of a known log
+    Valid,
+    InvalidSignature,
+    /// The SCT names a log that is not in the log list
+    UnknownLog,
+    /// No log list was configured, so the signature was not checked
+    Unverified,
+    Unsupported(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SctVerification {
+    pub source: SctSource,
+    pub log_id: String,
+    pub log_description: Option<String>,
+    pub log_operator: Option<String>,
+    /// Milliseconds since the Unix epoch, as recorded by the log
+    pub timestamp: u64,
+    pub status: SctStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateTransparencyReport {
+    pub log_list: Option<String>,
+    pub scts: Vec<SctVerification>,
+    /// SCTs or SCT lists that could not be parsed, by where they were delivered
+    #[serde(default)]
+    pub malformed: Vec<String>,
+}
+
+impl CertificateTransparencyReport {
+    pub fn valid_count(&self) -> usize {
+        self.scts.iter().filter(|s| s.status == SctStatus::Valid).count()
+    }
+
+    /// An SCT failed its signature check or could not be parsed; logs never issue either
+    pub fn has_invalid(&self) -> bool {
+        !self.malformed.is_empty() || self.scts.iter().any(|s| s.status == SctStatus::InvalidSignature)
+    }
+
+    /// Descriptions of the logs that issued a verified SCT for this certificate
+    pub fn logs(&self) -> Vec<String> {
+        let mut logs: Vec<String> = self.scts.iter()
+            .filter(|s| s.status == SctStatus::Valid)
+            .filter_map(|s| s.log_description.clone())
+            .collect();
+        logs.sort();
+        logs.dedup();
+        logs
+    }
+}
+
+/// The certificate among those presented after the leaf whose subject is the leaf's issuer; servers
+/// send chains out of order, or with cross-signed extras in between
+pub fn issuer_of<'a>(leaf: &[u8], presented: &'a [Vec<u8>]) -> Option<&'a [u8]> {
+    let (_, leaf) = X509Certificate::from_der(leaf).ok()?;
+    presented.iter()
+        .skip(1)
+        .find(|der| X509Certificate::from_der(der)
+            .is_ok_and(|(_, cert)| cert.subject().as_raw() == leaf.issuer().as_raw()))
+        .map(Vec::as_slice)
+}
+
+/// Collect and verify the SCTs for `leaf` from all three delivery mechanisms
+pub fn verify_scts(
+    leaf: &[u8],
+    issuer: Option<&[u8]>,
+    tls_scts: &[Vec<u8>],
+    ocsp_staple: Option<&[u8]>,
+    log_list: Option<&CtLogList>,
+) -> Result<CertificateTransparencyReport> {
+    let (_, cert) = X509Certificate::from_der(leaf)
+        .map_err(|e| anyhow::anyhow!("Failed to parse leaf certificate: {}", e))?;
+    let issuer_key_hash = match issuer {
+        Some(der) => X509Certificate::from_der(der)
+            .ok()
+            .map(|(_, issuer)| Sha256::digest(issuer.public_key().raw).to_vec()),
+        None => None,
+    };
+
+    let mut scts = Vec::new();
+    let mut malformed = Vec::new();
+
+    let mut embedded: Vec<&SignedCertificateTimestamp> = Vec::new();
+    for ext in cert.extensions() {
+        match ext.parsed_extension() {
+            ParsedExtension::SCT(list) => embedded.extend(list),
+            ParsedExtension::ParseError { error } if ext.oid.as_bytes() == OID_SCT_LIST => {
+                malformed.push(format!("embedded SCT list: {}", error));
+            }
+            _ => {}
+        }
+    }
+    if !embedded.is_empty() {
+        let precert_entry = match &issuer_key_hash {
+            Some(hash) => precert_signed_entry(hash, cert.tbs_certificate.as_ref())
+                .map_err(|e| SctStatus::Unsupported(format!("precertificate entry: {}", e))),
+            None => Err(SctStatus::Unsupported("issuer certificate unavailable".to_string())),
+        };
+        for sct in embedded {
+            let status = match &precert_entry {
+                Ok(entry) => check_sct(sct, 1, entry, log_list),
+                Err(status) => status.clone(),
+            };
+            scts.push(describe(sct, SctSource::Embedded, status, log_list));
+        }
+    }
+
+    let x509_entry = x509_signed_entry(leaf);
+    for (index, raw) in tls_scts.iter().enumerate() {
+        let mut framed = (raw.len() as u16).to_be_bytes().to_vec();
+        framed.extend_from_slice(raw);
+        match parse_ct_signed_certificate_timestamp(&framed) {
+            Ok((_, sct)) => {
+                let status = check_sct(&sct, 0, &x509_entry, log_list);
+                scts.push(describe(&sct, SctSource::TlsExtension, status, log_list));
+            }
+            Err(e) => malformed.push(format!("TLS extension SCT {}: {}", index + 1, e)),
+        }
+    }
+
+    if let Some(staple) = ocsp_staple {
+        if let Ok(response) = ocsp::parse_ocsp_response(staple) {
+            for single in response.responses.iter().filter(|r| r.serial_number == cert.raw_serial()) {
+                for ext in single.extensions.iter().filter(|e| e.oid == OID_OCSP_SCT_LIST) {
+                    match parse_ct_signed_certificate_timestamp_list(ext.value) {
+                        Ok((_, list)) => {
+                            for sct in &list {
+                                let status = check_sct(sct, 0, &x509_entry, log_list);
+                                scts.push(describe(sct, SctSource::OcspStaple, status, log_list));
+                            }
+                        }
+                        Err(e) => malformed.push(format!("OCSP staple SCT list: {}", e)),
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(CertificateTransparencyReport {
+        log_list: log_list.map(|l| l.source.clone()),
+        scts,
+        malformed,
+    })
+}
+
+fn describe(sct: &SignedCertificateTimestamp, source: SctSource, status: SctStatus, log_list: Option<&CtLogList>) -> SctVerification {
+    let log = log_list.and_then(|l| l.find(sct.id.key_id));
+    SctVerification {
+        source,
+        log_id: base64::engine::general_purpose::STANDARD.encode(sct.id.key_id),
+        log_description: log.map(|l| l.description.clone()),
+        log_operator: log.map(|l| l.operator.clone()),
+        timestamp: sct.timestamp,
+        status,
+    }
+}
+
+/// Verify one SCT over `signed_entry` (RFC 6962 section 3.2)
+fn check_sct(sct: &SignedCertificateTimestamp, entry_type: u16, signed_entry: &[u8], log_list: Option<&CtLogList>) -> SctStatus {
+    let Some(log_list) = log_list else {
+        return SctStatus::Unverified;
+    };
+    let Some(log) = log_list.find(sct.id.key_id) else {
+        return SctStatus::UnknownLog;
+    };
+    if sct.version.0 != 0 {
+        return SctStatus::Unsupported(format!("SCT version {}", sct.version.0));
+    }
+
+    let mut message = vec![0u8, 0u8];
+    message.extend_from_slice(&sct.timestamp.to_be_bytes());
+    message.extend_from_slice(&entry_type.to_be_bytes());
+    message.extend_from_slice(signed_entry);
+    message.extend_from_slice(&(sct.extensions.0.len() as u16).to_be_bytes());
+    message.extend_from_slice(sct.extensions.0);
+
+    let algorithm: &dyn signature::VerificationAlgorithm = match (sct.signature.hash_alg_id, sct.signature.sign_alg_id) {
+        (4, 3) => &signature::ECDSA_P256_SHA256_ASN1,
+        (4, 1) => &signature::RSA_PKCS1_2048_8192_SHA256,
+        (hash, sign) => return SctStatus::Unsupported(format!("hash {} / signature {}", hash, sign)),
+    };
+    let Ok((_, spki)) = SubjectPublicKeyInfo::from_der(&log.key) else {
+        return SctStatus::Unsupported("unparsable log key".to_string());
+    };
+
+    match UnparsedPublicKey::new(algorithm, &spki.subject_public_key.data).verify(&message, sct.signature.data) {
+        Ok(()) => SctStatus::Valid,
+        Err(_) => SctStatus::InvalidSignature,
+    }
+}
+
+fn x509_signed_entry(leaf: &[u8]) -> Vec<u8> {
+    let mut entry = (leaf.len() as u32).to_be_bytes()[1..].to_vec();
+    entry.extend_from_slice(leaf);
+    entry
+}
+
+/// `PreCert` entry: issuer key hash followed by the TBSCertificate without the SCT list extension
+fn precert_signed_entry(issuer_key_hash: &[u8], tbs: &[u8]) -> Result<Vec<u8>> {
+    let precert_tbs = strip_sct_extension(tbs)?;
+    let mut entry = issuer_key_hash.to_vec();
+    entry.extend_from_slice(&(precert_tbs.len() as u32).to_be_bytes()[1..]);
+    entry.extend_from_slice(&precert_tbs);
+    Ok(entry)
+}
+
+fn strip_sct_extension(tbs: &[u8]) -> Result<Vec<u8>> {
+    let (tbs, _) = der::read_tlv(tbs)?;
+    let mut content = Vec::new();
+    for field in der::children(tbs.content)? {
+        if field.tag != 0xa3 {
+            content.extend_from_slice(field.raw);
+            continue;
+        }
+        // extensions [3] EXPLICIT SEQUENCE OF Extension
+        let (extensions, _) = der::read_tlv(field.content)?;
+        let mut kept = Vec::new();
+        for ext in der::children(extensions.content)? {
+            let is_sct = der::children(ext.content)?
+                .first()
+                .map(|oid| oid.content == OID_SCT_LIST)
+                .unwrap_or(false);
+            if !is_sct {
+                kept.extend_from_slice(ext.raw);
+            }
+        }
+        if !kept.is_empty() {
+            content.extend(der::encode_tlv(0xa3, &der::encode_tlv(TAG_SEQUENCE, &kept)));
+        }
+    }
+    Ok(der::encode_tlv(TAG_SEQUENCE, &content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_validation::load_certificates;
+
+    fn fixture(name: &str) -> String {
+        format!("{}/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    #[test]
+    fn test_embedded_sct_verifies_against_log_list() {
+        let chain = load_certificates(&fixture("chain.pem")).unwrap();
+        let logs = CtLogList::from_file(&fixture("ct_log_list.json")).unwrap();
+
+        let report = verify_scts(&chain[0], Some(&chain[1]), &[], None, Some(&logs)).unwrap();
+        assert_eq!(report.scts.len(), 1);
+        assert_eq!(report.scts[0].source, SctSource::Embedded);
+        assert_eq!(report.scts[0].status, SctStatus::Valid);
+        assert_eq!(report.logs(), vec!["PoA Test Log 2024".to_string()]);
+    }
+
+    #[test]
+    fn test_issuer_is_found_by_name_in_an_unordered_chain() {
+        let chain = load_certificates(&fixture("chain.pem")).unwrap();
+        let root = load_certificates(&fixture("root.pem")).unwrap();
+        let logs = CtLogList::from_file(&fixture("ct_log_list.json")).unwrap();
+
+        let presented = vec![chain[0].clone(), root[0].clone(), chain[1].clone()];
+        let issuer = issuer_of(&presented[0], &presented);
+        assert_eq!(issuer, Some(chain[1].as_slice()));
+        let report = verify_scts(&presented[0], issuer, &[], None, Some(&logs)).unwrap();
+        assert_eq!(report.valid_count(), 1);
+
+        assert_eq!(issuer_of(&chain[0], &[chain[0].clone(), root[0].clone()]), None);
+    }
+
+    #[test]
+    fn test_malformed_embedded_sct_list_is_reported() {
+        let cert = load_certificates(&fixture("malformed_sct.pem")).unwrap();
+        let report = verify_scts(&cert[0], None, &[], None, None).unwrap();
+        assert!(report.scts.is_empty());
+        assert_eq!(report.malformed.len(), 1);
+        assert!(report.malformed[0].starts_with("embedded SCT list: "), "{:?}", report.malformed);
+        assert!(report.has_invalid());
+    }
+
+    #[test]
+    fn test_wrong_issuer_invalidates_sct() {
+        let chain = load_certificates(&fixture("chain.pem")).unwrap();
+        let root = load_certificates(&fixture("root.pem")).unwrap();
+        let logs = CtLogList::from_file(&fixture("ct_log_list.json")).unwrap();
+
+        let report = verify_scts(&chain[0], Some(&root[0]), &[], None, Some(&logs)).unwrap();
+        assert!(report.has_invalid());
+        assert_eq!(report.valid_count(), 0);
+    }
+
+    #[test]
+    fn test_missing_scts_and_no_log_list() {
+        let self_signed = load_certificates(&fixture("self_signed.pem")).unwrap();
+        let report = verify_scts(&self_signed[0], None, &[], None, None).unwrap();
+        assert!(report.scts.is_empty());
+
+        let chain = load_certificates(&fixture("chain.pem")).unwrap();
+        let report = verify_scts(&chain[0], Some(&chain[1]), &[], None, None).unwrap();
+        assert_eq!(report.scts[0].status, SctStatus::Unverified);
+    }
+
+    #[test]
+    fn test_malformed_tls_sct_is_reported() {
+        let chain = load_certificates(&fixture("chain.pem")).unwrap();
+        let report = verify_scts(&chain[0], Some(&chain[1]), &[vec![0x00, 0x01, 0x02]], None, None).unwrap();
+        assert_eq!(report.scts.len(), 1);
+        assert_eq!(report.malformed.len(), 1);
+        assert!(report.malformed[0].starts_with("TLS extension SCT 1: "), "{:?}", report.malformed);
+        assert!(report.has_invalid());
+    }
+}
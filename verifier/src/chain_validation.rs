@@ -0,0 +1,394 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use x509_parser::oid_registry::{OID_EC_P256, OID_KEY_TYPE_EC_PUBLIC_KEY, OID_NIST_EC_P384, OID_NIST_EC_P521, OID_PKCS1_RSAENCRYPTION, OID_SIG_ED25519};
+use x509_parser::extensions::GeneralName;
+use x509_parser::objects::{oid2sn, oid_registry};
+use x509_parser::pem::Pem;
+use x509_parser::prelude::{ASN1Time, FromDer, X509Certificate};
+
+/// Set of trust anchors used for certificate path validation
+#[derive(Debug, Clone)]
+pub struct TrustStore {
+    /// Human readable origin of the anchors ("native" or a file path)
+    pub source: String,
+    /// DER-encoded root certificates
+    pub anchors: Vec<Vec<u8>>,
+}
+
+impl TrustStore {
+    /// Load the operating system trust store
+    pub fn native() -> Self {
+        let anchors = match rustls_native_certs::load_native_certs() {
+            Ok(certs) => certs.into_iter().map(|c| c.0).collect(),
+            Err(e) => {
+                println!("⚠️ Could not load native root certificates: {}", e);
+                Vec::new()
+            }
+        };
+        Self { source: "native".to_string(), anchors }
+    }
+
+    /// Load trust anchors from a PEM bundle or a single DER certificate
+    pub fn from_file(path: &str) -> Result<Self> {
+        let anchors = load_certificates(path)?;
+        if anchors.is_empty() {
+            return Err(anyhow::anyhow!("Trust store {} contains no certificates", path));
+        }
+        Ok(Self { source: path.to_string(), anchors })
+    }
+
+    /// Convert the anchors into a rustls root store for the handshake verifier
+    pub fn to_root_store(&self) -> rustls::RootCertStore {
+        let mut roots = rustls::RootCertStore::empty();
+        let (_, ignored) = roots.add_parsable_certificates(&self.anchors);
+        if ignored > 0 {
+            println!("⚠️ Ignored {} unparsable trust anchors from {}", ignored, self.source);
+        }
+        roots
+    }
+}
+
+/// Read every certificate from a PEM file, falling back to a single DER certificate
+pub fn load_certificates(path: &str) -> Result<Vec<Vec<u8>>> {
+    let data = fs::read(path).with_context(|| format!("Failed to read certificate file: {}", path))?;
+    parse_certificates(&data).with_context(|| format!("Failed to parse certificates in {}", path))
+}
+
+/// Parse PEM-encoded certificates, or treat the input as a single DER certificate
+pub fn parse_certificates(data: &[u8]) -> Result<Vec<Vec<u8>>> {
+    if data.starts_with(b"-----") || data.windows(11).any(|w| w == b"-----BEGIN ") {
+        let mut certs = Vec::new();
+        for pem in Pem::iter_from_buffer(data) {
+            let pem = pem.map_err(|e| anyhow::anyhow!("Invalid PEM block: {}", e))?;
+            if pem.label == "CERTIFICATE" {
+                certs.push(pem.contents);
+            }
+        }
+        Ok(certs)
+    } else {
+        X509Certificate::from_der(data)
+            .map_err(|e| anyhow::anyhow!("Invalid DER certificate: {}", e))?;
+        Ok(vec![data.to_vec()])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HopRole {
+    Leaf,
+    Intermediate,
+    Root,
+}
+
+/// Specific reason a certificate path was not trusted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationFailure {
+    /// The certificate signs itself but is not a trust anchor
+    SelfSigned,
+    /// No presented certificate or trust anchor issued this certificate
+    UnknownIssuer { issuer: String },
+    /// The leaf does not cover the requested host name
+    NameMismatch { expected: String, names: Vec<String> },
+    Expired { not_after: u64 },
+    NotYetValid { not_before: u64 },
+    /// The issuer was found but its key does not verify this certificate's signature
+    BadSignature { issuer: String },
+    /// An issuing certificate is not marked as a CA
+    IssuerNotCa { issuer: String },
+    /// The handshake verifier rejected the chain for a reason not covered above
+    HandshakeRejected(String),
+    Unparsable(String),
+}
+
+impl std::fmt::Display for ValidationFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SelfSigned => write!(f, "self-signed certificate is not a trusted root"),
+            Self::UnknownIssuer { issuer } => write!(f, "unknown issuer (missing intermediate or untrusted root): {}", issuer),
+            Self::NameMismatch { expected, names } => write!(f, "name mismatch: {} not in [{}]", expected, names.join(", ")),
+            Self::Expired { not_after } => write!(f, "expired at {}", not_after),
+            Self::NotYetValid { not_before } => write!(f, "not valid before {}", not_before),
+            Self::BadSignature { issuer } => write!(f, "signature does not verify against issuer {}", issuer),
+            Self::IssuerNotCa { issuer } => write!(f, "issuer {} is not a CA certificate", issuer),
+            Self::HandshakeRejected(reason) => write!(f, "rejected during handshake: {}", reason),
+            Self::Unparsable(reason) => write!(f, "unparsable certificate: {}", reason),
+        }
+    }
+}
+
+/// One certificate in the validated path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateHop {
+    pub role: HopRole,
+    pub subject: String,
+    pub issuer: String,
+    pub key_type: String,
+    pub key_size: usize,
+    pub signature_algorithm: String,
+    /// False when the hop was taken from the trust store rather than presented by the server
+    pub presented: bool,
+    pub failures: Vec<ValidationFailure>,
+}
+
+/// Path validation result for a presented certificate chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainValidationReport {
+    pub trust_store: String,
+    pub hops: Vec<CertificateHop>,
+    pub is_trusted: bool,
+}
+
+impl ChainValidationReport {
+    /// All failures across the path, prefixed with the subject they apply to
+    pub fn failure_reasons(&self) -> Vec<String> {
+        self.hops.iter()
+            .flat_map(|hop| hop.failures.iter().map(move |f| format!("{}: {}", hop.subject, f)))
+            .collect()
+    }
+}
+
+/// Validate a presented chain (leaf first) for `domain` against `trust_store` at time `now`
+pub fn validate_chain(domain: &str, presented: &[Vec<u8>], trust_store: &TrustStore, now: u64) -> ChainValidationReport {
+    let mut parsed = Vec::new();
+    for der in presented {
+        match X509Certificate::from_der(der) {
+            Ok((_, cert)) => parsed.push(cert),
+            Err(e) => {
+                return ChainValidationReport {
+                    trust_store: trust_store.source.clone(),
+                    hops: vec![CertificateHop {
+                        role: if parsed.is_empty() { HopRole::Leaf } else { HopRole::Intermediate },
+                        subject: "<unparsable>".to_string(),
+                        issuer: String::new(),
+                        key_type: String::new(),
+                        key_size: 0,
+                        signature_algorithm: String::new(),
+                        presented: true,
+                        failures: vec![ValidationFailure::Unparsable(e.to_string())],
+                    }],
+                    is_trusted: false,
+                };
+            }
+        }
+    }
+    let anchors: Vec<X509Certificate> = trust_store.anchors.iter()
+        .filter_map(|der| X509Certificate::from_der(der).ok().map(|(_, c)| c))
+        .collect();
+
+    let mut hops = Vec::new();
+    if parsed.is_empty() {
+        return ChainValidationReport { trust_store: trust_store.source.clone(), hops, is_trusted: false };
+    }
+
+    let now_asn1 = ASN1Time::from_timestamp(now as i64).unwrap_or_else(|_| ASN1Time::now());
+    let mut current = 0usize;
+    let mut used = vec![false; parsed.len()];
+    used[0] = true;
+
+    loop {
+        let cert = &parsed[current];
+        let mut hop = describe(cert, if current == 0 { HopRole::Leaf } else { HopRole::Intermediate }, true);
+        check_validity(cert, now_asn1, &mut hop);
+        if current == 0 {
+            check_name(domain, cert, &mut hop);
+        }
+
+        if is_anchor(cert, &anchors) {
+            hop.role = if current == 0 { HopRole::Leaf } else { HopRole::Root };
+            hops.push(hop);
+            break;
+        }
+
+        if is_self_signed(cert) {
+            hop.failures.push(ValidationFailure::SelfSigned);
+            hops.push(hop);
+            break;
+        }
+
+        // Prefer the next presented certificate, then anchors, matching on subject DN
+        let next_presented = (0..parsed.len())
+            .find(|&i| !used[i] && parsed[i].subject().as_raw() == cert.issuer().as_raw());
+        if let Some(i) = next_presented {
+            check_issued_by(cert, &parsed[i], &mut hop);
+            hops.push(hop);
+            used[i] = true;
+            current = i;
+            continue;
+        }
+
+        match anchors.iter().find(|a| a.subject().as_raw() == cert.issuer().as_raw()) {
+            Some(anchor) => {
+                check_issued_by(cert, anchor, &mut hop);
+                hops.push(hop);
+                let mut root = describe(anchor, HopRole::Root, false);
+                check_validity(anchor, now_asn1, &mut root);
+                hops.push(root);
+            }
+            None => {
+                hop.failures.push(ValidationFailure::UnknownIssuer { issuer: cert.issuer().to_string() });
+                hops.push(hop);
+            }
+        }
+        break;
+    }
+
+    let is_trusted = hops.iter().all(|hop| hop.failures.is_empty());
+    ChainValidationReport {
+        trust_store: trust_store.source.clone(),
+        hops,
+        is_trusted,
+    }
+}
+
+fn describe(cert: &X509Certificate, role: HopRole, presented: bool) -> CertificateHop {
+    let (key_type, key_size) = key_description(cert);
+    let signature_algorithm = oid2sn(&cert.signature_algorithm.algorithm, oid_registry())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|_| cert.signature_algorithm.algorithm.to_id_string());
+    CertificateHop {
+        role,
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        key_type,
+        key_size,
+        signature_algorithm,
+        presented,
+        failures: Vec::new(),
+    }
+}
+
+/// Key algorithm name and size in bits for a certificate's public key
+pub fn key_description(cert: &X509Certificate) -> (String, usize) {
+    let spki = cert.public_key();
+    let size = spki.parsed().map(|k| k.key_size()).unwrap_or(0);
+    let algorithm = &spki.algorithm.algorithm;
+    if *algorithm == OID_PKCS1_RSAENCRYPTION {
+        ("RSA".to_string(), size)
+    } else if *algorithm == OID_KEY_TYPE_EC_PUBLIC_KEY {
+        let curve = spki.algorithm.parameters.as_ref().and_then(|p| p.as_oid().ok());
+        let name = match curve {
+            Some(oid) if oid == OID_EC_P256 => "EC P-256",
+            Some(oid) if oid == OID_NIST_EC_P384 => "EC P-384",
+            Some(oid) if oid == OID_NIST_EC_P521 => "EC P-521",
+            _ => "EC",
+        };
+        (name.to_string(), size)
+    } else if *algorithm == OID_SIG_ED25519 {
+        ("Ed25519".to_string(), 256)
+    } else {
+        (algorithm.to_id_string(), size)
+    }
+}
+
+fn check_validity(cert: &X509Certificate, now: ASN1Time, hop: &mut CertificateHop) {
+    let validity = cert.validity();
+    if now > validity.not_after {
+        hop.failures.push(ValidationFailure::Expired { not_after: validity.not_after.timestamp().max(0) as u64 });
+    } else if now < validity.not_before {
+        hop.failures.push(ValidationFailure::NotYetValid { not_before: validity.not_before.timestamp().max(0) as u64 });
+    }
+}
+
+fn check_name(domain: &str, cert: &X509Certificate, hop: &mut CertificateHop) {
+    let names: Vec<String> = match cert.subject_alternative_name() {
+        Ok(Some(san)) => san.value.general_names.iter()
+            .filter_map(|n| match n {
+                GeneralName::DNSName(dns) => Some(dns.to_string()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    if !names.iter().any(|name| hostname_matches(name, domain)) {
+        hop.failures.push(ValidationFailure::NameMismatch { expected: domain.to_string(), names });
+    }
+}
+
+/// Match a host name against a certificate DNS name, allowing a single left-most wildcard label
+pub fn hostname_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.trim_end_matches('.').to_ascii_lowercase();
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => host
+            .split_once('.')
+            .map(|(label, rest)| !label.is_empty() && rest == suffix)
+            .unwrap_or(false),
+        None => pattern == host,
+    }
+}
+
+fn check_issued_by(cert: &X509Certificate, issuer: &X509Certificate, hop: &mut CertificateHop) {
+    if !issuer.is_ca() {
+        hop.failures.push(ValidationFailure::IssuerNotCa { issuer: issuer.subject().to_string() });
+    }
+    if cert.verify_signature(Some(issuer.public_key())).is_err() {
+        hop.failures.push(ValidationFailure::BadSignature { issuer: issuer.subject().to_string() });
+    }
+}
+
+fn is_self_signed(cert: &X509Certificate) -> bool {
+    cert.subject().as_raw() == cert.issuer().as_raw() && cert.verify_signature(None).is_ok()
+}
+
+fn is_anchor(cert: &X509Certificate, anchors: &[X509Certificate]) -> bool {
+    anchors.iter().any(|a| {
+        a.subject().as_raw() == cert.subject().as_raw() && a.public_key().raw == cert.public_key().raw
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1735689600;
+
+    fn fixture_store() -> TrustStore {
+        TrustStore::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/root.pem")).unwrap()
+    }
+
+    fn fixture_chain() -> Vec<Vec<u8>> {
+        load_certificates(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/chain.pem")).unwrap()
+    }
+
+    #[test]
+    fn test_trusted_chain_includes_root() {
+        let report = validate_chain("www.example-project.com", &fixture_chain(), &fixture_store(), NOW);
+        assert!(report.is_trusted, "{:?}", report.failure_reasons());
+
+        let roles: Vec<HopRole> = report.hops.iter().map(|h| h.role).collect();
+        assert_eq!(roles, vec![HopRole::Leaf, HopRole::Intermediate, HopRole::Root]);
+        assert!(!report.hops[2].presented);
+        assert_eq!(report.hops[0].key_type, "EC P-256");
+        assert_eq!(report.hops[0].key_size, 256);
+        assert_eq!(report.hops[0].signature_algorithm, "ecdsa-with-SHA256");
+    }
+
+    #[test]
+    fn test_missing_intermediate_and_name_mismatch() {
+        let leaf_only = vec![fixture_chain().remove(0)];
+        let report = validate_chain("phishing.example", &leaf_only, &fixture_store(), NOW);
+        assert!(!report.is_trusted);
+
+        let failures = &report.hops[0].failures;
+        assert!(failures.iter().any(|f| matches!(f, ValidationFailure::NameMismatch { .. })));
+        assert!(failures.iter().any(|f| matches!(f, ValidationFailure::UnknownIssuer { .. })));
+    }
+
+    #[test]
+    fn test_self_signed_and_expired() {
+        let self_signed = load_certificates(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/self_signed.pem")).unwrap();
+        let report = validate_chain("example-project.com", &self_signed, &fixture_store(), NOW);
+        assert_eq!(report.hops[0].failures, vec![ValidationFailure::SelfSigned]);
+
+        let report = validate_chain("example-project.com", &fixture_chain(), &fixture_store(), 4_900_000_000);
+        assert!(report.hops[0].failures.iter().any(|f| matches!(f, ValidationFailure::Expired { .. })));
+    }
+
+    #[test]
+    fn test_hostname_matches() {
+        assert!(hostname_matches("*.example.com", "www.example.com"));
+        assert!(!hostname_matches("*.example.com", "example.com"));
+        assert!(!hostname_matches("*.example.com", "a.b.example.com"));
+        assert!(hostname_matches("Example.com.", "example.com"));
+    }
+}
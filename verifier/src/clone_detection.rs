@@ -0,0 +1,263 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::github::GitHubClient;
+use crate::repo_health::RepositoryHealth;
+
+/// Share of a repository's files identical to an upstream's that makes it a copy
+const COPY_FILE_SIMILARITY: f64 = 0.8;
+/// A near-identical layout is a copy with fewer identical files, since copiers rename tokens in place
+const COPY_TREE_SIMILARITY: f64 = 0.9;
+const COPY_FILE_SIMILARITY_WITH_SAME_TREE: f64 = 0.5;
+/// Commits listed per repository; more are reported as "at least"
+const MAX_COUNTED_COMMITS: u32 = 100;
+/// Git's hash of an empty file, shared by every repository
+const EMPTY_BLOB: &str = "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391";
+/// Files every project has, which say nothing about where the code came from
+const BOILERPLATE_FILES: &[&str] = &[
+    "license", "license.md", "license.txt", ".gitignore", ".gitattributes", ".editorconfig",
+    ".prettierrc", ".npmignore", "code_of_conduct.md",
+];
+
+/// A known upstream repository: file paths and their git blob hashes, as listed by `git ls-tree -r HEAD`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpstreamRepository {
+    pub repository: String,
+    pub files: BTreeMap<String, String>,
+}
+
+/// Upstream repositories that scam projects commonly copy, loaded from a JSON array of `UpstreamRepository`
+#[derive(Debug, Clone, Default)]
+pub struct UpstreamCorpus {
+    repositories: Vec<UpstreamRepository>,
+}
+
+impl UpstreamCorpus {
+    pub fn from_file(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read upstream corpus {}", path))?;
+        Self::parse(&content).with_context(|| format!("Invalid upstream corpus {}", path))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(Self { repositories: serde_json::from_str(content)? })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.repositories.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RepositoryOrigin {
+    Original,
+    Fork { parent: String },
+    FromTemplate { template: String },
+    /// Not a GitHub fork, but the files match a known upstream
+    Copy {
+        upstream: String,
+        /// Share of the repository's files identical to the upstream's, 0.0 to 1.0
+        file_similarity: f64,
+        /// Overlap of the two path sets (Jaccard index), 0.0 to 1.0
+        tree_similarity: f64,
+    },
+}
+
+/// Where a repository's code came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OriginReport {
+    pub repository: String,
+    pub origin: RepositoryOrigin,
+    /// Commits on the default branch, counted up to `MAX_COUNTED_COMMITS`
+    pub commits: Option<u32>,
+    /// Files compared against the corpus, boilerplate excluded; 0 when copy detection was skipped
+    pub files_compared: usize,
+}
+
+impl std::fmt::Display for OriginReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.origin {
+            RepositoryOrigin::Original if self.files_compared == 0 => {
+                write!(f, "{} is not a fork or template instance; its files were not compared against any upstream", self.repository)?
+            }
+            RepositoryOrigin::Original => write!(f, "{} is original development", self.repository)?,
+            RepositoryOrigin::Fork { parent } => write!(f, "{} is a fork of {}", self.repository, parent)?,
+            RepositoryOrigin::FromTemplate { template } => {
+                write!(f, "{} was generated from template {}", self.repository, template)?
+            }
+            RepositoryOrigin::Copy { upstream, file_similarity, .. } => {
+                write!(f, "{} is a {:.0}% copy of {}", self.repository, file_similarity * 100.0, upstream)?
+            }
+        }
+        match self.commits {
+            Some(commits) if commits >= MAX_COUNTED_COMMITS => write!(f, " with {}+ commits", commits),
+            Some(1) => f.write_str(" with 1 commit"),
+            Some(commits) => write!(f, " with {} commits", commits),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitTree {
+    tree: Vec<GitTreeEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitTreeEntry {
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+    sha: String,
+}
+
+fn is_boilerplate(path: &str, sha: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path).to_ascii_lowercase();
+    sha == EMPTY_BLOB || BOILERPLATE_FILES.contains(&name.as_str())
+}
+
+/// Best-matching upstream for `files` (path to blob hash) that passes the copy thresholds
+fn closest_upstream(corpus: &UpstreamCorpus, repository: &str, files: &BTreeMap<String, String>) -> Option<RepositoryOrigin> {
+    if files.is_empty() {
+        return None;
+    }
+    corpus.repositories.iter()
+        // The upstream itself is original
+        .filter(|upstream| !upstream.repository.eq_ignore_ascii_case(repository))
+        .map(|upstream| {
+            let upstream_files: BTreeMap<&str, &str> = upstream.files.iter()
+                .filter(|(path, sha)| !is_boilerplate(path, sha))
+                .map(|(path, sha)| (path.as_str(), sha.as_str()))
+                .collect();
+            let upstream_hashes: HashSet<&str> = upstream_files.values().copied().collect();
+            let identical = files.values().filter(|sha| upstream_hashes.contains(sha.as_str())).count();
+            let shared_paths = files.keys().filter(|path| upstream_files.contains_key(path.as_str())).count();
+            let all_paths = files.len() + upstream_files.len() - shared_paths;
+            let file_similarity = identical as f64 / files.len() as f64;
+            let tree_similarity = shared_paths as f64 / all_paths.max(1) as f64;
+            (upstream, file_similarity, tree_similarity)
+        })
+        .filter(|(_, file_similarity, tree_similarity)| {
+            *file_similarity >= COPY_FILE_SIMILARITY
+                || (*tree_similarity >= COPY_TREE_SIMILARITY && *file_similarity >= COPY_FILE_SIMILARITY_WITH_SAME_TREE)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(upstream, file_similarity, tree_similarity)| RepositoryOrigin::Copy {
+            upstream: upstream.repository.clone(),
+            file_similarity,
+            tree_similarity,
+        })
+}
+
+/// Classify `health.repository` as a fork, a template instance, a copy of a corpus upstream, or original
+pub async fn detect_origin(github: &GitHubClient, corpus: &UpstreamCorpus, health: &RepositoryHealth) -> Result<OriginReport> {
+    let repository = &health.repository;
+    let response = github.get(&format!("/repos/{}/commits?per_page={}", repository, MAX_COUNTED_COMMITS)).await?;
+    let commits = response.is_success()
+        .then(|| response.json::<Vec<serde_json::Value>>().ok())
+        .flatten()
+        .map(|commits| commits.len() as u32);
+
+    let mut files = BTreeMap::new();
+    if !corpus.is_empty() {
+        let response = github.get(&format!("/repos/{}/git/trees/HEAD?recursive=1", repository)).await?;
+        if response.is_success() {
+            let tree: GitTree = response.json()?;
+            files = tree.tree.into_iter()
+                .filter(|entry| entry.kind == "blob" && !is_boilerplate(&entry.path, &entry.sha))
+                .map(|entry| (entry.path, entry.sha))
+                .collect();
+        }
+    }
+
+    // GitHub's own records win; the corpus catches copies pushed as new repositories
+    let origin = if health.is_fork {
+        RepositoryOrigin::Fork { parent: health.parent.clone().unwrap_or_else(|| "an unknown repository".to_string()) }
+    } else if let Some(template) = &health.template_repository {
+        RepositoryOrigin::FromTemplate { template: template.clone() }
+    } else {
+        closest_upstream(corpus, repository, &files).unwrap_or(RepositoryOrigin::Original)
+    };
+    Ok(OriginReport { repository: repository.clone(), origin, commits, files_compared: files.len() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const CORPUS: &str = include_str!("../fixtures/upstream_corpus.json");
+
+    fn health(repository: &str) -> RepositoryHealth {
+        RepositoryHealth::new(repository, 1735689600)
+    }
+
+    #[tokio::test]
+    async fn test_detect_copy_of_known_upstream() {
+        let tree = r#"{"sha": "HEAD", "truncated": false, "tree": [
+            {"path": "contracts", "type": "tree", "sha": "1111111111111111111111111111111111111111"},
+            {"path": "contracts/Factory.sol", "type": "blob", "sha": "3ed927f7a10381b46a5cf8e14fa3fb1ed74f4ea6"},
+            {"path": "contracts/Pair.sol", "type": "blob", "sha": "a90c5f2f1528de31658d42279b5956cac79beebd"},
+            {"path": "contracts/ERC20.sol", "type": "blob", "sha": "bd44a6a91150df98af567aa5e1b8ae48cc4dc462"},
+            {"path": "contracts/libraries/Math.sol", "type": "blob", "sha": "ee5d4cb24f30bbce9ad2eacf890ba36d6f8ae534"},
+            {"path": "contracts/libraries/SafeMath.sol", "type": "blob", "sha": "ca709736c92ce75203078983b68722911aaade32"},
+            {"path": "contracts/libraries/UQ112x112.sol", "type": "blob", "sha": "fb74a26c0b7e51e97cb0a00e4829ac75edafe192"},
+            {"path": "contracts/interfaces/IFactory.sol", "type": "blob", "sha": "dbad62374092d235c3d5c717276d92400c734add"},
+            {"path": "contracts/interfaces/IPair.sol", "type": "blob", "sha": "1c364808995b65caed18e448804c9e8abe89e44a"},
+            {"path": "test/Pair.spec.ts", "type": "blob", "sha": "e3ba2c6164eef9fda84d6cfb17b175a25a50a650"},
+            {"path": "README.md", "type": "blob", "sha": "da32a260251b5e96826ed87e0dda5250924e5ff8"},
+            {"path": "LICENSE", "type": "blob", "sha": "2222222222222222222222222222222222222222"},
+            {"path": ".keep", "type": "blob", "sha": "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391"}]}"#;
+        let server = test_http::serve(vec![
+            ("/repos/scamswap/amm/commits", MockResponse::ok(r#"[{"sha": "c3"}, {"sha": "c2"}, {"sha": "c1"}]"#)),
+            ("/repos/scamswap/amm/git/trees/HEAD", MockResponse::ok(tree)),
+            ("/repos/example-upstream/amm-core/commits", MockResponse::ok(r#"[{"sha": "u1"}]"#)),
+            ("/repos/example-upstream/amm-core/git/trees/HEAD", MockResponse::ok(tree)),
+        ]).await;
+        let github = GitHubClient::new(&server.base_url, None);
+        let corpus = UpstreamCorpus::parse(CORPUS).unwrap();
+
+        let report = detect_origin(&github, &corpus, &health("scamswap/amm")).await.unwrap();
+        assert_eq!(report.files_compared, 10);
+        assert_eq!(report.origin, RepositoryOrigin::Copy {
+            upstream: "example-upstream/amm-core".to_string(),
+            file_similarity: 0.9,
+            tree_similarity: 1.0,
+        });
+        assert_eq!(report.to_string(), "scamswap/amm is a 90% copy of example-upstream/amm-core with 3 commits");
+
+        // The upstream is not a copy of itself
+        let report = detect_origin(&github, &corpus, &health("example-upstream/amm-core")).await.unwrap();
+        assert_eq!(report.origin, RepositoryOrigin::Original);
+        assert_eq!(report.to_string(), "example-upstream/amm-core is original development with 1 commit");
+    }
+
+    #[tokio::test]
+    async fn test_fork_and_template_come_from_github() {
+        let server = test_http::serve(vec![]).await;
+        let github = GitHubClient::new(&server.base_url, None);
+        let corpus = UpstreamCorpus::default();
+
+        let mut fork = health("copycat/protocol");
+        fork.is_fork = true;
+        fork.parent = Some("example-project/protocol".to_string());
+        let report = detect_origin(&github, &corpus, &fork).await.unwrap();
+        assert_eq!(report.origin, RepositoryOrigin::Fork { parent: "example-project/protocol".to_string() });
+        assert_eq!(report.commits, None);
+
+        let mut generated = health("newcoin/site");
+        generated.template_repository = Some("dapp-kit/starter".to_string());
+        let report = detect_origin(&github, &corpus, &generated).await.unwrap();
+        assert_eq!(report.to_string(), "newcoin/site was generated from template dapp-kit/starter");
+
+        // Without a corpus an unforked repository is not claimed to be original
+        let report = detect_origin(&github, &corpus, &health("newcoin/contracts")).await.unwrap();
+        assert_eq!(report.origin, RepositoryOrigin::Original);
+        assert_eq!(report.to_string(), "newcoin/contracts is not a fork or template instance; its files were not compared against any upstream");
+
+        // Without a corpus no tree is downloaded
+        let requests = server.requests.lock().unwrap();
+        assert!(requests.iter().all(|r| !r.contains("/git/trees/")));
+    }
+}
@@ -0,0 +1,374 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::github::GitHubClient;
+
+/// Most recent releases whose tags are checked for signatures
+const MAX_RELEASE_TAGS: usize = 10;
+/// Most active committers whose registered signing keys are looked up
+const MAX_MAINTAINERS: usize = 3;
+/// Verification reasons meaning the signature itself is wrong, not merely unverifiable
+const BAD_SIGNATURE_REASONS: &[&str] = &["bad_signature", "malformed_signature", "invalid"];
+
+#[derive(Debug, Deserialize)]
+struct CommitItem {
+    commit: CommitDetail,
+    /// GitHub account of the author; absent when the author email matches no account
+    author: Option<Account>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitDetail {
+    verification: Option<Verification>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Account {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Verification {
+    #[serde(default)]
+    verified: bool,
+    reason: String,
+    signature: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    draft: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitRef {
+    object: GitObject,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitObject {
+    #[serde(rename = "type")]
+    kind: String,
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitTag {
+    verification: Option<Verification>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GpgKey {
+    #[serde(default)]
+    can_sign: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SshSigningKey {
+    key: String,
+}
+
+/// Kind of key behind a commit or tag signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum SigningKeyType {
+    Gpg,
+    Ssh,
+    X509,
+    Unknown,
+}
+
+impl SigningKeyType {
+    /// Recognize the key type from the armor of a detached signature
+    fn from_signature(signature: &str) -> Self {
+        if signature.contains("BEGIN PGP SIGNATURE") {
+            SigningKeyType::Gpg
+        } else if signature.contains("BEGIN SSH SIGNATURE") {
+            SigningKeyType::Ssh
+        } else if signature.contains("BEGIN SIGNED MESSAGE") || signature.contains("BEGIN PKCS7") {
+            SigningKeyType::X509
+        } else {
+            SigningKeyType::Unknown
+        }
+    }
+}
+
+/// Signatures on a set of commits or tags as GitHub verified them
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureStats {
+    pub checked: u32,
+    pub signed: u32,
+    pub verified: u32,
+    /// Signatures that failed to verify, as opposed to ones made with a key GitHub does not know
+    pub bad_signatures: u32,
+    pub key_types: BTreeMap<SigningKeyType, u32>,
+}
+
+impl SignatureStats {
+    fn record(&mut self, verification: Option<&Verification>) {
+        self.checked += 1;
+        let Some(verification) = verification.filter(|v| v.reason != "unsigned") else {
+            return;
+        };
+        self.signed += 1;
+        if verification.verified {
+            self.verified += 1;
+        }
+        if BAD_SIGNATURE_REASONS.contains(&verification.reason.as_str()) {
+            self.bad_signatures += 1;
+        }
+        let key_type = verification.signature.as_deref().map_or(SigningKeyType::Unknown, SigningKeyType::from_signature);
+        *self.key_types.entry(key_type).or_default() += 1;
+    }
+
+    /// Share of checked items with a signature GitHub verified, 0.0 to 1.0
+    pub fn verified_ratio(&self) -> Option<f64> {
+        (self.checked > 0).then(|| self.verified as f64 / self.checked as f64)
+    }
+}
+
+impl std::fmt::Display for SignatureStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} signed, {} verified", self.signed, self.checked, self.verified)?;
+        if self.bad_signatures > 0 {
+            write!(f, ", {} bad", self.bad_signatures)?;
+        }
+        let key_types: Vec<String> = self.key_types.iter().map(|(k, c)| format!("{:?} {}", k, c)).collect();
+        if !key_types.is_empty() {
+            write!(f, " ({})", key_types.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Signing keys a frequent committer has registered on GitHub
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintainerKeys {
+    pub login: String,
+    /// Commits among those checked
+    pub commits: u32,
+    pub gpg_signing_keys: u32,
+    /// Algorithm of each SSH signing key, e.g. `ssh-ed25519`
+    pub ssh_signing_keys: Vec<String>,
+}
+
+impl MaintainerKeys {
+    pub fn has_signing_key(&self) -> bool {
+        self.gpg_signing_keys > 0 || !self.ssh_signing_keys.is_empty()
+    }
+}
+
+/// Commit and release tag signatures of a repository, plus the signing keys of its maintainers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SigningReport {
+    pub repository: String,
+    /// The most recent commits on the default branch (at most 100)
+    pub commits: SignatureStats,
+    /// Tags of the most recent releases; lightweight tags count as unsigned
+    pub release_tags: SignatureStats,
+    pub maintainers: Vec<MaintainerKeys>,
+    /// Lookups that failed; the tags and maintainers they concern are left out of the counts
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+impl SigningReport {
+    /// Every checked release tag carries a signature GitHub verified
+    pub fn releases_signed(&self) -> bool {
+        self.release_tags.checked > 0 && self.release_tags.verified == self.release_tags.checked
+    }
+}
+
+impl std::fmt::Display for SigningReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: commits {}", self.repository, self.commits)?;
+        if self.release_tags.checked > 0 {
+            write!(f, "; release tags {}", self.release_tags)?;
+        }
+        let with_keys = self.maintainers.iter().filter(|m| m.has_signing_key()).count();
+        if !self.maintainers.is_empty() {
+            write!(f, "; {}/{} maintainers publish signing keys", with_keys, self.maintainers.len())?;
+        }
+        if !self.errors.is_empty() {
+            write!(f, "; {} lookups failed", self.errors.len())?;
+        }
+        Ok(())
+    }
+}
+
+async fn release_tag_verification(github: &GitHubClient, repo: &str, tag: &str) -> Result<Option<Verification>> {
+    let response = github.get(&format!("/repos/{}/git/ref/tags/{}", repo, tag)).await?;
+    if !response.is_success() {
+        return Ok(None);
+    }
+    let reference: GitRef = response.json()?;
+    // Lightweight tags point straight at a commit and cannot carry a signature
+    if reference.object.kind != "tag" {
+        return Ok(None);
+    }
+    let response = github.get(&format!("/repos/{}/git/tags/{}", repo, reference.object.sha)).await?;
+    if !response.is_success() {
+        return Ok(None);
+    }
+    Ok(response.json::<GitTag>()?.verification)
+}
+
+async fn maintainer_keys(github: &GitHubClient, login: &str, commits: u32) -> Result<MaintainerKeys> {
+    let mut keys = MaintainerKeys { login: login.to_string(), commits, gpg_signing_keys: 0, ssh_signing_keys: Vec::new() };
+    let response = github.get(&format!("/users/{}/gpg_keys", login)).await?;
+    if response.is_success() {
+        keys.gpg_signing_keys = response.json::<Vec<GpgKey>>()?.iter().filter(|k| k.can_sign).count() as u32;
+    }
+    let response = github.get(&format!("/users/{}/ssh_signing_keys", login)).await?;
+    if response.is_success() {
+        keys.ssh_signing_keys = response.json::<Vec<SshSigningKey>>()?.iter()
+            .filter_map(|k| k.key.split_whitespace().next().map(str::to_string))
+            .collect();
+    }
+    Ok(keys)
+}
+
+/// Signature statistics for `repo` (`owner/name`) from its recent commits, release tags and top committers
+pub async fn fetch_signing_report(github: &GitHubClient, repo: &str) -> Result<SigningReport> {
+    let response = github.get(&format!("/repos/{}/commits?per_page=100", repo)).await?;
+    if !response.is_success() {
+        return Err(anyhow::anyhow!("Failed to list commits of {}: status {}", repo, response.status));
+    }
+    let mut commits = SignatureStats::default();
+    let mut authors: BTreeMap<String, u32> = BTreeMap::new();
+    for item in response.json::<Vec<CommitItem>>()? {
+        commits.record(item.commit.verification.as_ref());
+        if let Some(author) = item.author {
+            *authors.entry(author.login).or_default() += 1;
+        }
+    }
+
+    // Commit statistics stand on their own; later lookups that fail are recorded, not fatal
+    let mut errors = Vec::new();
+    let mut release_tags = SignatureStats::default();
+    let releases = match github.get(&format!("/repos/{}/releases?per_page=100", repo)).await {
+        Ok(response) if response.is_success() => response.json::<Vec<Release>>().unwrap_or_else(|e| {
+            errors.push(format!("releases: {}", e));
+            Vec::new()
+        }),
+        Ok(_) => Vec::new(),
+        Err(e) => {
+            errors.push(format!("releases: {}", e));
+            Vec::new()
+        }
+    };
+    for release in releases.iter().filter(|r| !r.draft).take(MAX_RELEASE_TAGS) {
+        match release_tag_verification(github, repo, &release.tag_name).await {
+            Ok(verification) => release_tags.record(verification.as_ref()),
+            Err(e) => errors.push(format!("release tag {}: {}", release.tag_name, e)),
+        }
+    }
+
+    let mut ranked: Vec<(String, u32)> = authors.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let mut maintainers = Vec::new();
+    for (login, count) in ranked.into_iter().take(MAX_MAINTAINERS) {
+        match maintainer_keys(github, &login, count).await {
+            Ok(keys) => maintainers.push(keys),
+            Err(e) => errors.push(format!("signing keys of {}: {}", login, e)),
+        }
+    }
+
+    Ok(SigningReport { repository: repo.to_string(), commits, release_tags, maintainers, errors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const PGP: &str = "-----BEGIN PGP SIGNATURE-----\\n\\niQEz\\n-----END PGP SIGNATURE-----";
+    const SSH: &str = "-----BEGIN SSH SIGNATURE-----\\nU1NIU0lH\\n-----END SSH SIGNATURE-----";
+
+    fn commit(login: &str, verified: bool, reason: &str, signature: Option<&str>) -> String {
+        let signature = signature.map_or("null".to_string(), |s| format!("\"{}\"", s));
+        format!(r#"{{"commit": {{"verification": {{"verified": {}, "reason": "{}", "signature": {}}}}}, "author": {{"login": "{}"}}}}"#,
+            verified, reason, signature, login)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_signing_report() {
+        let commits = [
+            commit("ada-example", true, "valid", Some(PGP)),
+            commit("ada-example", true, "valid", Some(PGP)),
+            commit("gracesample", true, "valid", Some(SSH)),
+            commit("gracesample", false, "unknown_key", Some(SSH)),
+            commit("drive-by", false, "unsigned", None),
+        ];
+        let server = test_http::serve(vec![
+            ("/repos/example-project/protocol/commits", MockResponse::ok(format!("[{}]", commits.join(",")))),
+            ("/repos/example-project/protocol/releases", MockResponse::ok(r#"[
+                {"tag_name": "v2.0.0", "draft": false}, {"tag_name": "v2.1.0-rc", "draft": true},
+                {"tag_name": "v1.1.0", "draft": false}, {"tag_name": "v1.0.0", "draft": false}]"#)),
+            ("/repos/example-project/protocol/git/ref/tags/v2.0.0", MockResponse::ok(r#"{"object": {"type": "tag", "sha": "a1"}}"#)),
+            ("/repos/example-project/protocol/git/tags/a1", MockResponse::ok(format!(
+                r#"{{"verification": {{"verified": true, "reason": "valid", "signature": "{}"}}}}"#, PGP))),
+            ("/repos/example-project/protocol/git/ref/tags/v1.1.0", MockResponse::ok(r#"{"object": {"type": "tag", "sha": "b2"}}"#)),
+            ("/repos/example-project/protocol/git/tags/b2", MockResponse::ok(format!(
+                r#"{{"verification": {{"verified": false, "reason": "bad_signature", "signature": "{}"}}}}"#, PGP))),
+            ("/repos/example-project/protocol/git/ref/tags/v1.0.0", MockResponse::ok(r#"{"object": {"type": "commit", "sha": "c3"}}"#)),
+            ("/users/ada-example/gpg_keys", MockResponse::ok(r#"[{"can_sign": true}, {"can_sign": false}]"#)),
+            ("/users/ada-example/ssh_signing_keys", MockResponse::ok("[]")),
+            ("/users/gracesample/gpg_keys", MockResponse::ok("[]")),
+            ("/users/gracesample/ssh_signing_keys", MockResponse::ok(r#"[{"key": "ssh-ed25519 AAAAC3Nza grace@laptop"}]"#)),
+            ("/users/drive-by/gpg_keys", MockResponse::ok("[]")),
+            ("/users/drive-by/ssh_signing_keys", MockResponse::ok("[]")),
+        ]).await;
+        let github = GitHubClient::new(&server.base_url, None);
+
+        let report = fetch_signing_report(&github, "example-project/protocol").await.unwrap();
+        assert_eq!((report.commits.checked, report.commits.signed, report.commits.verified), (5, 4, 3));
+        assert_eq!(report.commits.key_types.get(&SigningKeyType::Ssh), Some(&2));
+        // Drafts are skipped and the lightweight v1.0.0 tag counts as unsigned
+        assert_eq!((report.release_tags.checked, report.release_tags.signed, report.release_tags.verified), (3, 2, 1));
+        assert_eq!(report.release_tags.bad_signatures, 1);
+        assert!(!report.releases_signed());
+        assert_eq!(report.maintainers.iter().map(|m| m.login.as_str()).collect::<Vec<_>>(), vec!["ada-example", "gracesample", "drive-by"]);
+        assert_eq!(report.maintainers[0].gpg_signing_keys, 1);
+        assert_eq!(report.maintainers[1].ssh_signing_keys, vec!["ssh-ed25519".to_string()]);
+        assert_eq!(report.to_string(), "example-project/protocol: commits 4/5 signed, 3 verified (Gpg 2, Ssh 2); \
+            release tags 2/3 signed, 1 verified, 1 bad (Gpg 2); 2/3 maintainers publish signing keys");
+    }
+
+    #[tokio::test]
+    async fn test_failed_lookups_keep_the_report() {
+        let commits = [commit("ada-example", true, "valid", Some(PGP)), commit("gracesample", false, "gpgverify_error", Some(PGP))];
+        let server = test_http::serve(vec![
+            ("/repos/example-project/protocol/commits", MockResponse::ok(format!("[{}]", commits.join(",")))),
+            ("/repos/example-project/protocol/releases", MockResponse::ok(r#"[{"tag_name": "v2.0.0", "draft": false}, {"tag_name": "v1.0.0", "draft": false}]"#)),
+            ("/repos/example-project/protocol/git/ref/tags/v2.0.0", MockResponse::ok("<html>upstream error</html>")),
+            ("/repos/example-project/protocol/git/ref/tags/v1.0.0", MockResponse::ok(r#"{"object": {"type": "tag", "sha": "b2"}}"#)),
+            ("/repos/example-project/protocol/git/tags/b2", MockResponse::ok(format!(
+                r#"{{"verification": {{"verified": true, "reason": "valid", "signature": "{}"}}}}"#, PGP))),
+            ("/users/ada-example/gpg_keys", MockResponse::ok("not json")),
+            ("/users/gracesample/gpg_keys", MockResponse::ok(r#"[{"can_sign": true}]"#)),
+            ("/users/gracesample/ssh_signing_keys", MockResponse::ok("[]")),
+        ]).await;
+        let github = GitHubClient::new(&server.base_url, None);
+
+        let report = fetch_signing_report(&github, "example-project/protocol").await.unwrap();
+        // GitHub failing to run gpg says nothing about the signature itself
+        assert_eq!((report.commits.checked, report.commits.signed, report.commits.bad_signatures), (2, 2, 0));
+        assert_eq!((report.release_tags.checked, report.release_tags.verified), (1, 1));
+        assert_eq!(report.maintainers.iter().map(|m| m.login.as_str()).collect::<Vec<_>>(), vec!["gracesample"]);
+        assert_eq!(report.errors.len(), 2);
+        assert!(report.errors[0].starts_with("release tag v2.0.0: "));
+        assert!(report.errors[1].starts_with("signing keys of ada-example: "));
+        assert!(report.to_string().ends_with("; 2 lookups failed"));
+    }
+
+    #[test]
+    fn test_key_type_from_signature() {
+        assert_eq!(SigningKeyType::from_signature("-----BEGIN PGP SIGNATURE-----\n..."), SigningKeyType::Gpg);
+        assert_eq!(SigningKeyType::from_signature("-----BEGIN SSH SIGNATURE-----\n..."), SigningKeyType::Ssh);
+        assert_eq!(SigningKeyType::from_signature("-----BEGIN SIGNED MESSAGE-----\n..."), SigningKeyType::X509);
+        assert_eq!(SigningKeyType::from_signature("garbage"), SigningKeyType::Unknown);
+    }
+}
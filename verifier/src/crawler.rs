@@ -0,0 +1,631 @@
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::OnceLock;
+use std::time::Duration;
+use url::Url;
+
+use crate::audit_registry::AuditRegistry;
+use crate::page_analysis::{self, Link, PageAnalysis};
+use crate::public_suffix;
+
+/// Product token matched against robots.txt `User-agent` lines
+pub const CRAWLER_AGENT: &str = "poa-verifier";
+
+/// Longest Crawl-delay honored; slower sites are crawled at this pace instead
+const MAX_CRAWL_DELAY: Duration = Duration::from_secs(5);
+/// Sitemap files read per crawl, counting nested sitemap indexes
+const MAX_SITEMAPS: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct CrawlConfig {
+    /// Link hops followed from the front page; sitemap entries count as one hop
+    pub max_depth: usize,
+    /// Pages fetched in total, robots.txt and sitemaps excluded
+    pub max_pages: usize,
+    /// Minimum pause between requests
+    pub delay: Duration,
+    /// No page is fetched once the crawl has run this long, however much budget is left
+    pub max_duration: Duration,
+}
+
+impl Default for CrawlConfig {
+    fn default() -> Self {
+        Self { max_depth: 2, max_pages: 25, delay: Duration::from_millis(250), max_duration: Duration::from_secs(60) }
+    }
+}
+
+/// Transparency indicators recognized on crawled pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Indicator {
+    Roadmap,
+    AuditReports,
+    TeamInformation,
+    TokenEconomics,
+}
+
+impl Indicator {
+    pub const ALL: [Indicator; 4] = [
+        Indicator::Roadmap,
+        Indicator::AuditReports,
+        Indicator::TeamInformation,
+        Indicator::TokenEconomics,
+    ];
+
+    /// Words that name the indicator in headings, titles and link labels
+    pub fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Indicator::Roadmap => &["roadmap", "milestones", "timeline", "development plan"],
+            Indicator::AuditReports => &["audit", "audits", "security audit", "audit report", "audit reports"],
+            Indicator::TeamInformation => &["team", "our team", "founders", "founding team", "leadership", "core contributors"],
+            Indicator::TokenEconomics => &[
+                "tokenomics", "token economics", "token distribution", "token allocation", "token utility",
+                "whitepaper", "white paper",
+            ],
+        }
+    }
+}
+
+impl std::fmt::Display for Indicator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Indicator::Roadmap => "roadmap",
+            Indicator::AuditReports => "audit reports",
+            Indicator::TeamInformation => "team information",
+            Indicator::TokenEconomics => "token economics",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CrawlReport {
+    /// The start page, when it could be fetched
+    pub front_page: Option<PageAnalysis>,
+    /// Pages fetched, in order
+    pub pages_visited: Vec<String>,
+    /// URLs skipped because robots.txt disallows them
+    pub disallowed: Vec<String>,
+    /// First URL at which each indicator was found
+    pub indicators: BTreeMap<Indicator, String>,
+    /// Audit reports linked from any crawled page, in discovery order
+    pub audit_documents: Vec<String>,
+}
+
+/// Parsed robots.txt rules for one user agent (RFC 9309)
+#[derive(Debug, Clone, Default)]
+pub struct RobotsTxt {
+    rules: Vec<RobotsRule>,
+    pub crawl_delay: Option<Duration>,
+    pub sitemaps: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct RobotsRule {
+    allow: bool,
+    /// Length of the original pattern; the longest match wins
+    specificity: usize,
+    pattern: Regex,
+}
+
+impl RobotsTxt {
+    /// Rules of the groups naming `agent`, or of the `*` groups when none does
+    pub fn parse(text: &str, agent: &str) -> Self {
+        let agent = agent.to_ascii_lowercase();
+        let mut specific = RobotsTxt::default();
+        let mut wildcard = RobotsTxt::default();
+        let mut sitemaps = Vec::new();
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        // A group naming the agent replaces the * groups even when it has no rules (RFC 9309 §2.2.1)
+        let mut has_specific = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let (key, value) = (key.trim().to_ascii_lowercase(), value.trim());
+            match key.as_str() {
+                "user-agent" => {
+                    // A user-agent line after rules starts a new group
+                    if in_rules {
+                        group_agents.clear();
+                        in_rules = false;
+                    }
+                    group_agents.push(value.to_ascii_lowercase());
+                    has_specific |= value.eq_ignore_ascii_case(&agent);
+                }
+                "sitemap" => sitemaps.push(value.to_string()),
+                "allow" | "disallow" | "crawl-delay" => {
+                    in_rules = true;
+                    let targets = [
+                        group_agents.contains(&agent).then_some(&mut specific),
+                        group_agents.iter().any(|a| a == "*").then_some(&mut wildcard),
+                    ];
+                    for robots in targets.into_iter().flatten() {
+                        if key == "crawl-delay" {
+                            robots.crawl_delay = value.parse::<f64>().ok()
+                                .filter(|d| d.is_finite() && *d >= 0.0)
+                                .map(Duration::from_secs_f64);
+                        } else if !value.is_empty() {
+                            robots.rules.push(RobotsRule::new(key == "allow", value));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut robots = if has_specific { specific } else { wildcard };
+        robots.sitemaps = sitemaps;
+        robots
+    }
+
+    /// Whether `path` (with query) may be fetched
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules.iter()
+            .filter(|rule| rule.pattern.is_match(path))
+            // Longest pattern wins; on a tie the allow rule does
+            .max_by_key(|rule| (rule.specificity, rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+}
+
+impl RobotsRule {
+    fn new(allow: bool, pattern: &str) -> Self {
+        let (body, anchored) = match pattern.strip_suffix('$') {
+            Some(body) => (body, true),
+            None => (pattern, false),
+        };
+        let regex = format!(
+            "^{}{}",
+            body.split('*').map(regex::escape).collect::<Vec<_>>().join(".*"),
+            if anchored { "$" } else { "" },
+        );
+        Self {
+            allow,
+            specificity: pattern.len(),
+            pattern: Regex::new(&regex).expect("escaped robots pattern is a valid regex"),
+        }
+    }
+}
+
+/// Page URLs and nested sitemap URLs listed in a sitemap or sitemap index
+pub fn parse_sitemap(xml: &str) -> (Vec<String>, Vec<String>) {
+    let loc = Regex::new(r"(?s)<loc>\s*(.*?)\s*</loc>").expect("static regex is valid");
+    let urls: Vec<String> = loc.captures_iter(xml)
+        .map(|c| c[1].replace("&amp;", "&"))
+        .collect();
+    if xml.contains("<sitemapindex") {
+        (Vec::new(), urls)
+    } else {
+        (urls, Vec::new())
+    }
+}
+
+/// Crawl from `start_url`, honoring robots.txt, and record where each indicator appears
+pub async fn crawl(start_url: &str, config: &CrawlConfig) -> CrawlReport {
+    let mut report = CrawlReport::default();
+    let started = tokio::time::Instant::now();
+    let Ok(start) = Url::parse(start_url) else { return report };
+    // docs.example.com belongs to a crawl of app.example.com; another github.io user does not
+    let Some(root) = start.host_str().map(public_suffix::site_domain) else {
+        return report;
+    };
+    // Redirects are queued like links instead, so every hop passes the scope and robots.txt checks
+    let Ok(client) = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .user_agent(format!("{}/{}", CRAWLER_AGENT, env!("CARGO_PKG_VERSION")))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+    else {
+        return report;
+    };
+
+    let mut robots_by_origin: HashMap<String, RobotsTxt> = HashMap::new();
+    let start_robots = robots_for(&client, &start, &mut robots_by_origin).await.clone();
+    let mut last_request: HashMap<String, tokio::time::Instant> = HashMap::new();
+
+    let mut queue: VecDeque<(String, usize)> = VecDeque::from([(start.to_string(), 0)]);
+    let mut queued: HashSet<String> = HashSet::from([start.to_string()]);
+    for url in sitemap_urls(&client, &start, &start_robots).await {
+        if in_scope(&url, &root) && queued.insert(url.clone()) {
+            queue.push_back((url, 1));
+        }
+    }
+
+    while let Some((url, depth)) = queue.pop_front() {
+        if report.pages_visited.len() >= config.max_pages {
+            break;
+        }
+        let Ok(parsed) = Url::parse(&url) else { continue };
+        // Each origin's robots.txt governs its own paths and pace
+        let robots = robots_for(&client, &parsed, &mut robots_by_origin).await;
+        if !robots.is_allowed(&path_and_query(&parsed)) {
+            report.disallowed.push(url);
+            continue;
+        }
+        let delay = config.delay.max(robots.crawl_delay.unwrap_or_default().min(MAX_CRAWL_DELAY));
+        let origin = parsed.origin().ascii_serialization();
+        if let Some(last) = last_request.get(&origin) {
+            tokio::time::sleep_until(*last + delay).await;
+        }
+        // The front page is always read; after it the time limit applies
+        if depth > 0 && started.elapsed() >= config.max_duration {
+            break;
+        }
+        last_request.insert(origin, tokio::time::Instant::now());
+        report.pages_visited.push(url.clone());
+        let Ok(response) = client.get(&url).send().await else { continue };
+        if response.status().is_redirection() {
+            // The target takes the redirecting page's place in the queue, at the same depth
+            let target = response.headers().get(reqwest::header::LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| parsed.join(location).ok())
+                .map(|target| target.as_str().split('#').next().unwrap_or_default().to_string());
+            if let Some(target) = target.filter(|target| in_scope(target, &root)) {
+                if queued.insert(target.clone()) {
+                    queue.push_front((target, depth));
+                }
+            }
+            continue;
+        }
+        let page = match page_analysis::parse_response(response).await {
+            Ok(page) => page,
+            Err(_) => continue,
+        };
+
+        for (indicator, location) in classify_page(&page) {
+            if let std::collections::btree_map::Entry::Vacant(entry) = report.indicators.entry(indicator) {
+                println!("✅ Found {} at: {}", indicator, location);
+                entry.insert(location);
+            }
+        }
+
+        for document in audit_document_links(&page) {
+            if !report.audit_documents.contains(&document) {
+                report.audit_documents.push(document);
+            }
+        }
+
+        if depth < config.max_depth {
+            let links = page.internal_links.iter().chain(&page.external_links);
+            for link in links {
+                let target = link.url.split('#').next().unwrap_or_default().to_string();
+                if in_scope(&target, &root) && !is_document(&target) && queued.insert(target.clone()) {
+                    queue.push_back((target, depth + 1));
+                }
+            }
+        }
+        if depth == 0 {
+            report.front_page = Some(page);
+        }
+    }
+
+    report
+}
+
+/// Indicators evidenced by `page`, with the URL (or fragment) where each was found
+pub fn classify_page(page: &PageAnalysis) -> Vec<(Indicator, String)> {
+    let mut found = Vec::new();
+    for indicator in Indicator::ALL {
+        let keywords = indicator.keywords();
+        if let Some(section) = page.section_about(keywords) {
+            let anchor = section.heading.as_ref().and_then(|h| h.id.as_ref());
+            let location = match anchor {
+                Some(id) => format!("{}#{}", page.url.split('#').next().unwrap_or_default(), id),
+                None => page.url.clone(),
+            };
+            found.push((indicator, location));
+        } else if page.is_about(keywords) {
+            found.push((indicator, page.url.clone()));
+        } else if let Some(document) = page.internal_links.iter().find(|l| is_document(&l.url) && l.is_about(keywords)) {
+            // Whitepapers and audit reports are usually PDFs linked by name
+            found.push((indicator, document.url.clone()));
+        } else if indicator == Indicator::AuditReports {
+            // A report hosted by the auditing firm is stronger evidence than the project's own page
+            if let Some(link) = page.external_links.iter().find(|l| is_audit_firm_link(l)) {
+                found.push((indicator, link.url.clone()));
+            }
+        } else if indicator == Indicator::TeamInformation && page.json_ld_types().iter().any(|t| t == "Person") {
+            found.push((indicator, page.url.clone()));
+        }
+    }
+    found
+}
+
+/// Links to audit reports: documents named as audits, and reports hosted by audit firms
+pub fn audit_document_links(page: &PageAnalysis) -> Vec<String> {
+    let keywords = Indicator::AuditReports.keywords();
+    page.internal_links.iter()
+        .chain(&page.external_links)
+        .filter(|link| {
+            let named_audit = link.is_about(keywords) || link.url.to_ascii_lowercase().contains("audit");
+            (is_document(&link.url) && named_audit) || is_audit_firm_link(link)
+        })
+        .map(|link| link.url.split('#').next().unwrap_or_default().to_string())
+        .collect()
+}
+
+/// Link to an audit firm whose label or URL refers to an audit or report, not just a badge
+fn is_audit_firm_link(link: &Link) -> bool {
+    // Firms come from the built-in audit registry, which is parsed once
+    static FIRMS: OnceLock<AuditRegistry> = OnceLock::new();
+    let by_firm = FIRMS.get_or_init(AuditRegistry::default).auditor_at(&link.url).is_some();
+    let lower_url = link.url.to_ascii_lowercase();
+    by_firm && (link.is_about(&["audit", "audit report", "security review", "report"])
+        || lower_url.contains("audit")
+        || lower_url.ends_with(".pdf"))
+}
+
+/// A host of the site `root`, e.g. `docs.example.com` for a crawl that started on `app.example.com`
+fn in_scope(url: &str, root: &str) -> bool {
+    Url::parse(url).ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .and_then(|url| url.host_str().map(public_suffix::site_domain))
+        .is_some_and(|site| site == root)
+}
+
+fn is_document(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or_default().to_ascii_lowercase();
+    path.ends_with(".pdf")
+}
+
+fn path_and_query(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+/// robots.txt of the URL's origin, fetched once; a missing or unreadable file allows everything
+async fn robots_for<'a>(
+    client: &reqwest::Client,
+    url: &Url,
+    cache: &'a mut HashMap<String, RobotsTxt>,
+) -> &'a RobotsTxt {
+    let origin = url.origin().ascii_serialization();
+    if !cache.contains_key(&origin) {
+        let robots = match fetch_text(client, &format!("{}/robots.txt", origin)).await {
+            Ok(text) => RobotsTxt::parse(&text, CRAWLER_AGENT),
+            Err(_) => RobotsTxt::default(),
+        };
+        cache.insert(origin.clone(), robots);
+    }
+    &cache[&origin]
+}
+
+/// Page URLs from the sitemaps robots.txt declares, or from `/sitemap.xml`, indicator-like paths first
+async fn sitemap_urls(client: &reqwest::Client, start: &Url, robots: &RobotsTxt) -> Vec<String> {
+    let mut pending: VecDeque<String> = if robots.sitemaps.is_empty() {
+        VecDeque::from([format!("{}/sitemap.xml", start.origin().ascii_serialization())])
+    } else {
+        robots.sitemaps.iter().cloned().collect()
+    };
+    let mut urls = Vec::new();
+    let mut read = 0;
+    while let Some(sitemap) = pending.pop_front() {
+        if read >= MAX_SITEMAPS {
+            break;
+        }
+        read += 1;
+        if let Ok(xml) = fetch_text(client, &sitemap).await {
+            let (pages, nested) = parse_sitemap(&xml);
+            urls.extend(pages);
+            pending.extend(nested);
+        }
+    }
+
+    let looks_relevant = |url: &String| {
+        let link = Link { url: url.clone(), text: String::new() };
+        Indicator::ALL.iter().any(|indicator| link.is_about(indicator.keywords()))
+    };
+    urls.sort_by_key(|url| !looks_relevant(url));
+    urls
+}
+
+async fn fetch_text(client: &reqwest::Client, url: &str) -> Result<String> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("{} returned {}", url, response.status()));
+    }
+    Ok(response.text().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    fn html(body: &str) -> MockResponse {
+        MockResponse::ok(format!("<html><body>{}</body></html>", body)).with_header("Content-Type", "text/html")
+    }
+
+    #[test]
+    fn test_robots_txt_rules() {
+        let robots = RobotsTxt::parse(
+            "User-agent: *\nDisallow: /\n\n\
+             User-agent: Googlebot\nUser-agent: POA-Verifier\nDisallow: /private\nAllow: /private/press\n\
+             Disallow: /*.json$\nCrawl-delay: 2 # seconds\n\n\
+             Sitemap: https://example-project.com/sitemap_index.xml\n",
+            CRAWLER_AGENT,
+        );
+        assert!(robots.is_allowed("/docs/roadmap"));
+        assert!(!robots.is_allowed("/private/team"));
+        assert!(robots.is_allowed("/private/press/kit"));
+        assert!(!robots.is_allowed("/api/tokens.json"));
+        assert!(robots.is_allowed("/api/tokens.json?v=2"));
+        assert_eq!(robots.crawl_delay, Some(Duration::from_secs(2)));
+        assert_eq!(robots.sitemaps, vec!["https://example-project.com/sitemap_index.xml"]);
+
+        // Other agents fall back to the * group
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow: /\n\nUser-agent: otherbot\nAllow: /\n", CRAWLER_AGENT);
+        assert!(!robots.is_allowed("/"));
+        assert!(RobotsTxt::parse("", CRAWLER_AGENT).is_allowed("/anything"));
+
+        // An empty group for the agent allows everything, whatever the * group says
+        let robots = RobotsTxt::parse(&format!("User-agent: *\nDisallow: /\n\nUser-agent: {}\n", CRAWLER_AGENT), CRAWLER_AGENT);
+        assert!(robots.is_allowed("/docs"));
+    }
+
+    #[test]
+    fn test_parse_sitemap_and_scope() {
+        let (pages, nested) = parse_sitemap(
+            "<urlset><url><loc>https://example-project.com/</loc></url>\
+             <url><loc>\n  https://docs.example-project.com/tokenomics?lang=en&amp;v=2\n</loc></url></urlset>",
+        );
+        assert_eq!(pages, vec!["https://example-project.com/", "https://docs.example-project.com/tokenomics?lang=en&v=2"]);
+        assert!(nested.is_empty());
+
+        let (pages, nested) = parse_sitemap("<sitemapindex><sitemap><loc>https://example-project.com/blog.xml</loc></sitemap></sitemapindex>");
+        assert!(pages.is_empty());
+        assert_eq!(nested, vec!["https://example-project.com/blog.xml"]);
+
+        assert!(in_scope("https://docs.example-project.com/intro", "example-project.com"));
+        let root = public_suffix::site_domain("app.example-project.co.uk");
+        assert!(in_scope("https://docs.example-project.co.uk/", &root));
+        assert!(!in_scope("https://other-project.co.uk/", &root));
+        assert!(!in_scope("https://bob.github.io/", &public_suffix::site_domain("alice.github.io")));
+        assert!(in_scope("http://www.example-project.com/", "example-project.com"));
+        assert!(!in_scope("https://example-project.com.evil.xyz/", "example-project.com"));
+        assert!(!in_scope("mailto:team@example-project.com", "example-project.com"));
+    }
+
+    #[tokio::test]
+    async fn test_crawl_discovers_indicators() {
+        let server = test_http::serve(vec![
+            ("/robots.txt", MockResponse::ok("User-agent: *\nDisallow: /private\n")),
+            ("/sitemap.xml", MockResponse::ok("<urlset><url><loc>{base_url}/docs/reviews</loc></url></urlset>")),
+            ("/", html(r#"<script>const audit = "certik";</script><a href="/blog">Blog</a> <a href="/private/people">People</a>
+                <a href="/whitepaper.pdf">Read the whitepaper</a>"#)),
+            ("/blog", html(r#"<h1>Blog</h1><a href="/blog/q3-update">Q3 update</a>"#)),
+            ("/blog/q3-update", html(r#"<h1>Q3 update</h1><section id="next"><h2>Roadmap</h2></section><a href="/deep">Deeper</a>"#)),
+            ("/deep", html("<h1>Team</h1>")),
+            ("/docs/reviews", html("<h1>Security audits</h1>")),
+            ("/private/people", html("<h1>Our team</h1>")),
+        ]).await;
+        let config = CrawlConfig { max_depth: 2, max_pages: 10, delay: Duration::ZERO, ..CrawlConfig::default() };
+
+        let report = crawl(&format!("{}/", server.base_url), &config).await;
+        let base = &server.base_url;
+        assert!(report.front_page.is_some());
+        assert_eq!(report.indicators.get(&Indicator::Roadmap), Some(&format!("{}/blog/q3-update#next", base)));
+        assert_eq!(report.indicators.get(&Indicator::TokenEconomics), Some(&format!("{}/whitepaper.pdf", base)));
+        // Found through the sitemap, not through the front page's script mentioning an auditor
+        assert_eq!(report.indicators.get(&Indicator::AuditReports), Some(&format!("{}/docs/reviews", base)));
+        // Robots-blocked and too-deep pages are never read
+        assert_eq!(report.disallowed, vec![format!("{}/private/people", base)]);
+        assert!(!report.pages_visited.contains(&format!("{}/deep", base)));
+        assert!(!report.indicators.contains_key(&Indicator::TeamInformation));
+        assert!(server.requests.lock().unwrap()[0].contains(CRAWLER_AGENT));
+    }
+
+    #[tokio::test]
+    async fn test_indicators_need_page_sections() {
+        let server = test_http::serve(vec![
+            ("/", html(r#"<script>const audit = "certik"; const tokenomics = 1;</script>
+                <nav><a href="/people">Meet us</a> <a href="/crew">Our team</a> <a href="/audits">Security</a></nav>
+                <h1>Example Project</h1><p>Tokens for everyone.</p>"#)),
+            ("/people", html("<h1>Meet us</h1><p>We love tokenomics.</p>")),
+            ("/crew", html("<h2>Core contributors</h2><p>Ada, Grace</p>")),
+            ("/audits", html("<p>An audit is coming soon</p>")),
+        ]).await;
+        let config = CrawlConfig { delay: Duration::ZERO, ..CrawlConfig::default() };
+
+        let report = crawl(&format!("{}/", server.base_url), &config).await;
+        assert_eq!(report.indicators.get(&Indicator::TeamInformation), Some(&format!("{}/crew", server.base_url)));
+        // Mentions in scripts, link labels and body copy are not sections about the topic
+        assert!(!report.indicators.contains_key(&Indicator::AuditReports));
+        assert!(!report.indicators.contains_key(&Indicator::TokenEconomics));
+        assert!(!report.indicators.contains_key(&Indicator::Roadmap));
+    }
+
+    #[tokio::test]
+    async fn test_crawl_stops_at_time_limit() {
+        let server = test_http::serve(vec![
+            ("/", html(r#"<a href="/a">A</a> <a href="/b">B</a>"#)),
+            ("/a", html("<h1>Roadmap</h1>")),
+        ]).await;
+        let config = CrawlConfig { delay: Duration::from_millis(200), max_duration: Duration::from_millis(100), ..CrawlConfig::default() };
+
+        let report = crawl(&format!("{}/", server.base_url), &config).await;
+        assert_eq!(report.pages_visited, vec![format!("{}/", server.base_url)]);
+    }
+
+    #[tokio::test]
+    async fn test_redirects_are_checked_like_links() {
+        let server = test_http::serve(vec![
+            ("/robots.txt", MockResponse::ok("User-agent: *\nDisallow: /private\n")),
+            ("/", MockResponse::status(301).with_header("Location", "/home")),
+            ("/home", html(r#"<a href="/team">Team</a> <a href="/roadmap">Roadmap</a> <a href="/login">Log in</a>"#)),
+            ("/team", MockResponse::status(302).with_header("Location", "/private/team")),
+            ("/private/team", html("<h1>Team</h1>")),
+            ("/roadmap", MockResponse::status(307).with_header("Location", "roadmap-2025")),
+            ("/roadmap-2025", html("<h1>Roadmap</h1>")),
+            ("/login", MockResponse::status(302).with_header("Location", "https://accounts.example-wallet.com/login")),
+        ]).await;
+        let base = &server.base_url;
+        let config = CrawlConfig { delay: Duration::ZERO, ..CrawlConfig::default() };
+
+        let report = crawl(&format!("{}/", base), &config).await;
+        assert_eq!(report.front_page.as_ref().map(|p| p.url.as_str()), Some(format!("{}/home", base).as_str()));
+        assert_eq!(report.indicators.get(&Indicator::Roadmap), Some(&format!("{}/roadmap-2025", base)));
+        // A redirect into a disallowed path or off the site goes no further than a link would
+        assert_eq!(report.disallowed, vec![format!("{}/private/team", base)]);
+        assert!(!report.indicators.contains_key(&Indicator::TeamInformation));
+        assert!(server.requests.lock().unwrap().iter().all(|r| !r.contains("/private/team")));
+    }
+
+    #[tokio::test]
+    async fn test_each_origin_has_its_own_robots() {
+        let docs = test_http::serve(vec![
+            ("/robots.txt", MockResponse::ok("User-agent: *\nDisallow: /drafts\nCrawl-delay: 0.3\n")),
+            ("/intro", html(r#"<h1>Docs</h1><a href="/drafts/team">Team draft</a> <a href="/tokenomics">Tokenomics</a>"#)),
+            ("/tokenomics", html("<h1>Tokenomics</h1>")),
+            ("/drafts/team", html("<h1>Team</h1>")),
+        ]).await;
+        let site = test_http::serve(vec![
+            ("/robots.txt", MockResponse::ok("User-agent: *\nDisallow: /tokenomics\n")),
+            ("/", html(&format!(r#"<a href="{}/intro">Docs</a>"#, docs.base_url))),
+        ]).await;
+        let config = CrawlConfig { delay: Duration::ZERO, ..CrawlConfig::default() };
+
+        let started = std::time::Instant::now();
+        let report = crawl(&format!("{}/", site.base_url), &config).await;
+        // The docs host's rules apply to its pages, not the site's
+        assert_eq!(report.indicators.get(&Indicator::TokenEconomics), Some(&format!("{}/tokenomics", docs.base_url)));
+        assert_eq!(report.disallowed, vec![format!("{}/drafts/team", docs.base_url)]);
+        assert!(started.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_audit_document_links() {
+        let page = PageAnalysis::parse("https://example-project.com/security", r#"<html><body>
+            <a href="/docs/2024-audit.pdf#page=2">Q1 report</a>
+            <a href="/whitepaper.pdf">Whitepaper</a>
+            <a href="https://github.com/example-project/audits/raw/main/Halborn.pdf">Halborn audit</a>
+            <a href="https://www.certik.com/projects/example-project">CertiK audit report</a>
+            <a href="https://www.certik.com/">Secured by CertiK</a>
+        </body></html>"#);
+
+        assert_eq!(audit_document_links(&page), vec![
+            "https://example-project.com/docs/2024-audit.pdf",
+            "https://github.com/example-project/audits/raw/main/Halborn.pdf",
+            "https://www.certik.com/projects/example-project",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_respects_page_budget() {
+        let server = test_http::serve(vec![
+            ("/", html(r#"<a href="/a">A</a> <a href="/b">B</a> <a href="/c">C</a>"#)),
+            ("/a", html("<p>a</p>")),
+            ("/b", html("<p>b</p>")),
+            ("/c", html("<h2>Token distribution</h2>")),
+        ]).await;
+        let config = CrawlConfig { max_depth: 3, max_pages: 3, delay: Duration::ZERO, ..CrawlConfig::default() };
+
+        let report = crawl(&format!("{}/", server.base_url), &config).await;
+        assert_eq!(report.pages_visited.len(), 3);
+        assert!(report.indicators.is_empty());
+    }
+}
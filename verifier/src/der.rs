@@ -0,0 +1,75 @@
+use anyhow::Result;
+
+/// A single DER tag-length-value element
+#[derive(Debug, Clone, Copy)]
+pub struct Tlv<'a> {
+    pub tag: u8,
+    pub content: &'a [u8],
+    /// The complete encoding, including tag and length
+    pub raw: &'a [u8],
+}
+
+/// Read one TLV from the front of `input`, returning it and the remaining bytes
+pub fn read_tlv(input: &[u8]) -> Result<(Tlv<'_>, &[u8])> {
+    if input.len() < 2 {
+        return Err(anyhow::anyhow!("DER element truncated"));
+    }
+    let tag = input[0];
+    if tag & 0x1f == 0x1f {
+        return Err(anyhow::anyhow!("Multi-byte DER tags are not supported"));
+    }
+    let (len, header_len) = match input[1] {
+        l if l < 0x80 => (l as usize, 2),
+        0x80 => return Err(anyhow::anyhow!("Indefinite length is not valid DER")),
+        l => {
+            let count = (l & 0x7f) as usize;
+            if count > 4 || input.len() < 2 + count {
+                return Err(anyhow::anyhow!("Invalid DER length"));
+            }
+            let len = input[2..2 + count].iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+            (len, 2 + count)
+        }
+    };
+    let end = header_len
+        .checked_add(len)
+        .filter(|&end| end <= input.len())
+        .ok_or_else(|| anyhow::anyhow!("DER element exceeds input"))?;
+    Ok((
+        Tlv {
+            tag,
+            content: &input[header_len..end],
+            raw: &input[..end],
+        },
+        &input[end..],
+    ))
+}
+
+/// Split constructed content into its child elements
+pub fn children(mut content: &[u8]) -> Result<Vec<Tlv<'_>>> {
+    let mut items = Vec::new();
+    while !content.is_empty() {
+        let (tlv, rest) = read_tlv(content)?;
+        items.push(tlv);
+        content = rest;
+    }
+    Ok(items)
+}
+
+/// Encode `content` under a single-byte `tag`
+pub fn encode_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = content.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes: Vec<u8> = len.to_be_bytes().iter().copied().skip_while(|&b| b == 0).collect();
+        out.push(0x80 | bytes.len() as u8);
+        out.extend_from_slice(&bytes);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+pub const TAG_OCTET_STRING: u8 = 0x04;
+pub const TAG_OID: u8 = 0x06;
+pub const TAG_SEQUENCE: u8 = 0x30;
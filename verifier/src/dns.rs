@@ -0,0 +1,575 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+
+pub const TYPE_A: u16 = 1;
+pub const TYPE_CNAME: u16 = 5;
+pub const TYPE_MX: u16 = 15;
+pub const TYPE_TXT: u16 = 16;
+pub const TYPE_AAAA: u16 = 28;
+pub const TYPE_OPT: u16 = 41;
+pub const TYPE_RRSIG: u16 = 46;
+pub const TYPE_CAA: u16 = 257;
+
+/// CAA identifiers published by the CAs we can recognize from an issuer organization
+const CA_IDENTIFIERS: &[(&str, &[&str])] = &[
+    ("Let's Encrypt", &["letsencrypt.org"]),
+    ("Google Trust Services", &["pki.goog"]),
+    ("DigiCert", &["digicert.com", "symantec.com", "geotrust.com", "rapidssl.com", "thawte.com"]),
+    ("Sectigo", &["sectigo.com", "comodoca.com", "comodo.com"]),
+    ("ZeroSSL", &["sectigo.com", "zerossl.com"]),
+    ("GlobalSign", &["globalsign.com"]),
+    ("Amazon", &["amazon.com", "amazontrust.com", "awstrust.com", "amazonaws.com"]),
+    ("Entrust", &["entrust.net"]),
+    ("GoDaddy", &["godaddy.com", "starfieldtech.com"]),
+    ("Microsoft", &["microsoft.com"]),
+    ("Buypass", &["buypass.com", "buypass.no"]),
+    ("SSL Corporation", &["ssl.com"]),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordData {
+    A(Ipv4Addr),
+    Aaaa(Ipv6Addr),
+    Cname(String),
+    Mx { preference: u16, exchange: String },
+    Txt(String),
+    Caa(CaaRecord),
+    /// Any record type we do not decode, such as RRSIG
+    Other(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaaRecord {
+    pub flags: u8,
+    pub tag: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DnsResponse {
+    pub rcode: u8,
+    /// The resolver set the AD bit: it validated the answer with DNSSEC
+    pub authenticated: bool,
+    pub answers: Vec<RecordData>,
+}
+
+impl DnsResponse {
+    /// Whether the answer section carried RRSIG records
+    pub fn signed(&self) -> bool {
+        self.answers.contains(&RecordData::Other(TYPE_RRSIG))
+    }
+}
+
+/// Minimal stub resolver speaking DNS over UDP, retrying over TCP for truncated answers
+#[derive(Debug, Clone)]
+pub struct DnsResolver {
+    pub server: SocketAddr,
+    pub timeout: Duration,
+}
+
+impl DnsResolver {
+    pub fn new(server: SocketAddr) -> Self {
+        Self { server, timeout: Duration::from_secs(5) }
+    }
+
+    /// Use the first nameserver from /etc/resolv.conf, or a public resolver
+    pub fn system() -> Self {
+        let server = std::fs::read_to_string("/etc/resolv.conf")
+            .ok()
+            .and_then(|conf| {
+                conf.lines()
+                    .filter_map(|line| line.trim().strip_prefix("nameserver"))
+                    .find_map(|addr| addr.trim().parse::<std::net::IpAddr>().ok())
+            })
+            .map(|ip| SocketAddr::new(ip, 53))
+            .unwrap_or_else(|| SocketAddr::from(([1, 1, 1, 1], 53)));
+        Self::new(server)
+    }
+
+    pub async fn query(&self, name: &str, record_type: u16) -> Result<DnsResponse> {
+        let id: u16 = rand::random();
+        let query = encode_query(id, name, record_type)?;
+
+        let bind: SocketAddr = if self.server.is_ipv4() { "0.0.0.0:0".parse()? } else { "[::]:0".parse()? };
+        let socket = UdpSocket::bind(bind).await?;
+        socket.connect(self.server).await?;
+        socket.send(&query).await?;
+
+        let mut buf = vec![0u8; 4096];
+        let response = loop {
+            let n = tokio::time::timeout(self.timeout, socket.recv(&mut buf))
+                .await
+                .with_context(|| format!("DNS query for {} timed out", name))??;
+            // Ignore stray datagrams that do not answer our query
+            if n >= 12 && u16::from_be_bytes([buf[0], buf[1]]) == id {
+                break buf[..n].to_vec();
+            }
+        };
+
+        // TC bit: the answer did not fit in a datagram
+        if response[2] & 0x02 != 0 {
+            return self.query_tcp(&query, name).await;
+        }
+        parse_response(&response)
+    }
+
+    async fn query_tcp(&self, query: &[u8], name: &str) -> Result<DnsResponse> {
+        let exchange = async {
+            let mut stream = TcpStream::connect(self.server).await?;
+            let mut framed = (query.len() as u16).to_be_bytes().to_vec();
+            framed.extend_from_slice(query);
+            stream.write_all(&framed).await?;
+            let len = stream.read_u16().await? as usize;
+            let mut response = vec![0u8; len];
+            stream.read_exact(&mut response).await?;
+            anyhow::Ok(response)
+        };
+        let response = tokio::time::timeout(self.timeout, exchange)
+            .await
+            .with_context(|| format!("DNS query over TCP for {} timed out", name))??;
+        parse_response(&response)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DnssecStatus {
+    /// The resolver validated the signatures
+    Validated,
+    /// Signatures present, but the resolver did not vouch for them
+    SignedUnvalidated,
+    Unsigned,
+}
+
+/// Whether the certificate issuer may issue for the domain under its CAA policy as published now;
+/// the policy in force when the certificate was issued is not recorded anywhere we can query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaaAuthorization {
+    /// No CAA records anywhere up the tree: every CA may issue
+    NoPolicy,
+    Permitted { ca_domain: String },
+    Forbidden { allowed: Vec<String> },
+    /// The issuer could not be mapped to a CAA identifier
+    UnknownIssuer { allowed: Vec<String> },
+}
+
+impl std::fmt::Display for CaaAuthorization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaaAuthorization::NoPolicy => f.write_str("no CAA policy published; any CA may issue"),
+            CaaAuthorization::Permitted { ca_domain } => write!(f, "permitted by the current CAA policy ({})", ca_domain),
+            CaaAuthorization::Forbidden { allowed } if allowed.is_empty() => {
+                f.write_str("not permitted by the current CAA policy, which forbids all issuance; it may have changed since issuance")
+            }
+            CaaAuthorization::Forbidden { allowed } => write!(f,
+                "not permitted by the current CAA policy, which allows only {}; it may have changed since issuance",
+                allowed.join(", ")),
+            CaaAuthorization::UnknownIssuer { allowed } => {
+                write!(f, "issuer not recognized; the current CAA policy allows {}", allowed.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MxRecord {
+    pub preference: u16,
+    pub exchange: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DnsReport {
+    pub domain: String,
+    pub resolver: String,
+    pub a: Vec<Ipv4Addr>,
+    pub aaaa: Vec<Ipv6Addr>,
+    pub cname: Option<String>,
+    pub mx: Vec<MxRecord>,
+    pub txt: Vec<String>,
+    pub caa: Vec<CaaRecord>,
+    /// Name the CAA records were found at, which may be a parent domain
+    pub caa_domain: Option<String>,
+    pub dnssec: DnssecStatus,
+    pub has_spf: bool,
+    pub has_dmarc: bool,
+    /// Filled in once the certificate issuer is known
+    pub issuer_authorization: Option<CaaAuthorization>,
+    /// Record types whose query failed, with the error; their fields above are empty, not absent
+    #[serde(default)]
+    pub lookup_errors: BTreeMap<String, String>,
+}
+
+impl DnsReport {
+    /// Whether the `record` query (e.g. "TXT" or "CAA") failed, leaving its result unknown
+    pub fn lookup_failed(&self, record: &str) -> bool {
+        self.lookup_errors.contains_key(record)
+    }
+}
+
+/// Answers to a supplementary query; a failure is recorded under `record` rather than failing the report
+async fn query_answers(
+    resolver: &DnsResolver,
+    name: &str,
+    record_type: u16,
+    record: &str,
+    errors: &mut BTreeMap<String, String>,
+) -> Vec<RecordData> {
+    match resolver.query(name, record_type).await {
+        Ok(response) => response.answers,
+        Err(e) => {
+            errors.insert(record.to_string(), e.to_string());
+            Vec::new()
+        }
+    }
+}
+
+/// Resolve the records used as legitimacy signals for `domain`
+pub async fn analyze_domain(resolver: &DnsResolver, domain: &str) -> Result<DnsReport> {
+    let a_response = resolver.query(domain, TYPE_A).await?;
+    if a_response.rcode == 3 {
+        return Err(anyhow::anyhow!("{} does not exist (NXDOMAIN)", domain));
+    }
+    let mut lookup_errors = BTreeMap::new();
+    let aaaa = query_answers(resolver, domain, TYPE_AAAA, "AAAA", &mut lookup_errors).await;
+    let mx = query_answers(resolver, domain, TYPE_MX, "MX", &mut lookup_errors).await;
+    let txt = query_answers(resolver, domain, TYPE_TXT, "TXT", &mut lookup_errors).await;
+    let dmarc = query_answers(resolver, &format!("_dmarc.{}", domain), TYPE_TXT, "DMARC", &mut lookup_errors).await;
+
+    let dnssec = if a_response.authenticated {
+        DnssecStatus::Validated
+    } else if a_response.signed() {
+        DnssecStatus::SignedUnvalidated
+    } else {
+        DnssecStatus::Unsigned
+    };
+
+    // RFC 8659: use the closest CAA record set, climbing towards (but excluding) the TLD
+    let mut caa = Vec::new();
+    let mut caa_domain = None;
+    let mut name = domain.trim_end_matches('.');
+    while name.contains('.') {
+        let response = match resolver.query(name, TYPE_CAA).await {
+            Ok(response) => response,
+            Err(e) => {
+                // Without every level of the tree the applicable policy is unknown
+                lookup_errors.insert("CAA".to_string(), e.to_string());
+                break;
+            }
+        };
+        let records: Vec<CaaRecord> = response.answers.into_iter()
+            .filter_map(|r| match r { RecordData::Caa(caa) => Some(caa), _ => None })
+            .collect();
+        if !records.is_empty() {
+            caa = records;
+            caa_domain = Some(name.to_string());
+            break;
+        }
+        name = name.split_once('.').map(|(_, parent)| parent).unwrap_or_default();
+    }
+
+    let txt: Vec<String> = txt.into_iter()
+        .filter_map(|r| match r { RecordData::Txt(t) => Some(t), _ => None })
+        .collect();
+    let has_spf = txt.iter().any(|t| t.to_ascii_lowercase().starts_with("v=spf1"));
+    let has_dmarc = dmarc.iter()
+        .any(|r| matches!(r, RecordData::Txt(t) if t.to_ascii_lowercase().starts_with("v=dmarc1")));
+
+    Ok(DnsReport {
+        domain: domain.to_string(),
+        resolver: resolver.server.to_string(),
+        a: a_response.answers.iter().filter_map(|r| match r { RecordData::A(ip) => Some(*ip), _ => None }).collect(),
+        aaaa: aaaa.iter().filter_map(|r| match r { RecordData::Aaaa(ip) => Some(*ip), _ => None }).collect(),
+        cname: a_response.answers.iter().find_map(|r| match r { RecordData::Cname(c) => Some(c.clone()), _ => None }),
+        mx: mx.into_iter()
+            .filter_map(|r| match r {
+                RecordData::Mx { preference, exchange } => Some(MxRecord { preference, exchange }),
+                _ => None,
+            })
+            .collect(),
+        txt,
+        caa,
+        caa_domain,
+        dnssec,
+        has_spf,
+        has_dmarc,
+        issuer_authorization: None,
+        lookup_errors,
+    })
+}
+
+/// Check a certificate issuer DN against the CAA `issue` (or `issuewild`) property
+pub fn check_caa(caa: &[CaaRecord], issuer: &str, wildcard: bool) -> CaaAuthorization {
+    let property = |tag: &str| -> Vec<String> {
+        caa.iter()
+            .filter(|r| r.tag.eq_ignore_ascii_case(tag))
+            // Parameters after ';' do not change which CA is named
+            .map(|r| r.value.split(';').next().unwrap_or_default().trim().to_ascii_lowercase())
+            .collect()
+    };
+    let mut allowed = if wildcard { property("issuewild") } else { Vec::new() };
+    if allowed.is_empty() {
+        allowed = property("issue");
+    }
+    if allowed.is_empty() {
+        return CaaAuthorization::NoPolicy;
+    }
+
+    let organization = issuer.split(", ")
+        .find_map(|rdn| rdn.strip_prefix("O="))
+        .unwrap_or(issuer)
+        .to_ascii_lowercase();
+    let identifiers: Vec<&str> = CA_IDENTIFIERS.iter()
+        .filter(|(name, _)| organization.contains(&name.to_ascii_lowercase()))
+        .flat_map(|(_, ids)| ids.iter().copied())
+        .collect();
+    if identifiers.is_empty() {
+        return CaaAuthorization::UnknownIssuer { allowed };
+    }
+    match allowed.iter().find(|a| identifiers.contains(&a.as_str())) {
+        Some(ca_domain) => CaaAuthorization::Permitted { ca_domain: ca_domain.clone() },
+        // An empty value (`issue ";"`) forbids every CA
+        None => CaaAuthorization::Forbidden { allowed: allowed.into_iter().filter(|a| !a.is_empty()).collect() },
+    }
+}
+
+fn encode_query(id: u16, name: &str, record_type: u16) -> Result<Vec<u8>> {
+    let mut out = id.to_be_bytes().to_vec();
+    // RD and AD set; one question; one additional (EDNS0 OPT)
+    out.extend_from_slice(&[0x01, 0x20, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01]);
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(anyhow::anyhow!("Invalid DNS name: {}", name));
+        }
+        out.push(label.len() as u8);
+        out.extend_from_slice(label.as_bytes());
+    }
+    out.push(0);
+    out.extend_from_slice(&record_type.to_be_bytes());
+    out.extend_from_slice(&[0x00, 0x01]);
+    // OPT: root name, 4096-byte payload, DO bit requests DNSSEC records
+    out.push(0);
+    out.extend_from_slice(&TYPE_OPT.to_be_bytes());
+    out.extend_from_slice(&4096u16.to_be_bytes());
+    out.extend_from_slice(&[0x00, 0x00, 0x80, 0x00, 0x00, 0x00]);
+    Ok(out)
+}
+
+fn parse_response(msg: &[u8]) -> Result<DnsResponse> {
+    if msg.len() < 12 {
+        return Err(anyhow::anyhow!("DNS response truncated"));
+    }
+    let rcode = msg[3] & 0x0f;
+    let authenticated = msg[3] & 0x20 != 0;
+    let qdcount = u16::from_be_bytes([msg[4], msg[5]]);
+    let ancount = u16::from_be_bytes([msg[6], msg[7]]);
+
+    let mut pos = 12;
+    for _ in 0..qdcount {
+        let (_, next) = read_name(msg, pos)?;
+        pos = next + 4;
+    }
+
+    let mut answers = Vec::new();
+    for _ in 0..ancount {
+        let (_, next) = read_name(msg, pos)?;
+        let header = msg.get(next..next + 10).ok_or_else(|| anyhow::anyhow!("DNS record truncated"))?;
+        let record_type = u16::from_be_bytes([header[0], header[1]]);
+        let rdlength = u16::from_be_bytes([header[8], header[9]]) as usize;
+        let start = next + 10;
+        let rdata = msg.get(start..start + rdlength).ok_or_else(|| anyhow::anyhow!("DNS record data truncated"))?;
+        answers.push(parse_rdata(msg, record_type, start, rdata)?);
+        pos = start + rdlength;
+    }
+    Ok(DnsResponse { rcode, authenticated, answers })
+}
+
+fn parse_rdata(msg: &[u8], record_type: u16, start: usize, rdata: &[u8]) -> Result<RecordData> {
+    Ok(match record_type {
+        TYPE_A if rdata.len() == 4 => RecordData::A(Ipv4Addr::new(rdata[0], rdata[1], rdata[2], rdata[3])),
+        TYPE_AAAA if rdata.len() == 16 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(rdata);
+            RecordData::Aaaa(Ipv6Addr::from(octets))
+        }
+        TYPE_CNAME => RecordData::Cname(read_name(msg, start)?.0),
+        TYPE_MX if rdata.len() >= 3 => RecordData::Mx {
+            preference: u16::from_be_bytes([rdata[0], rdata[1]]),
+            exchange: read_name(msg, start + 2)?.0,
+        },
+        TYPE_TXT => {
+            // One or more length-prefixed character strings, concatenated
+            let mut text = String::new();
+            let mut rest = rdata;
+            while let Some((&len, tail)) = rest.split_first() {
+                let chunk = tail.get(..len as usize).unwrap_or(tail);
+                text.push_str(&String::from_utf8_lossy(chunk));
+                rest = &tail[chunk.len()..];
+            }
+            RecordData::Txt(text)
+        }
+        TYPE_CAA if rdata.len() >= 2 => {
+            let tag_len = rdata[1] as usize;
+            let tag = rdata.get(2..2 + tag_len).ok_or_else(|| anyhow::anyhow!("CAA tag truncated"))?;
+            RecordData::Caa(CaaRecord {
+                flags: rdata[0],
+                tag: String::from_utf8_lossy(tag).into_owned(),
+                value: String::from_utf8_lossy(&rdata[2 + tag_len..]).into_owned(),
+            })
+        }
+        other => RecordData::Other(other),
+    })
+}
+
+/// Read a possibly compressed domain name, returning it and the offset after it
+fn read_name(msg: &[u8], mut pos: usize) -> Result<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut end = None;
+    // Bound pointer chasing so a malicious response cannot loop forever
+    for _ in 0..128 {
+        let len = *msg.get(pos).ok_or_else(|| anyhow::anyhow!("DNS name truncated"))? as usize;
+        if len == 0 {
+            return Ok((labels.join("."), end.unwrap_or(pos + 1)));
+        }
+        if len & 0xc0 == 0xc0 {
+            let low = *msg.get(pos + 1).ok_or_else(|| anyhow::anyhow!("DNS pointer truncated"))? as usize;
+            end.get_or_insert(pos + 2);
+            pos = ((len & 0x3f) << 8) | low;
+            continue;
+        }
+        let label = msg.get(pos + 1..pos + 1 + len).ok_or_else(|| anyhow::anyhow!("DNS label truncated"))?;
+        labels.push(String::from_utf8_lossy(label).into_owned());
+        pos += 1 + len;
+    }
+    Err(anyhow::anyhow!("DNS name compression loop"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Records served by the stub server, keyed by (name, type), each as (type, rdata)
+    type Zone = HashMap<(String, u16), Vec<(u16, Vec<u8>)>>;
+
+    /// Encode a record as (type, rdata) for the stub server
+    fn caa(tag: &str, value: &str) -> (u16, Vec<u8>) {
+        let mut rdata = vec![0, tag.len() as u8];
+        rdata.extend_from_slice(tag.as_bytes());
+        rdata.extend_from_slice(value.as_bytes());
+        (TYPE_CAA, rdata)
+    }
+
+    fn txt(value: &str) -> (u16, Vec<u8>) {
+        let mut rdata = vec![value.len() as u8];
+        rdata.extend_from_slice(value.as_bytes());
+        (TYPE_TXT, rdata)
+    }
+
+    /// Spawn a UDP DNS server answering from `zone`, keyed by (name, type); sets AD when `signed`.
+    /// Queries for the `unanswered` types are dropped, so they time out.
+    async fn stub_server(zone: Zone, signed: bool, unanswered: &'static [u16]) -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            loop {
+                let Ok((n, peer)) = socket.recv_from(&mut buf).await else { break };
+                let query = &buf[..n];
+                let (name, next) = read_name(query, 12).unwrap();
+                let qtype = u16::from_be_bytes([query[next], query[next + 1]]);
+                if unanswered.contains(&qtype) {
+                    continue;
+                }
+                let records = zone.get(&(name, qtype)).cloned().unwrap_or_default();
+
+                let mut response = query[..2].to_vec();
+                response.extend_from_slice(&[0x81, if signed { 0xa0 } else { 0x80 }, 0x00, 0x01]);
+                response.extend_from_slice(&(records.len() as u16).to_be_bytes());
+                response.extend_from_slice(&[0, 0, 0, 0]);
+                response.extend_from_slice(&query[12..next + 4]);
+                for (record_type, rdata) in records {
+                    // Name is a pointer back to the question
+                    response.extend_from_slice(&[0xc0, 0x0c]);
+                    response.extend_from_slice(&record_type.to_be_bytes());
+                    response.extend_from_slice(&[0x00, 0x01, 0x00, 0x00, 0x0e, 0x10]);
+                    response.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+                    response.extend_from_slice(&rdata);
+                }
+                let _ = socket.send_to(&response, peer).await;
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_analyze_domain_against_stub_server() {
+        let mut zone = Zone::new();
+        zone.insert(("example-project.com".to_string(), TYPE_A), vec![(TYPE_A, vec![192, 0, 2, 10])]);
+        let mut mx = vec![0, 10];
+        mx.extend_from_slice(&[4, b'm', b'a', b'i', b'l', 0xc0, 0x0c]);
+        zone.insert(("example-project.com".to_string(), TYPE_MX), vec![(TYPE_MX, mx)]);
+        zone.insert(("example-project.com".to_string(), TYPE_TXT), vec![txt("v=spf1 -all")]);
+        zone.insert(("_dmarc.example-project.com".to_string(), TYPE_TXT), vec![txt("v=DMARC1; p=reject")]);
+        // CAA lives on the parent of the queried name
+        zone.insert(("example-project.com".to_string(), TYPE_CAA), vec![caa("issue", "letsencrypt.org")]);
+
+        let resolver = DnsResolver::new(stub_server(zone, true, &[]).await);
+        let report = analyze_domain(&resolver, "www.example-project.com").await.unwrap();
+        assert_eq!(report.dnssec, DnssecStatus::Validated);
+        assert!(report.a.is_empty());
+        assert_eq!(report.caa_domain.as_deref(), Some("example-project.com"));
+        assert_eq!(report.caa[0].value, "letsencrypt.org");
+
+        let report = analyze_domain(&resolver, "example-project.com").await.unwrap();
+        assert_eq!(report.a, vec![Ipv4Addr::new(192, 0, 2, 10)]);
+        assert_eq!(report.mx[0].exchange, "mail.example-project.com");
+        assert!(report.has_spf && report.has_dmarc);
+        assert!(report.lookup_errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_queries_are_recorded_per_record_type() {
+        let mut zone = Zone::new();
+        zone.insert(("example-project.com".to_string(), TYPE_A), vec![(TYPE_A, vec![192, 0, 2, 10])]);
+        zone.insert(("example-project.com".to_string(), TYPE_TXT), vec![txt("v=spf1 -all")]);
+
+        let mut resolver = DnsResolver::new(stub_server(zone, false, &[TYPE_MX, TYPE_CAA]).await);
+        resolver.timeout = Duration::from_millis(200);
+        let report = analyze_domain(&resolver, "example-project.com").await.unwrap();
+        assert_eq!(report.a, vec![Ipv4Addr::new(192, 0, 2, 10)]);
+        assert!(report.has_spf);
+        assert_eq!(report.lookup_errors.keys().collect::<Vec<_>>(), vec!["CAA", "MX"]);
+        assert!(report.lookup_failed("CAA") && !report.lookup_failed("TXT"));
+    }
+
+    #[test]
+    fn test_check_caa() {
+        let records = vec![
+            CaaRecord { flags: 0, tag: "issue".to_string(), value: "letsencrypt.org".to_string() },
+            CaaRecord { flags: 0, tag: "issuewild".to_string(), value: ";".to_string() },
+        ];
+        let lets_encrypt = "C=US, O=Let's Encrypt, CN=R3";
+        assert_eq!(check_caa(&records, lets_encrypt, false), CaaAuthorization::Permitted { ca_domain: "letsencrypt.org".to_string() });
+        assert_eq!(check_caa(&records, lets_encrypt, true), CaaAuthorization::Forbidden { allowed: vec![] });
+        assert_eq!(
+            check_caa(&records, "C=GB, O=Sectigo Limited, CN=Sectigo RSA Domain Validation Secure Server CA", false),
+            CaaAuthorization::Forbidden { allowed: vec!["letsencrypt.org".to_string()] },
+        );
+        assert!(matches!(check_caa(&records, "CN=Shady CA", false), CaaAuthorization::UnknownIssuer { .. }));
+        assert_eq!(
+            check_caa(&records, "C=GB, O=Sectigo Limited, CN=Sectigo RSA Domain Validation Secure Server CA", false).to_string(),
+            "not permitted by the current CAA policy, which allows only letsencrypt.org; it may have changed since issuance",
+        );
+        assert_eq!(check_caa(&[], lets_encrypt, false), CaaAuthorization::NoPolicy);
+    }
+
+    #[test]
+    fn test_read_name_rejects_pointer_loops() {
+        let msg = [0u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xc0, 0x0c];
+        assert!(read_name(&msg, 12).is_err());
+    }
+}
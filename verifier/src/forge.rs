@@ -0,0 +1,632 @@
+use anyhow::Result;
+use regex::Regex;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::crawler::CRAWLER_AGENT;
+use crate::repo_health::{bus_factor, parse_timestamp, ItemActivity, RepositoryHealth};
+
+const DAY: u64 = 24 * 60 * 60;
+/// Issue and pull request states that mean the item is still being worked on
+const OPEN_STATES: &[&str] = &["open", "opened", "new", "on hold", "locked"];
+
+pub const DEFAULT_GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+pub const DEFAULT_GITEA_API_URL: &str = "https://codeberg.org/api/v1";
+pub const DEFAULT_BITBUCKET_API_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// GitHub's web host; its API is reached through `GitHubClient`
+const GITHUB_HOST: &str = "github.com";
+
+/// Public instances of the other forges: web host, software and API
+const PUBLIC_INSTANCES: &[(&str, ForgeKind, &str)] = &[
+    ("gitlab.com", ForgeKind::GitLab, DEFAULT_GITLAB_API_URL),
+    ("codeberg.org", ForgeKind::Gitea, DEFAULT_GITEA_API_URL),
+    ("bitbucket.org", ForgeKind::Bitbucket, DEFAULT_BITBUCKET_API_URL),
+];
+
+/// Software hosting a repository, which decides the API used to inspect it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+    /// Gitea and its Forgejo fork, e.g. Codeberg
+    Gitea,
+    Bitbucket,
+}
+
+impl std::fmt::Display for ForgeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ForgeKind::GitHub => "GitHub",
+            ForgeKind::GitLab => "GitLab",
+            ForgeKind::Gitea => "Gitea",
+            ForgeKind::Bitbucket => "Bitbucket",
+        })
+    }
+}
+
+/// A public repository on one of the recognized forges, e.g. `gitlab.com/owner/name`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceRepository {
+    pub forge: ForgeKind,
+    pub host: String,
+    /// `owner/name`, or `group/subgroup/name` on GitLab
+    pub path: String,
+}
+
+impl SourceRepository {
+    pub fn github(path: &str) -> Self {
+        Self { forge: ForgeKind::GitHub, host: "github.com".to_string(), path: path.to_string() }
+    }
+}
+
+impl std::fmt::Display for SourceRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.host, self.path)
+    }
+}
+
+/// Repository path in the segments following a forge host, e.g. `owner/name` from `owner/name.git/tree/main`
+fn repository_path(forge: ForgeKind, segments: &str) -> Option<String> {
+    let segments: Vec<&str> = segments.split('/').collect();
+    let mut path: Vec<&str> = match forge {
+        // Projects sit in arbitrarily nested groups; GitLab routes below a project start with `-`
+        ForgeKind::GitLab => segments.iter().take_while(|s| **s != "-").copied().collect(),
+        _ => segments.iter().take(2).copied().collect(),
+    };
+    let name = path.pop()?;
+    // Dots are valid in names, but a trailing one is sentence punctuation
+    let name = name.trim_end_matches('.');
+    let name = name.strip_suffix(".git").unwrap_or(name);
+    if path.is_empty() || name.is_empty() || path.iter().any(|s| s.trim_matches('.').is_empty()) {
+        return None;
+    }
+    Some(format!("{}/{}", path.join("/"), name))
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedRepository {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabProject {
+    path_with_namespace: String,
+    description: Option<String>,
+    #[serde(default)]
+    star_count: u32,
+    #[serde(default)]
+    forks_count: u32,
+    created_at: Option<String>,
+    last_activity_at: Option<String>,
+    #[serde(default)]
+    archived: bool,
+    forked_from_project: Option<GitLabForkParent>,
+    /// Present when requested with `license=true`
+    license: Option<GitLabLicense>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabForkParent {
+    path_with_namespace: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabLicense {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaRepository {
+    full_name: String,
+    description: Option<String>,
+    #[serde(default)]
+    stars_count: u32,
+    #[serde(default)]
+    forks_count: u32,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    fork: bool,
+    parent: Option<NamedRepository>,
+    #[serde(default)]
+    template: bool,
+    /// License names detected by Gitea 1.22 and later
+    #[serde(default)]
+    licenses: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketRepository {
+    full_name: String,
+    description: Option<String>,
+    created_on: Option<String>,
+    updated_on: Option<String>,
+    parent: Option<NamedRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabContributor {
+    commits: u32,
+}
+
+/// Bitbucket's paginated list envelope
+#[derive(Debug, Deserialize)]
+struct BitbucketPage<T> {
+    values: Vec<T>,
+    /// Total across all pages, when Bitbucket counts it
+    size: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketCommit {
+    date: Option<String>,
+    author: Option<BitbucketAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketAuthor {
+    /// `Name <email>` as written in the commit
+    raw: String,
+}
+
+/// Issue, merge request or pull request on any of the forges
+#[derive(Debug, Deserialize)]
+struct ForgeItem {
+    state: String,
+    #[serde(alias = "created_on")]
+    created_at: Option<String>,
+    closed_at: Option<String>,
+    /// GitLab leaves `closed_at` empty on merged merge requests
+    merged_at: Option<String>,
+    /// Bitbucket's only hint of when an item was closed
+    updated_on: Option<String>,
+}
+
+impl ForgeItem {
+    fn is_closed(&self) -> bool {
+        !OPEN_STATES.contains(&self.state.to_ascii_lowercase().as_str())
+    }
+
+    fn days_to_close(&self) -> Option<u64> {
+        if !self.is_closed() {
+            return None;
+        }
+        let opened = parse_timestamp(self.created_at.as_deref())?;
+        let closed = parse_timestamp(self.closed_at.as_deref().or(self.merged_at.as_deref()).or(self.updated_on.as_deref()))?;
+        Some(closed.saturating_sub(opened) / DAY)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ForgeRelease {
+    #[serde(alias = "released_at")]
+    published_at: Option<String>,
+    #[serde(default)]
+    draft: bool,
+}
+
+/// Open versus closed counts of `items`, and the days each closed one took
+fn item_activity(items: &[ForgeItem], days_to_close: &mut Vec<u64>) -> ItemActivity {
+    days_to_close.extend(items.iter().filter_map(ForgeItem::days_to_close));
+    ItemActivity { total: items.len() as u32, closed: items.iter().filter(|i| i.is_closed()).count() as u32 }
+}
+
+/// Record issue and pull request activity, with the median time to close across both
+fn set_item_activity(health: &mut RepositoryHealth, issues: Option<Vec<ForgeItem>>, pull_requests: Option<Vec<ForgeItem>>) {
+    let mut days_to_close = Vec::new();
+    health.issues = issues.map(|items| item_activity(&items, &mut days_to_close));
+    health.pull_requests = pull_requests.map(|items| item_activity(&items, &mut days_to_close));
+    days_to_close.sort_unstable();
+    health.median_days_to_close = days_to_close.get(days_to_close.len() / 2).copied();
+}
+
+fn set_releases(health: &mut RepositoryHealth, releases: Vec<ForgeRelease>, now: u64) {
+    let published: Vec<u64> = releases.iter()
+        .filter(|r| !r.draft)
+        .filter_map(|r| parse_timestamp(r.published_at.as_deref()))
+        .collect();
+    health.releases_365d = Some(published.iter().filter(|&&t| t + 365 * DAY > now).count() as u32);
+    health.last_release = published.iter().max().copied();
+}
+
+/// `since` query value `days` before `now`
+fn since(now: u64, days: u64) -> String {
+    chrono::DateTime::from_timestamp(now.saturating_sub(days * DAY) as i64, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// A host serving one of the non-GitHub forges, and the API that describes its repositories
+#[derive(Debug, Clone)]
+struct ForgeInstance {
+    host: String,
+    forge: ForgeKind,
+    api_url: String,
+}
+
+/// REST clients for the non-GitHub forges; GitHub goes through the shared `GitHubClient`
+pub struct ForgeClient {
+    http: reqwest::Client,
+    instances: Vec<ForgeInstance>,
+}
+
+impl Default for ForgeClient {
+    fn default() -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent(format!("{}/{}", CRAWLER_AGENT, env!("CARGO_PKG_VERSION")))
+            .build()
+            .expect("static client configuration is valid");
+        Self {
+            http,
+            instances: PUBLIC_INSTANCES.iter()
+                .map(|&(host, forge, api_url)| ForgeInstance { host: host.to_string(), forge, api_url: api_url.to_string() })
+                .collect(),
+        }
+    }
+}
+
+impl ForgeClient {
+    /// Recognize a self-hosted `forge` on the host of `base_url`, e.g. a GitLab at
+    /// `https://git.example.com/api/v4`; links to the public instances keep using their own APIs
+    pub fn with_api_url(self, forge: ForgeKind, base_url: &str) -> Self {
+        let host = url::Url::parse(base_url).ok().and_then(|url| {
+            let host = url.host_str()?.to_ascii_lowercase();
+            Some(match url.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host,
+            })
+        });
+        match host {
+            Some(host) => self.with_instance(&host, forge, base_url),
+            None => self,
+        }
+    }
+
+    /// Query `forge` links on `host` through the API at `api_url`, replacing any instance already on `host`
+    fn with_instance(mut self, host: &str, forge: ForgeKind, api_url: &str) -> Self {
+        // GitHub's URL is set on the GitHub client
+        if forge == ForgeKind::GitHub {
+            return self;
+        }
+        let instance = ForgeInstance { host: host.to_ascii_lowercase(), forge, api_url: api_url.trim_end_matches('/').to_string() };
+        self.instances.retain(|i| i.host != instance.host);
+        self.instances.push(instance);
+        self
+    }
+
+    /// Repository links to GitHub or a known forge instance, in page order and without duplicates
+    pub fn repository_links(&self, html: &str) -> Vec<SourceRepository> {
+        let hosts: Vec<(&str, ForgeKind)> = std::iter::once((GITHUB_HOST, ForgeKind::GitHub))
+            .chain(self.instances.iter().map(|i| (i.host.as_str(), i.forge)))
+            .collect();
+        let alternatives: Vec<String> = hosts.iter().map(|(host, _)| regex::escape(host)).collect();
+        // The leading character keeps `about.gitlab.com` or `notgithub.com` from matching a forge host
+        let pattern = Regex::new(&format!(r"(?i)(?:^|[^a-z0-9.-])({})/([a-z0-9_.-]+(?:/[a-z0-9_.-]+)+)", alternatives.join("|")))
+            .expect("forge link pattern is valid");
+        let mut links: Vec<SourceRepository> = Vec::new();
+        for cap in pattern.captures_iter(html) {
+            let host = cap[1].to_ascii_lowercase();
+            let Some(&(host, forge)) = hosts.iter().find(|(known, _)| *known == host) else {
+                continue;
+            };
+            let Some(path) = repository_path(forge, &cap[2]) else { continue };
+            let repo = SourceRepository { forge, host: host.to_string(), path };
+            if !links.contains(&repo) {
+                links.push(repo);
+            }
+        }
+        links
+    }
+
+    /// API URL of `endpoint` below `repo`, from the instance serving its host
+    fn repository_url(&self, repo: &SourceRepository, endpoint: &str) -> Result<String> {
+        let instance = self.instances.iter()
+            .find(|i| i.host == repo.host && i.forge == repo.forge)
+            .ok_or_else(|| anyhow::anyhow!("No {} API configured for {}", repo.forge, repo.host))?;
+        Ok(match repo.forge {
+            // GitLab addresses projects by their URL-encoded full path
+            ForgeKind::GitLab => format!("{}/projects/{}{}", instance.api_url, repo.path.replace('/', "%2F"), endpoint),
+            ForgeKind::Gitea => format!("{}/repos/{}{}", instance.api_url, repo.path, endpoint),
+            ForgeKind::Bitbucket => format!("{}/repositories/{}{}", instance.api_url, repo.path, endpoint),
+            ForgeKind::GitHub => return Err(anyhow::anyhow!("GitHub repositories are queried through the GitHub client")),
+        })
+    }
+
+    async fn get<T: DeserializeOwned>(&self, repo: &SourceRepository, endpoint: &str) -> Result<Option<T>> {
+        let response = self.http.get(self.repository_url(repo, endpoint)?).send().await?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        Ok(Some(response.json().await?))
+    }
+
+    /// Best-effort GET of an activity endpoint; `None` when the forge has no answer
+    async fn activity<T: DeserializeOwned>(&self, repo: &SourceRepository, endpoint: &str) -> Option<T> {
+        self.get(repo, endpoint).await.ok().flatten()
+    }
+
+    /// Items listed by `endpoint`: the forge's total count header, else the length of the first page
+    async fn count(&self, repo: &SourceRepository, endpoint: &str) -> Option<u32> {
+        let response = self.http.get(self.repository_url(repo, endpoint).ok()?).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let total = ["x-total", "x-total-count"].iter()
+            .find_map(|name| response.headers().get(*name)?.to_str().ok()?.parse().ok());
+        let page: Vec<IgnoredAny> = response.json().await.ok()?;
+        Some(total.unwrap_or(page.len() as u32))
+    }
+
+    pub async fn repo_exists(&self, repo: &SourceRepository) -> Result<bool> {
+        let response = self.http.get(self.repository_url(repo, "")?).send().await?;
+        Ok(response.status().is_success())
+    }
+
+    /// Repository metadata of `repo` plus whatever commit, contributor, issue and release
+    /// activity its forge reports; metrics the forge has no endpoint for are marked unmeasured
+    pub async fn fetch_repository_health(&self, repo: &SourceRepository, now: u64) -> Result<RepositoryHealth> {
+        let missing = || anyhow::anyhow!("Failed to fetch {} repository {}", repo.forge, repo);
+        let mut health = match repo.forge {
+            ForgeKind::GitLab => {
+                let project: GitLabProject = self.get(repo, "?license=true").await?.ok_or_else(missing)?;
+                RepositoryHealth {
+                    description: project.description,
+                    stars: project.star_count,
+                    forks: project.forks_count,
+                    license: project.license.map(|l| l.name),
+                    created_at: parse_timestamp(project.created_at.as_deref()),
+                    pushed_at: parse_timestamp(project.last_activity_at.as_deref()),
+                    archived: project.archived,
+                    is_fork: project.forked_from_project.is_some(),
+                    parent: project.forked_from_project.map(|p| p.path_with_namespace),
+                    ..RepositoryHealth::new(&project.path_with_namespace, now)
+                }
+            }
+            ForgeKind::Gitea => {
+                let info: GiteaRepository = self.get(repo, "").await?.ok_or_else(missing)?;
+                let updated_at = parse_timestamp(info.updated_at.as_deref());
+                RepositoryHealth {
+                    description: info.description,
+                    stars: info.stars_count,
+                    forks: info.forks_count,
+                    license: info.licenses.into_iter().next(),
+                    created_at: parse_timestamp(info.created_at.as_deref()),
+                    updated_at,
+                    pushed_at: updated_at,
+                    archived: info.archived,
+                    is_fork: info.fork,
+                    parent: info.parent.map(|p| p.full_name),
+                    is_template: info.template,
+                    ..RepositoryHealth::new(&info.full_name, now)
+                }
+            }
+            ForgeKind::Bitbucket => {
+                // Bitbucket has no stars and no archived flag
+                let info: BitbucketRepository = self.get(repo, "").await?.ok_or_else(missing)?;
+                let updated_at = parse_timestamp(info.updated_on.as_deref());
+                RepositoryHealth {
+                    description: info.description,
+                    created_at: parse_timestamp(info.created_on.as_deref()),
+                    updated_at,
+                    pushed_at: updated_at,
+                    is_fork: info.parent.is_some(),
+                    parent: info.parent.map(|p| p.full_name),
+                    ..RepositoryHealth::new(&info.full_name, now)
+                }
+            }
+            ForgeKind::GitHub => return Err(anyhow::anyhow!("GitHub repositories are queried through the GitHub client")),
+        };
+
+        match repo.forge {
+            ForgeKind::GitLab => self.gitlab_activity(repo, &mut health, now).await,
+            ForgeKind::Gitea => self.gitea_activity(repo, &mut health, now).await,
+            ForgeKind::Bitbucket => self.bitbucket_activity(repo, &mut health, now).await,
+            ForgeKind::GitHub => {}
+        }
+        Ok(health)
+    }
+
+    async fn gitlab_activity(&self, repo: &SourceRepository, health: &mut RepositoryHealth, now: u64) {
+        health.commits_90d = self.count(repo, &format!("/repository/commits?since={}&per_page=100", since(now, 90))).await;
+        health.commits_365d = self.count(repo, &format!("/repository/commits?since={}&per_page=100", since(now, 365))).await;
+
+        let contributors: Option<Vec<GitLabContributor>> = self.activity(repo, "/repository/contributors?per_page=100").await;
+        if let Some(contributors) = contributors {
+            health.contributors = Some(contributors.len() as u32);
+            health.bus_factor = Some(bus_factor(contributors.iter().map(|c| c.commits).collect()));
+        }
+
+        let issues = self.activity(repo, "/issues?scope=all&per_page=100").await;
+        let merge_requests = self.activity(repo, "/merge_requests?state=all&per_page=100").await;
+        set_item_activity(health, issues, merge_requests);
+
+        if let Some(releases) = self.activity(repo, "/releases?per_page=100").await {
+            set_releases(health, releases, now);
+        }
+    }
+
+    async fn gitea_activity(&self, repo: &SourceRepository, health: &mut RepositoryHealth, now: u64) {
+        health.commits_90d = self.count(repo, &format!("/commits?since={}&limit=50&stat=false", since(now, 90))).await;
+        health.commits_365d = self.count(repo, &format!("/commits?since={}&limit=50&stat=false", since(now, 365))).await;
+        // Gitea has no contributor statistics endpoint
+        health.unmeasured.push("contributors".to_string());
+
+        let issues = self.activity(repo, "/issues?state=all&type=issues&limit=50").await;
+        let pull_requests = self.activity(repo, "/issues?state=all&type=pulls&limit=50").await;
+        set_item_activity(health, issues, pull_requests);
+
+        if let Some(releases) = self.activity(repo, "/releases?limit=50").await {
+            set_releases(health, releases, now);
+        }
+    }
+
+    async fn bitbucket_activity(&self, repo: &SourceRepository, health: &mut RepositoryHealth, now: u64) {
+        // Bitbucket has neither stars nor releases
+        health.unmeasured.extend(["stars".to_string(), "releases".to_string()]);
+
+        let forks: Option<BitbucketPage<IgnoredAny>> = self.activity(repo, "/forks?pagelen=100").await;
+        if let Some(forks) = forks {
+            health.forks = forks.size.unwrap_or(forks.values.len() as u32);
+        }
+
+        // The newest commits stand in for commit and contributor statistics Bitbucket does not compute
+        let commits: Option<BitbucketPage<BitbucketCommit>> = self.activity(repo, "/commits?pagelen=100").await;
+        if let Some(commits) = commits {
+            let dates: Vec<u64> = commits.values.iter().filter_map(|c| parse_timestamp(c.date.as_deref())).collect();
+            let within = |days: u64| dates.iter().filter(|&&t| t + days * DAY > now).count() as u32;
+            health.commits_90d = Some(within(90));
+            health.commits_365d = Some(within(365));
+
+            let mut authors: HashMap<&str, u32> = HashMap::new();
+            for author in commits.values.iter().filter_map(|c| c.author.as_ref()) {
+                *authors.entry(author.raw.as_str()).or_default() += 1;
+            }
+            health.contributors = Some(authors.len() as u32);
+            health.bus_factor = Some(bus_factor(authors.into_values().collect()));
+        }
+
+        let issues: Option<BitbucketPage<ForgeItem>> = self.activity(repo, "/issues?pagelen=50").await;
+        let pull_requests: Option<BitbucketPage<ForgeItem>> =
+            self.activity(repo, "/pullrequests?state=OPEN&state=MERGED&state=DECLINED&state=SUPERSEDED&pagelen=50").await;
+        set_item_activity(health, issues.map(|p| p.values), pull_requests.map(|p| p.values));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const NOW: u64 = 1735689600; // 2025-01-01
+
+    #[test]
+    fn test_repository_links() {
+        let html = r#"<a href="https://gitlab.com/example-project/protocol">GitLab</a>
+            <a href="https://codeberg.org/example-project/sdk.git">Codeberg</a>
+            <a href="https://bitbucket.org/example-project/contracts/src/main/">Bitbucket</a>
+            <a href="https://github.com/example-project/protocol">GitHub</a>
+            <a href="https://gitlab.com/example-project/protocol/-/issues">Issues</a>
+            <a href="https://example.org/example-project/protocol">Elsewhere</a>"#;
+        let forges = ForgeClient::default();
+        let links: Vec<String> = forges.repository_links(html).iter().map(|r| r.to_string()).collect();
+        assert_eq!(links, vec![
+            "gitlab.com/example-project/protocol",
+            "codeberg.org/example-project/sdk",
+            "bitbucket.org/example-project/contracts",
+            "github.com/example-project/protocol",
+        ]);
+        assert_eq!(forges.repository_links(html)[1].forge, ForgeKind::Gitea);
+    }
+
+    #[test]
+    fn test_nested_groups_dotted_names_and_self_hosted_instances() {
+        let html = r#"<a href="https://gitlab.com/example-project/chain/node.rs/-/tree/main">Node</a>
+            <a href="https://github.com/example-project/example-project.github.io">Site</a>
+            See github.com/example-project/sdk.js.
+            <a href="https://git.example-project.com/core/contracts.git">Self-hosted</a>
+            <a href="https://about.gitlab.com/company/team/">Not a repository</a>
+            <a href="https://notgithub.com/example-project/fake">Look-alike host</a>"#;
+        let forges = ForgeClient::default().with_api_url(ForgeKind::GitLab, "https://git.example-project.com/api/v4");
+        let links: Vec<String> = forges.repository_links(html).iter().map(|r| r.to_string()).collect();
+        assert_eq!(links, vec![
+            "gitlab.com/example-project/chain/node.rs",
+            "github.com/example-project/example-project.github.io",
+            "github.com/example-project/sdk.js",
+            "git.example-project.com/core/contracts",
+        ]);
+
+        // gitlab.com stays on its own API next to the self-hosted instance
+        let public = SourceRepository { forge: ForgeKind::GitLab, host: "gitlab.com".to_string(), path: "example-project/chain/node.rs".to_string() };
+        assert_eq!(forges.repository_url(&public, "?license=true").unwrap(), "https://gitlab.com/api/v4/projects/example-project%2Fchain%2Fnode.rs?license=true");
+        let hosted = SourceRepository { host: "git.example-project.com".to_string(), path: "core/contracts".to_string(), ..public };
+        assert_eq!(forges.repository_url(&hosted, "?license=true").unwrap(), "https://git.example-project.com/api/v4/projects/core%2Fcontracts?license=true");
+        // Without the flag the self-hosted link is not recognized at all
+        assert_eq!(ForgeClient::default().repository_links(html).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_repository_health_from_each_forge() {
+        let server = test_http::serve(vec![
+            ("/gitlab/projects/example-project%2Fprotocol", MockResponse::ok(r#"{"path_with_namespace": "example-project/protocol",
+                "description": "Settlement layer", "star_count": 340, "forks_count": 21, "created_at": "2022-03-01T10:00:00.000Z",
+                "last_activity_at": "2024-12-28T09:30:00.000Z", "archived": false, "license": {"name": "Apache License 2.0"}}"#)),
+            ("/gitlab/projects/example-project%2Fprotocol/repository/commits", MockResponse::ok("[{}, {}]").with_header("X-Total", "25")),
+            ("/gitlab/projects/example-project%2Fprotocol/repository/commits", MockResponse::ok("[{}, {}]").with_header("X-Total", "140")),
+            ("/gitlab/projects/example-project%2Fprotocol/repository/contributors", MockResponse::ok(r#"[{"name": "a", "commits": 90},
+                {"name": "b", "commits": 40}, {"name": "c", "commits": 10}]"#)),
+            ("/gitlab/projects/example-project%2Fprotocol/issues", MockResponse::ok(r#"[
+                {"state": "closed", "created_at": "2024-12-01T00:00:00Z", "closed_at": "2024-12-03T00:00:00Z"},
+                {"state": "opened", "created_at": "2024-12-20T00:00:00Z", "closed_at": null}]"#)),
+            ("/gitlab/projects/example-project%2Fprotocol/merge_requests", MockResponse::ok(r#"[
+                {"state": "merged", "created_at": "2024-12-10T00:00:00Z", "closed_at": null, "merged_at": "2024-12-11T00:00:00Z"}]"#)),
+            ("/gitlab/projects/example-project%2Fprotocol/releases", MockResponse::ok(r#"[{"released_at": "2024-11-01T00:00:00Z"},
+                {"released_at": "2023-06-01T00:00:00Z"}]"#)),
+            ("/gitea/repos/example-project/sdk", MockResponse::ok(r#"{"full_name": "example-project/sdk", "description": null,
+                "stars_count": 12, "forks_count": 2, "created_at": "2023-05-01T00:00:00Z", "updated_at": "2024-12-01T00:00:00Z",
+                "archived": false, "fork": true, "parent": {"full_name": "upstream/sdk"}, "template": false, "licenses": ["MIT"]}"#)),
+            ("/gitea/repos/example-project/sdk/commits", MockResponse::ok("[{}, {}, {}]").with_header("X-Total-Count", "3")),
+            ("/gitea/repos/example-project/sdk/commits", MockResponse::ok("[{}, {}, {}]").with_header("X-Total-Count", "30")),
+            ("/gitea/repos/example-project/sdk/releases", MockResponse::ok(r#"[{"published_at": "2024-10-01T00:00:00Z", "draft": false},
+                {"published_at": "2024-12-30T00:00:00Z", "draft": true}]"#)),
+            ("/bitbucket/repositories/example-project/contracts", MockResponse::ok(r#"{"full_name": "example-project/contracts",
+                "description": "", "created_on": "2021-01-01T00:00:00.000000+00:00", "updated_on": "2024-06-01T12:00:00.123456+00:00"}"#)),
+            ("/bitbucket/repositories/example-project/contracts/forks", MockResponse::ok(r#"{"values": [], "size": 4}"#)),
+            ("/bitbucket/repositories/example-project/contracts/commits", MockResponse::ok(r#"{"values": [
+                {"date": "2024-12-20T10:00:00+00:00", "author": {"raw": "Ada <ada@example-project.com>"}},
+                {"date": "2024-11-02T10:00:00+00:00", "author": {"raw": "Ada <ada@example-project.com>"}},
+                {"date": "2024-03-02T10:00:00+00:00", "author": {"raw": "Lin <lin@example-project.com>"}}]}"#)),
+            ("/bitbucket/repositories/example-project/contracts/pullrequests", MockResponse::ok(r#"{"values": [
+                {"state": "MERGED", "created_on": "2024-11-01T00:00:00+00:00", "updated_on": "2024-11-05T00:00:00+00:00"},
+                {"state": "OPEN", "created_on": "2024-12-01T00:00:00+00:00", "updated_on": "2024-12-02T00:00:00+00:00"}]}"#)),
+        ]).await;
+        let forges = ForgeClient::default()
+            .with_instance("gitlab.com", ForgeKind::GitLab, &format!("{}/gitlab", server.base_url))
+            .with_instance("codeberg.org", ForgeKind::Gitea, &format!("{}/gitea/", server.base_url))
+            .with_instance("bitbucket.org", ForgeKind::Bitbucket, &format!("{}/bitbucket", server.base_url));
+        let repo = |forge, host: &str, path: &str| SourceRepository { forge, host: host.to_string(), path: path.to_string() };
+
+        let gitlab = forges.fetch_repository_health(&repo(ForgeKind::GitLab, "gitlab.com", "example-project/protocol"), NOW).await.unwrap();
+        assert_eq!((gitlab.stars, gitlab.forks), (340, 21));
+        assert_eq!(gitlab.license.as_deref(), Some("Apache License 2.0"));
+        assert_eq!(gitlab.pushed_at, Some(1735378200));
+        assert_eq!((gitlab.commits_90d, gitlab.commits_365d), (Some(25), Some(140)));
+        assert_eq!((gitlab.contributors, gitlab.bus_factor), (Some(3), Some(1)));
+        assert_eq!(gitlab.issues, Some(ItemActivity { total: 2, closed: 1 }));
+        assert_eq!(gitlab.pull_requests, Some(ItemActivity { total: 1, closed: 1 }));
+        assert_eq!(gitlab.median_days_to_close, Some(2));
+        assert_eq!(gitlab.releases_365d, Some(1));
+        assert!(gitlab.unmeasured.is_empty());
+
+        let gitea = forges.fetch_repository_health(&repo(ForgeKind::Gitea, "codeberg.org", "example-project/sdk"), NOW).await.unwrap();
+        assert_eq!(gitea.parent.as_deref(), Some("upstream/sdk"));
+        assert_eq!(gitea.license.as_deref(), Some("MIT"));
+        assert_eq!((gitea.commits_90d, gitea.commits_365d), (Some(3), Some(30)));
+        assert_eq!((gitea.releases_365d, gitea.last_release), (Some(1), Some(1727740800)));
+        // Issue endpoints that fail leave the activity unknown
+        assert_eq!(gitea.issues, None);
+        assert_eq!(gitea.unmeasured, vec!["contributors"]);
+
+        let bitbucket = forges.fetch_repository_health(&repo(ForgeKind::Bitbucket, "bitbucket.org", "example-project/contracts"), NOW).await.unwrap();
+        assert_eq!(bitbucket.repository, "example-project/contracts");
+        assert_eq!(bitbucket.pushed_at, Some(1717243200));
+        assert_eq!(bitbucket.forks, 4);
+        assert_eq!((bitbucket.commits_90d, bitbucket.commits_365d), (Some(2), Some(3)));
+        assert_eq!((bitbucket.contributors, bitbucket.bus_factor), (Some(2), Some(1)));
+        assert_eq!(bitbucket.pull_requests, Some(ItemActivity { total: 2, closed: 1 }));
+        assert_eq!(bitbucket.median_days_to_close, Some(4));
+        assert_eq!(bitbucket.unmeasured, vec!["stars", "releases"]);
+        let stars = bitbucket.score_components().into_iter().find(|c| c.metric == "stars").unwrap();
+        assert_eq!(stars.points, 0);
+
+        let missing = repo(ForgeKind::GitLab, "gitlab.com", "example-project/missing");
+        assert!(!forges.repo_exists(&missing).await.unwrap());
+        assert!(forges.fetch_repository_health(&missing, NOW).await.is_err());
+        assert!(forges.repo_exists(&SourceRepository::github("example-project/protocol")).await.is_err());
+    }
+}
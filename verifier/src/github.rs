@@ -0,0 +1,400 @@
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, ETAG, IF_NONE_MATCH, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::crawler::CRAWLER_AGENT;
+
+pub const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+/// Retries after a rate-limit response before giving up
+const MAX_RETRIES: u32 = 3;
+/// Longest wait for a rate limit to lift; a later reset fails the request instead
+const MAX_BACKOFF_SECS: u64 = 60;
+/// Responses without an ETag (GitHub sends none with a 404) cannot be revalidated and are reused this long
+const UNVALIDATED_CACHE_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResponse {
+    status: u16,
+    etag: Option<String>,
+    body: String,
+    fetched_at: u64,
+    /// Fetched with a token, so it may hold private data; kept for this run only
+    #[serde(skip)]
+    authenticated: bool,
+}
+
+/// GitHub API responses by URL, kept on disk between runs when a path is set
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ResponseCache {
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Responses changed since the file was last written
+    #[serde(skip)]
+    dirty: bool,
+    responses: BTreeMap<String, CachedResponse>,
+}
+
+impl ResponseCache {
+    fn load(path: &str) -> Result<Self> {
+        let mut cache = if Path::new(path).exists() {
+            let data = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read GitHub cache: {}", path))?;
+            serde_json::from_str::<Self>(&data)
+                .with_context(|| format!("Failed to parse GitHub cache: {}", path))?
+        } else {
+            Self::default()
+        };
+        cache.path = Some(PathBuf::from(path));
+        Ok(cache)
+    }
+
+    /// The file contents to write, or `None` when there is no file or nothing changed
+    fn take_snapshot(&mut self) -> Result<Option<(PathBuf, String)>> {
+        let Some(path) = self.path.clone().filter(|_| self.dirty) else { return Ok(None) };
+        let public = Self {
+            path: None,
+            dirty: false,
+            responses: self.responses.iter()
+                .filter(|(_, response)| !response.authenticated)
+                .map(|(url, response)| (url.clone(), response.clone()))
+                .collect(),
+        };
+        self.dirty = false;
+        Ok(Some((path, serde_json::to_string(&public)?)))
+    }
+}
+
+/// Request quota reported by the most recent response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub remaining: u32,
+    /// Unix time at which the quota refills
+    pub reset: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct GitHubResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl GitHubResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_str(&self.body)?)
+    }
+}
+
+impl From<CachedResponse> for GitHubResponse {
+    fn from(cached: CachedResponse) -> Self {
+        Self { status: cached.status, body: cached.body }
+    }
+}
+
+/// GitHub REST client shared by every lookup: one connection pool, a User-Agent, conditional
+/// requests against a response cache, and backoff when the rate limit is hit
+pub struct GitHubClient {
+    base_url: String,
+    token: Option<String>,
+    http: reqwest::Client,
+    cache: Mutex<ResponseCache>,
+    rate_limit: Mutex<Option<RateLimit>>,
+}
+
+impl GitHubClient {
+    pub fn new(base_url: &str, token: Option<String>) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent(format!("{}/{}", CRAWLER_AGENT, env!("CARGO_PKG_VERSION")))
+            .build()
+            .expect("static client configuration is valid");
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            http,
+            cache: Mutex::new(ResponseCache::default()),
+            rate_limit: Mutex::new(None),
+        }
+    }
+
+    /// Send requests to `base_url`, e.g. `https://github.example.com/api/v3` for GitHub Enterprise
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Keep responses in the file at `path` and revalidate them with ETags on later runs. Responses
+    /// to authenticated requests are never written; the file is updated by `save_cache`
+    pub fn with_cache(self, path: &str) -> Result<Self> {
+        *self.cache.lock().unwrap() = ResponseCache::load(path)?;
+        Ok(self)
+    }
+
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap()
+    }
+
+    /// GET `path` (e.g. `/repos/owner/name`) relative to the API base URL
+    pub async fn get(&self, path: &str) -> Result<GitHubResponse> {
+        let url = format!("{}{}", self.base_url, path);
+        let cached = self.cache.lock().unwrap().responses.get(&url).cloned();
+        if let Some(cached) = cached.clone().filter(|c| c.etag.is_none() && unix_now().saturating_sub(c.fetched_at) < UNVALIDATED_CACHE_SECS) {
+            return Ok(cached.into());
+        }
+        // With the quota spent, a stale answer beats a request that is sure to fail
+        let now = unix_now();
+        if let Some(limit) = self.rate_limit().filter(|l| l.remaining == 0 && l.reset > now) {
+            return cached.map(GitHubResponse::from)
+                .ok_or_else(|| anyhow::anyhow!("GitHub rate limit exhausted for {}s", limit.reset - now));
+        }
+
+        let mut attempt = 0;
+        loop {
+            let mut request = self.http.get(&url).header("Accept", "application/vnd.github+json");
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+            if let Some(etag) = cached.as_ref().and_then(|c| c.etag.as_deref()) {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            let response = request.send().await?;
+            let status = response.status();
+            if let Some(limit) = rate_limit(response.headers()) {
+                *self.rate_limit.lock().unwrap() = Some(limit);
+            }
+
+            if status == StatusCode::NOT_MODIFIED {
+                if let Some(mut cached) = cached {
+                    cached.fetched_at = unix_now();
+                    self.store(&url, cached.clone());
+                    return Ok(cached.into());
+                }
+            }
+            if matches!(status, StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS) {
+                if let Some(wait) = backoff_secs(status, response.headers(), attempt) {
+                    if wait > MAX_BACKOFF_SECS || attempt >= MAX_RETRIES {
+                        return cached.map(GitHubResponse::from)
+                            .ok_or_else(|| anyhow::anyhow!("GitHub rate limit exceeded for {}; retry in {}s", url, wait));
+                    }
+                    println!("⏳ GitHub rate limit hit, retrying in {}s", wait);
+                    tokio::time::sleep(Duration::from_secs(wait)).await;
+                    attempt += 1;
+                    continue;
+                }
+            }
+
+            let etag = response.headers().get(ETAG).and_then(|v| v.to_str().ok()).map(str::to_string);
+            let body = response.text().await?;
+            // A 202 means GitHub is still computing the answer; the next request should ask again
+            if (status.is_success() && status != StatusCode::ACCEPTED) || status == StatusCode::NOT_FOUND {
+                self.store(&url, CachedResponse {
+                    status: status.as_u16(),
+                    etag,
+                    body: body.clone(),
+                    fetched_at: unix_now(),
+                    authenticated: self.token.is_some(),
+                });
+            }
+            return Ok(GitHubResponse { status: status.as_u16(), body });
+        }
+    }
+
+    /// Run a GraphQL query; GitHub only answers these for authenticated clients
+    pub async fn graphql(&self, query: &str, variables: serde_json::Value) -> Result<serde_json::Value> {
+        let token = self.token.as_deref()
+            .ok_or_else(|| anyhow::anyhow!("GitHub GraphQL API requires a token"))?;
+        // GitHub Enterprise serves GraphQL beside its REST root rather than below it
+        let url = match self.base_url.strip_suffix("/v3") {
+            Some(api_root) => format!("{}/graphql", api_root),
+            None => format!("{}/graphql", self.base_url),
+        };
+        let response = self.http.post(&url)
+            .bearer_auth(token)
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await?;
+        if let Some(limit) = rate_limit(response.headers()) {
+            *self.rate_limit.lock().unwrap() = Some(limit);
+        }
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("GitHub GraphQL API returned {}", response.status()));
+        }
+        let mut body: serde_json::Value = response.json().await?;
+        if let Some(errors) = body.get("errors").filter(|e| !e.is_null()) {
+            return Err(anyhow::anyhow!("GitHub GraphQL query failed: {}", errors));
+        }
+        Ok(body["data"].take())
+    }
+
+    /// Whether `repo` (`owner/name`) exists and is visible with the configured token
+    pub async fn repo_exists(&self, repo: &str) -> Result<bool> {
+        Ok(self.get(&format!("/repos/{}", repo)).await?.is_success())
+    }
+
+    /// Write the responses gathered so far to the cache file, if one is set and anything changed
+    pub async fn save_cache(&self) -> Result<()> {
+        let Some((path, data)) = self.cache.lock().unwrap().take_snapshot()? else { return Ok(()) };
+        tokio::task::spawn_blocking(move || {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, data).with_context(|| format!("Failed to write GitHub cache: {}", path.display()))
+        }).await?
+    }
+
+    fn store(&self, url: &str, response: CachedResponse) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.responses.insert(url.to_string(), response);
+        cache.dirty = true;
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+fn rate_limit(headers: &HeaderMap) -> Option<RateLimit> {
+    Some(RateLimit {
+        remaining: header_u64(headers, "x-ratelimit-remaining")?.try_into().ok()?,
+        reset: header_u64(headers, "x-ratelimit-reset")?,
+    })
+}
+
+/// Seconds to wait before retrying a 403/429, or `None` for a 403 that is a plain permission error
+fn backoff_secs(status: StatusCode, headers: &HeaderMap, attempt: u32) -> Option<u64> {
+    if let Some(seconds) = header_u64(headers, RETRY_AFTER.as_str()) {
+        return Some(seconds);
+    }
+    if let Some(limit) = rate_limit(headers).filter(|l| l.remaining == 0) {
+        return Some(limit.reset.saturating_sub(unix_now()).max(1));
+    }
+    // Secondary limits may come without headers; back off exponentially
+    (status == StatusCode::TOO_MANY_REQUESTS).then(|| 1 << attempt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const REPO: &str = r#"{"full_name": "example-project/protocol", "stargazers_count": 42}"#;
+
+    #[tokio::test]
+    async fn test_conditional_requests_survive_restart() {
+        let server = test_http::serve(vec![
+            ("/repos/example-project/protocol", MockResponse::ok(REPO)
+                .with_header("ETag", "\"v1\"")
+                .with_header("X-RateLimit-Remaining", "59")
+                .with_header("X-RateLimit-Reset", "4102444800")),
+            ("/repos/example-project/protocol", MockResponse::status(304)),
+        ]).await;
+        let path = std::env::temp_dir().join(format!("poa_github_cache_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = fs::remove_file(path);
+
+        let client = GitHubClient::new(DEFAULT_GITHUB_API_URL, None)
+            .with_base_url(&server.base_url)
+            .with_cache(path)
+            .unwrap();
+        let response = client.get("/repos/example-project/protocol").await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(client.rate_limit(), Some(RateLimit { remaining: 59, reset: 4102444800 }));
+        // Responses are written in one go, not per request
+        assert!(!Path::new(path).exists());
+        client.save_cache().await.unwrap();
+
+        // A fresh client revalidates the stored response instead of downloading it again
+        let client = GitHubClient::new(&server.base_url, None).with_cache(path).unwrap();
+        let response = client.get("/repos/example-project/protocol").await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, REPO);
+
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        let first = requests[0].to_ascii_lowercase();
+        assert!(first.contains(&format!("user-agent: {}", CRAWLER_AGENT)));
+        assert!(!first.contains("if-none-match"));
+        assert!(requests[1].to_ascii_lowercase().contains("if-none-match: \"v1\""));
+        let _ = fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_and_pending_responses_are_not_kept() {
+        let server = test_http::serve(vec![
+            ("/repos/example-project/internal", MockResponse::ok(REPO).with_header("ETag", "\"private\"")),
+            ("/repos/example-project/protocol/stats/commit_activity", MockResponse::status(202)),
+            ("/repos/example-project/protocol/stats/commit_activity", MockResponse::ok("[]")),
+        ]).await;
+        let path = std::env::temp_dir().join(format!("poa_github_private_cache_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = fs::remove_file(path);
+
+        let client = GitHubClient::new(&server.base_url, Some("test-token".to_string())).with_cache(path).unwrap();
+        let response = client.get("/repos/example-project/internal").await.unwrap();
+        assert_eq!(response.status, 200);
+        assert!(server.requests.lock().unwrap()[0].to_ascii_lowercase().contains("authorization: bearer test-token"));
+        // GitHub is still computing the statistics, so the next call asks again
+        assert_eq!(client.get("/repos/example-project/protocol/stats/commit_activity").await.unwrap().status, 202);
+        assert_eq!(client.get("/repos/example-project/protocol/stats/commit_activity").await.unwrap().status, 200);
+        client.save_cache().await.unwrap();
+
+        let saved = fs::read_to_string(path).unwrap();
+        assert!(!saved.contains("/repos/example-project/internal"));
+        assert_eq!(server.requests.lock().unwrap().len(), 3);
+        let _ = fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_is_cached_and_rate_limits_back_off() {
+        let server = test_http::serve(vec![
+            ("/repos/example-project/missing", MockResponse::status(404)),
+            ("/repos/example-project/protocol", MockResponse::status(429).with_header("Retry-After", "1")),
+            ("/repos/example-project/protocol", MockResponse::ok(REPO)),
+            ("/repos/example-project/private", MockResponse::status(403)),
+        ]).await;
+        let client = GitHubClient::new(&server.base_url, None);
+
+        assert!(!client.repo_exists("example-project/missing").await.unwrap());
+        assert!(!client.repo_exists("example-project/missing").await.unwrap());
+        assert!(client.repo_exists("example-project/protocol").await.unwrap());
+        // A 403 without rate-limit headers is a permission error, not a reason to wait
+        assert_eq!(client.get("/repos/example-project/private").await.unwrap().status, 403);
+
+        let requests = server.requests.lock().unwrap();
+        let paths: Vec<&str> = requests.iter().map(|r| r.split_whitespace().nth(1).unwrap()).collect();
+        assert_eq!(paths, vec![
+            "/repos/example-project/missing",
+            "/repos/example-project/protocol",
+            "/repos/example-project/protocol",
+            "/repos/example-project/private",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_quota_fails_without_request() {
+        let server = test_http::serve(vec![
+            ("/repos/example-project/protocol", MockResponse::status(403)
+                .with_header("X-RateLimit-Remaining", "0")
+                .with_header("X-RateLimit-Reset", "4102444800")),
+        ]).await;
+        let client = GitHubClient::new(&server.base_url, None);
+
+        assert!(client.get("/repos/example-project/protocol").await.is_err());
+        assert!(client.repo_exists("example-project/other").await.is_err());
+        assert_eq!(server.requests.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,315 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use url::Url;
+
+use crate::github::GitHubClient;
+use crate::public_suffix;
+
+const DAY: u64 = 24 * 60 * 60;
+/// Repositories pushed to within this window count as active
+const ACTIVE_WINDOW_DAYS: u64 = 90;
+
+const PINNED_QUERY: &str = "query($login: String!) { organization(login: $login) { \
+    pinnedItems(first: 6, types: REPOSITORY) { nodes { ... on Repository { nameWithOwner } } } } }";
+const VERIFIED_DOMAINS_QUERY: &str = "query($login: String!) { organization(login: $login) { \
+    domains(first: 50, isVerified: true) { nodes { domain } } } }";
+
+#[derive(Debug, Deserialize)]
+struct GitHubOrganization {
+    login: String,
+    name: Option<String>,
+    /// Website from the organization profile
+    blog: Option<String>,
+    /// GitHub verified ownership of at least one of the organization's domains, not necessarily the blog's
+    #[serde(default)]
+    is_verified: bool,
+    #[serde(default)]
+    public_repos: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrganizationRepository {
+    full_name: String,
+    language: Option<String>,
+    #[serde(default)]
+    stargazers_count: u32,
+    #[serde(default)]
+    forks_count: u32,
+    pushed_at: Option<String>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    fork: bool,
+}
+
+/// How firmly a GitHub organization is tied to the analyzed domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrganizationLink {
+    /// The analyzed domain is among the domains GitHub verified for the organization
+    VerifiedDomain,
+    /// The organization's self-declared website is the analyzed domain, which anyone can claim
+    Website,
+    /// Only the name suggests the organization belongs to the project
+    Unconfirmed,
+}
+
+/// Activity aggregated over an organization's public repositories
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationAnalysis {
+    pub login: String,
+    pub name: Option<String>,
+    pub website: Option<String>,
+    pub is_verified: bool,
+    /// Domains GitHub verified for the organization; unknown without a token allowed to list them
+    pub verified_domains: Option<Vec<String>>,
+    pub link: OrganizationLink,
+    pub public_repos: u32,
+    /// Public members on the first page of the member list; hidden memberships are not counted
+    pub public_members: Option<u32>,
+    /// Repositories on the first page of the repository list (at most 100)
+    pub repositories_analyzed: u32,
+    pub total_stars: u32,
+    pub total_forks: u32,
+    pub active_repos: u32,
+    pub archived_repos: u32,
+    /// Number of repositories per primary language
+    pub languages: BTreeMap<String, u32>,
+    /// Repositories pinned on the organization profile; unknown without a token
+    pub pinned: Option<Vec<String>>,
+    /// Most starred active original repository, the best guess for the project's main code
+    pub main_repository: Option<String>,
+}
+
+impl OrganizationAnalysis {
+    /// Languages by repository count, most used first
+    pub fn language_mix(&self) -> Vec<(&str, u32)> {
+        let mut languages: Vec<(&str, u32)> = self.languages.iter().map(|(l, c)| (l.as_str(), *c)).collect();
+        languages.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        languages
+    }
+}
+
+impl std::fmt::Display for OrganizationAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({:?}): {} public repos, {} active in {} days, {} stars",
+            self.login, self.link, self.public_repos, self.active_repos, ACTIVE_WINDOW_DAYS, self.total_stars)?;
+        if let Some(members) = self.public_members {
+            write!(f, ", {} public members", members)?;
+        }
+        let languages: Vec<String> = self.language_mix().iter().take(3).map(|(l, c)| format!("{} {}", l, c)).collect();
+        if !languages.is_empty() {
+            write!(f, ", languages {}", languages.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The website is on the same site as `domain`: `docs.example.com` matches `app.example.com`, but a bare
+/// `github.io` or `co.uk` matches nothing
+fn website_matches(website: &str, domain: &str) -> bool {
+    let website = if website.contains("://") { website.to_string() } else { format!("https://{}", website) };
+    let Some(host) = Url::parse(&website).ok().and_then(|u| u.host_str().map(str::to_string)) else {
+        return false;
+    };
+    public_suffix::site_domain(&host) == public_suffix::site_domain(domain)
+}
+
+/// `domain` is a verified domain of the organization or a subdomain of one
+fn domain_is_verified(verified_domains: &[String], domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    verified_domains.iter().any(|verified| {
+        let verified = verified.trim_end_matches('.').to_ascii_lowercase();
+        domain == verified || domain.ends_with(&format!(".{}", verified))
+    })
+}
+
+/// Look up `candidates` as organizations in order and analyze the first that exists
+pub async fn resolve_organization(
+    github: &GitHubClient,
+    domain: &str,
+    candidates: &[String],
+    now: u64,
+) -> Result<Option<OrganizationAnalysis>> {
+    let mut seen = Vec::new();
+    for login in candidates {
+        let login = login.to_ascii_lowercase();
+        if login.is_empty() || seen.contains(&login) {
+            continue;
+        }
+        seen.push(login.clone());
+        let response = github.get(&format!("/orgs/{}", login)).await?;
+        if response.is_success() {
+            return analyze_organization(github, domain, response.json()?, now).await.map(Some);
+        }
+    }
+    Ok(None)
+}
+
+async fn analyze_organization(
+    github: &GitHubClient,
+    domain: &str,
+    organization: GitHubOrganization,
+    now: u64,
+) -> Result<OrganizationAnalysis> {
+    let website = organization.blog.filter(|b| !b.trim().is_empty());
+    // `is_verified` only says some domain was verified; the list says which, and needs a token
+    let verified_domains = if organization.is_verified {
+        github.graphql(VERIFIED_DOMAINS_QUERY, json!({ "login": organization.login })).await.ok().and_then(|data| {
+            data["organization"]["domains"]["nodes"].as_array().map(|nodes| {
+                nodes.iter().filter_map(|n| n["domain"].as_str().map(str::to_string)).collect::<Vec<_>>()
+            })
+        })
+    } else {
+        Some(Vec::new())
+    };
+    let link = if verified_domains.as_deref().is_some_and(|domains| domain_is_verified(domains, domain)) {
+        OrganizationLink::VerifiedDomain
+    } else if website.as_deref().is_some_and(|w| website_matches(w, domain)) {
+        OrganizationLink::Website
+    } else {
+        OrganizationLink::Unconfirmed
+    };
+    let mut analysis = OrganizationAnalysis {
+        login: organization.login.clone(),
+        name: organization.name,
+        website,
+        is_verified: organization.is_verified,
+        verified_domains,
+        link,
+        public_repos: organization.public_repos,
+        public_members: None,
+        repositories_analyzed: 0,
+        total_stars: 0,
+        total_forks: 0,
+        active_repos: 0,
+        archived_repos: 0,
+        languages: BTreeMap::new(),
+        pinned: None,
+        main_repository: None,
+    };
+
+    let response = github.get(&format!("/orgs/{}/repos?type=public&sort=pushed&per_page=100", organization.login)).await?;
+    if response.is_success() {
+        let repositories: Vec<OrganizationRepository> = response.json()?;
+        let active_since = now.saturating_sub(ACTIVE_WINDOW_DAYS * DAY);
+        let mut main: Option<&OrganizationRepository> = None;
+        for repository in &repositories {
+            analysis.repositories_analyzed += 1;
+            analysis.total_stars += repository.stargazers_count;
+            analysis.total_forks += repository.forks_count;
+            if repository.archived {
+                analysis.archived_repos += 1;
+            }
+            let pushed = repository.pushed_at.as_deref()
+                .and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok())
+                .and_then(|date| u64::try_from(date.timestamp()).ok());
+            let active = !repository.archived && pushed.is_some_and(|t| t >= active_since);
+            if active {
+                analysis.active_repos += 1;
+            }
+            if let Some(language) = &repository.language {
+                *analysis.languages.entry(language.clone()).or_default() += 1;
+            }
+            if active && !repository.fork && main.is_none_or(|m| repository.stargazers_count > m.stargazers_count) {
+                main = Some(repository);
+            }
+        }
+        analysis.main_repository = main.map(|r| r.full_name.clone());
+    }
+
+    let response = github.get(&format!("/orgs/{}/public_members?per_page=100", organization.login)).await?;
+    if response.is_success() {
+        analysis.public_members = Some(response.json::<Vec<serde_json::Value>>()?.len() as u32);
+    }
+
+    // Pinned repositories are only exposed through GraphQL
+    if let Ok(data) = github.graphql(PINNED_QUERY, json!({ "login": organization.login })).await {
+        analysis.pinned = data["organization"]["pinnedItems"]["nodes"].as_array().map(|nodes| {
+            nodes.iter().filter_map(|n| n["nameWithOwner"].as_str().map(str::to_string)).collect()
+        });
+    }
+    Ok(analysis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const NOW: u64 = 1735689600; // 2025-01-01
+
+    #[test]
+    fn test_website_matches() {
+        assert!(website_matches("https://www.example-project.com/", "example-project.com"));
+        assert!(website_matches("example-project.com", "app.example-project.com"));
+        assert!(website_matches("https://docs.example-project.com", "example-project.com"));
+        assert!(!website_matches("https://example-project.io", "example-project.com"));
+        assert!(!website_matches("https://notexample-project.com", "example-project.com"));
+        // A blog field naming only a public suffix is not every site under it
+        assert!(!website_matches("https://github.io", "alice.github.io"));
+        assert!(!website_matches("co.uk", "example-project.co.uk"));
+        assert!(!website_matches("https://bob.github.io", "alice.github.io"));
+    }
+
+    #[tokio::test]
+    async fn test_squatter_with_another_verified_domain_is_not_verified() {
+        // The org verified some domain of its own and merely names the project's site as its blog
+        let server = test_http::serve(vec![
+            ("/orgs/example-project", MockResponse::ok(r#"{"login": "example-project", "blog": "https://example-project.com",
+                "is_verified": true, "public_repos": 1}"#)),
+            ("/graphql", MockResponse::ok(r#"{"data": {"organization": {"domains": {"nodes": [{"domain": "squatter.example"}]}}}}"#)),
+        ]).await;
+        let github = GitHubClient::new(&server.base_url, Some("test-token".to_string()));
+        let org = resolve_organization(&github, "example-project.com", &["example-project".to_string()], NOW).await.unwrap().unwrap();
+        assert_eq!(org.link, OrganizationLink::Website);
+        assert!(domain_is_verified(org.verified_domains.as_deref().unwrap(), "app.squatter.example"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_organization() {
+        let server = test_http::serve(vec![
+            ("/orgs/example-project", MockResponse::ok(r#"{"login": "example-project", "name": "Example Project",
+                "blog": "https://example-project.com", "is_verified": true, "public_repos": 4}"#)),
+            ("/orgs/example-project/repos", MockResponse::ok(r#"[
+                {"full_name": "example-project/protocol", "language": "Rust", "stargazers_count": 420, "forks_count": 30,
+                 "pushed_at": "2024-12-20T00:00:00Z", "archived": false, "fork": false},
+                {"full_name": "example-project/sdk", "language": "TypeScript", "stargazers_count": 80, "forks_count": 5,
+                 "pushed_at": "2024-11-20T00:00:00Z", "archived": false, "fork": false},
+                {"full_name": "example-project/solana", "language": "Rust", "stargazers_count": 900, "forks_count": 200,
+                 "pushed_at": "2024-12-01T00:00:00Z", "archived": false, "fork": true},
+                {"full_name": "example-project/v1", "language": "Solidity", "stargazers_count": 1000, "forks_count": 10,
+                 "pushed_at": "2022-01-01T00:00:00Z", "archived": true, "fork": false}]"#)),
+            ("/orgs/example-project/public_members", MockResponse::ok(r#"[{"login": "ada-example"}, {"login": "gracesample"}]"#)),
+            ("/graphql", MockResponse::ok(r#"{"data": {"organization": {"domains": {"nodes": [
+                {"domain": "example-project.com"}]}}}}"#)),
+            ("/graphql", MockResponse::ok(r#"{"data": {"organization": {"pinnedItems": {"nodes": [
+                {"nameWithOwner": "example-project/protocol"}, {"nameWithOwner": "example-project/sdk"}]}}}}"#)),
+        ]).await;
+        let github = GitHubClient::new(&server.base_url, Some("test-token".to_string()));
+        let candidates = vec!["example-project-com".to_string(), "example-project".to_string()];
+
+        let org = resolve_organization(&github, "example-project.com", &candidates, NOW).await.unwrap().unwrap();
+        assert_eq!(org.link, OrganizationLink::VerifiedDomain);
+        assert_eq!(org.verified_domains, Some(vec!["example-project.com".to_string()]));
+        assert_eq!((org.repositories_analyzed, org.active_repos, org.archived_repos), (4, 3, 1));
+        assert_eq!(org.total_stars, 2400);
+        assert_eq!(org.public_members, Some(2));
+        assert_eq!(org.language_mix(), vec![("Rust", 2), ("Solidity", 1), ("TypeScript", 1)]);
+        assert_eq!(org.pinned, Some(vec!["example-project/protocol".to_string(), "example-project/sdk".to_string()]));
+        // Forks and archived repositories are never the project's main code
+        assert_eq!(org.main_repository.as_deref(), Some("example-project/protocol"));
+
+        // Without a token the pinned repositories and verified domains stay unknown, so the blog is only a claim
+        let github = GitHubClient::new(&server.base_url, None);
+        let org = resolve_organization(&github, "example-project.com", &candidates, NOW).await.unwrap().unwrap();
+        assert_eq!(org.link, OrganizationLink::Website);
+        assert_eq!((org.pinned, org.verified_domains), (None, None));
+        let org = resolve_organization(&github, "example-project.io", &candidates, NOW).await.unwrap().unwrap();
+        assert_eq!(org.link, OrganizationLink::Unconfirmed);
+
+        let missing = resolve_organization(&github, "example-project.com", &["nobody".to_string()], NOW).await.unwrap();
+        assert!(missing.is_none());
+    }
+}
@@ -0,0 +1,521 @@
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::public_suffix;
+
+/// Ordinary words that happen to sit an edit or two from a brand name
+const DICTIONARY_WORDS: &str = include_str!("../lexicon/dictionary_words.txt");
+
+/// Well-established domains; they and their subdomains get the established-company baseline
+pub const ESTABLISHED_DOMAINS: &[&str] = &[
+    // Tech Giants
+    "google.com", "microsoft.com", "apple.com", "amazon.com", "facebook.com",
+    "twitter.com", "linkedin.com", "youtube.com", "instagram.com", "whatsapp.com",
+    "netflix.com", "spotify.com", "uber.com", "airbnb.com", "tesla.com",
+
+    // Financial Institutions
+    "paypal.com", "stripe.com", "visa.com", "mastercard.com", "americanexpress.com",
+    "wellsfargo.com", "chase.com", "bankofamerica.com", "citibank.com",
+
+    // E-commerce & Services
+    "ebay.com", "etsy.com", "shopify.com", "square.com", "zoom.us", "slack.com",
+    "dropbox.com", "salesforce.com", "adobe.com", "oracle.com", "ibm.com",
+
+    // Media & Entertainment
+    "cnn.com", "bbc.com", "reuters.com", "bloomberg.com", "forbes.com",
+    "wikipedia.org", "reddit.com", "stackoverflow.com", "github.com",
+];
+
+/// Government & education suffixes, matched as `.gov` or as a second level like `.gov.uk`
+const INSTITUTIONAL_SUFFIXES: &[&str] = &["gov", "edu", "mil"];
+
+/// Hosting domains that carry user content under a brand's name, with the brand that runs them
+const BRAND_HOSTING_DOMAINS: &[(&str, &str)] = &[
+    ("github.io", "github.com"),
+    ("githubusercontent.com", "github.com"),
+    ("amazonaws.com", "amazon.com"),
+    ("googleusercontent.com", "google.com"),
+    ("youtu.be", "youtube.com"),
+];
+
+/// Minimum brand name length for edit-distance matching; shorter names are one edit from ordinary
+/// words (chase/phase, slack/stack)
+const MIN_TYPO_BRAND_LEN: usize = 6;
+
+const KEYBOARD_ROWS: &[&str] = &["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// `domain` is an established domain, a subdomain of one, or a government/education domain
+pub fn is_established(domain: &str) -> bool {
+    let domain = normalize(domain);
+    if ESTABLISHED_DOMAINS.iter().any(|established| owns(established, &domain)) {
+        return true;
+    }
+    let labels: Vec<&str> = domain.split('.').collect();
+    match labels.as_slice() {
+        [.., _, tld] if INSTITUTIONAL_SUFFIXES.contains(tld) => true,
+        [.., _, second, tld] => tld.len() == 2 && INSTITUTIONAL_SUFFIXES.contains(second),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImpersonationTechnique {
+    /// Look-alike characters from other scripts, punycode or digit swaps fold to the brand name
+    Homoglyph,
+    /// One character replaced by a neighbouring key
+    KeyboardTypo,
+    Typosquat { distance: usize },
+    /// Brand name used as a label or hyphen-separated part of a label of an unrelated domain
+    EmbeddedBrand,
+    /// Exact brand name registered under another TLD
+    TldSwap,
+}
+
+impl ImpersonationTechnique {
+    /// Risk points this technique contributes
+    pub fn risk(&self) -> u8 {
+        match self {
+            ImpersonationTechnique::Homoglyph => 6,
+            ImpersonationTechnique::KeyboardTypo => 5,
+            ImpersonationTechnique::Typosquat { distance } => if *distance == 1 { 5 } else { 4 },
+            ImpersonationTechnique::EmbeddedBrand => 4,
+            ImpersonationTechnique::TldSwap => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for ImpersonationTechnique {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImpersonationTechnique::Homoglyph => f.write_str("look-alike characters"),
+            ImpersonationTechnique::KeyboardTypo => f.write_str("adjacent-key typo"),
+            ImpersonationTechnique::Typosquat { distance } => write!(f, "{} edit(s) away", distance),
+            ImpersonationTechnique::EmbeddedBrand => f.write_str("brand name embedded in another domain"),
+            ImpersonationTechnique::TldSwap => f.write_str("brand name under another TLD"),
+        }
+    }
+}
+
+/// A domain that resembles a protected brand without belonging to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImpersonationFinding {
+    /// The analyzed domain with punycode labels decoded
+    pub domain: String,
+    /// Official domain of the brand, or the bare name for brands listed without one
+    pub brand: String,
+    pub technique: ImpersonationTechnique,
+}
+
+impl std::fmt::Display for ImpersonationFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} imitates {} ({})", self.domain, self.brand, self.technique)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Brand {
+    /// Registrable label, e.g. `paypal` for `paypal.com`
+    name: String,
+    domain: Option<String>,
+}
+
+impl Brand {
+    fn parse(entry: &str) -> Option<Self> {
+        let entry = normalize(entry);
+        if entry.is_empty() {
+            return None;
+        }
+        if !entry.contains('.') {
+            return Some(Self { name: entry, domain: None });
+        }
+        let labels: Vec<&str> = entry.split('.').collect();
+        let name = labels[labels.len() - suffix_len(&entry)? - 1];
+        Some(Self { name: name.to_string(), domain: Some(entry) })
+    }
+
+    fn label(&self) -> &str {
+        self.domain.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Compares domains against the established domains plus any user-supplied brands
+#[derive(Debug, Clone)]
+pub struct ImpersonationDetector {
+    brands: Vec<Brand>,
+    /// Registrable labels that are real words and never count as typos
+    dictionary: HashSet<String>,
+}
+
+impl Default for ImpersonationDetector {
+    fn default() -> Self {
+        Self {
+            brands: ESTABLISHED_DOMAINS.iter().filter_map(|d| Brand::parse(d)).collect(),
+            dictionary: DICTIONARY_WORDS.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl ImpersonationDetector {
+    /// Add brands from a file with one domain (or bare brand name) per line; `#` starts a comment
+    pub fn with_brand_file(mut self, path: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read brand list {}", path))?;
+        self.brands.extend(
+            contents.lines()
+                .map(|line| line.split('#').next().unwrap_or_default())
+                .filter_map(Brand::parse),
+        );
+        Ok(self)
+    }
+
+    /// Every brand `domain` appears to impersonate, at most one finding per brand
+    pub fn check(&self, domain: &str) -> Vec<ImpersonationFinding> {
+        let domain = normalize(domain);
+        if is_established(&domain) {
+            return Vec::new();
+        }
+        // Pages on a brand's hosting platform carry its name legitimately; other brands in them do not
+        let host_brand = BRAND_HOSTING_DOMAINS.iter()
+            .find(|(hosting, _)| owns(hosting, &domain))
+            .map(|&(_, brand)| brand);
+
+        let Some(suffix_len) = suffix_len(&domain) else {
+            return Vec::new();
+        };
+        let decoded: Vec<String> = domain.split('.').map(decode_label).collect();
+        let labels: Vec<&str> = decoded.iter().map(String::as_str).collect();
+        let registrable = labels[labels.len() - suffix_len - 1];
+        let display = decoded.join(".");
+        let candidate = Candidate {
+            registrable,
+            skeleton: fold_homoglyphs(registrable),
+            is_word: self.dictionary.contains(registrable),
+            hosted: public_suffix::site_domain(&domain) != public_suffix::registered_domain(&domain),
+            // Labels left of the public suffix, split on hyphens: paypal-secure-login -> paypal, secure, login
+            labels: labels[..labels.len() - suffix_len].iter()
+                .map(|label| label.split('-').map(fold_homoglyphs).collect())
+                .collect(),
+        };
+
+        self.brands.iter()
+            .filter(|brand| brand.domain.as_deref().is_none_or(|owned| !owns(owned, &domain) && Some(owned) != host_brand))
+            .filter_map(|brand| {
+                let technique = classify(&candidate, brand)?;
+                Some(ImpersonationFinding {
+                    domain: display.clone(),
+                    brand: brand.label().to_string(),
+                    technique,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The analyzed domain in the forms the techniques compare against
+struct Candidate<'a> {
+    registrable: &'a str,
+    skeleton: String,
+    is_word: bool,
+    /// Registered under a privately run suffix such as github.io, so the registrable label is a user's choice
+    hosted: bool,
+    /// Hyphen-separated parts of each label left of the public suffix, homoglyphs folded
+    labels: Vec<Vec<String>>,
+}
+
+fn classify(candidate: &Candidate, brand: &Brand) -> Option<ImpersonationTechnique> {
+    let name = fold_homoglyphs(&brand.name);
+    if candidate.registrable == brand.name {
+        if candidate.hosted {
+            return Some(ImpersonationTechnique::EmbeddedBrand);
+        }
+        // A bare brand name says nothing about which TLDs the brand owns
+        return brand.domain.as_ref().map(|_| ImpersonationTechnique::TldSwap);
+    }
+    if candidate.skeleton == name {
+        return Some(ImpersonationTechnique::Homoglyph);
+    }
+    if name.chars().count() >= MIN_TYPO_BRAND_LEN && !candidate.is_word {
+        let distance = edit_distance(&candidate.skeleton, &name);
+        if distance == 1 && is_keyboard_typo(candidate.registrable, &brand.name) {
+            return Some(ImpersonationTechnique::KeyboardTypo);
+        }
+        if distance == 1 || (distance == 2 && name.chars().count() >= 8) {
+            return Some(ImpersonationTechnique::Typosquat { distance });
+        }
+    }
+    // Only whole hyphen-separated parts count, so purchase does not contain chase
+    let parts: Vec<&str> = name.split('-').collect();
+    let embedded = candidate.labels.iter()
+        .any(|label| label.windows(parts.len()).any(|window| window.iter().eq(parts.iter())));
+    embedded.then_some(ImpersonationTechnique::EmbeddedBrand)
+}
+
+fn normalize(domain: &str) -> String {
+    domain.trim().trim_end_matches('.').to_lowercase()
+}
+
+/// `domain` is `owner` or one of its subdomains
+fn owns(owner: &str, domain: &str) -> bool {
+    domain == owner || domain.ends_with(&format!(".{}", owner))
+}
+
+/// Number of labels in the public suffix, private suffixes like github.io included; `None` without a
+/// registrable label in front of it
+fn suffix_len(domain: &str) -> Option<usize> {
+    let site = public_suffix::site_domain(domain);
+    let suffix_len = site.split('.').count().checked_sub(1).filter(|&len| len > 0)?;
+    let registrable = site.split('.').next()?;
+    (!registrable.is_empty()).then_some(suffix_len)
+}
+
+/// Decode an `xn--` label to Unicode, leaving anything else (or malformed punycode) untouched
+fn decode_label(label: &str) -> String {
+    label.strip_prefix("xn--")
+        .and_then(punycode_decode)
+        .unwrap_or_else(|| label.to_string())
+}
+
+/// RFC 3492 punycode decoding
+fn punycode_decode(input: &str) -> Option<String> {
+    const BASE: u32 = 36;
+    const TMIN: u32 = 1;
+    const TMAX: u32 = 26;
+
+    let (basic, encoded) = match input.rfind('-') {
+        Some(pos) => (&input[..pos], &input[pos + 1..]),
+        None => ("", input),
+    };
+    if !basic.is_ascii() {
+        return None;
+    }
+    let mut output: Vec<char> = basic.chars().collect();
+    let mut n: u32 = 128;
+    let mut i: u32 = 0;
+    let mut bias: u32 = 72;
+    let mut digits = encoded.chars().peekable();
+
+    while digits.peek().is_some() {
+        let old_i = i;
+        let mut weight: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let digit = match digits.next()? {
+                c @ 'a'..='z' => c as u32 - 'a' as u32,
+                c @ 'A'..='Z' => c as u32 - 'A' as u32,
+                c @ '0'..='9' => c as u32 - '0' as u32 + 26,
+                _ => return None,
+            };
+            i = i.checked_add(digit.checked_mul(weight)?)?;
+            let t = if k <= bias { TMIN } else if k >= bias + TMAX { TMAX } else { k - bias };
+            if digit < t {
+                break;
+            }
+            weight = weight.checked_mul(BASE - t)?;
+            k += BASE;
+        }
+        let length = output.len() as u32 + 1;
+        bias = adapt_bias(i - old_i, length, old_i == 0);
+        n = n.checked_add(i / length)?;
+        i %= length;
+        output.insert(i as usize, char::from_u32(n)?);
+        i += 1;
+    }
+    Some(output.into_iter().collect())
+}
+
+fn adapt_bias(delta: u32, length: u32, first: bool) -> u32 {
+    let mut delta = if first { delta / 700 } else { delta / 2 };
+    delta += delta / length;
+    let mut k = 0;
+    while delta > ((36 - 1) * 26) / 2 {
+        delta /= 36 - 1;
+        k += 36;
+    }
+    k + (36 * delta) / (delta + 38)
+}
+
+/// Map look-alike characters to the ASCII letters they imitate
+fn fold_homoglyphs(label: &str) -> String {
+    let folded: String = label.chars()
+        .map(|c| match c {
+            // Cyrillic
+            'а' => 'a', 'е' => 'e', 'о' => 'o', 'р' => 'p', 'с' => 'c', 'у' => 'y', 'х' => 'x',
+            'і' => 'i', 'ј' => 'j', 'ѕ' => 's', 'ԁ' => 'd', 'ԛ' => 'q', 'ԝ' => 'w', 'һ' => 'h',
+            'ӏ' => 'l', 'к' => 'k', 'в' => 'b', 'м' => 'm', 'т' => 't', 'н' => 'h',
+            // Greek
+            'α' => 'a', 'ε' => 'e', 'ι' => 'i', 'κ' => 'k', 'ν' => 'v', 'ο' => 'o', 'ρ' => 'p',
+            'υ' => 'u', 'χ' => 'x',
+            // Armenian
+            'օ' => 'o', 'ո' => 'n', 'ս' => 'u', 'հ' => 'h',
+            // Latin look-alikes and diacritics
+            'ɡ' => 'g', 'ɩ' => 'l', 'ı' => 'i', 'ł' => 'l',
+            'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' | 'ą' => 'a',
+            'é' | 'è' | 'ê' | 'ë' | 'ę' | 'ė' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' | 'õ' | 'ø' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' | 'ć' => 'c', 'ñ' | 'ń' => 'n', 'ý' | 'ÿ' => 'y', 'ś' | 'š' => 's', 'ž' | 'ź' | 'ż' => 'z',
+            // Digits standing in for letters
+            '0' => 'o', '1' => 'l', '3' => 'e', '5' => 's',
+            other => other,
+        })
+        .collect();
+    folded.replace("vv", "w")
+}
+
+/// Optimal string alignment distance: insertions, deletions, substitutions and adjacent transpositions
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// `candidate` differs from `name` by exactly one character replaced with a neighbouring QWERTY key
+fn is_keyboard_typo(candidate: &str, name: &str) -> bool {
+    let candidate: Vec<char> = candidate.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    if candidate.len() != name.len() {
+        return false;
+    }
+    let mut differences = candidate.iter().zip(&name).filter(|(a, b)| a != b);
+    match (differences.next(), differences.next()) {
+        (Some((&typed, &intended)), None) => keys_adjacent(typed, intended),
+        _ => false,
+    }
+}
+
+fn keys_adjacent(a: char, b: char) -> bool {
+    let position = |c: char| {
+        KEYBOARD_ROWS.iter().enumerate().find_map(|(row, keys)| keys.find(c).map(|col| (row as i32, col as i32)))
+    };
+    match (position(a), position(b)) {
+        // Rows are staggered: a key touches its row neighbours and two keys in each adjacent row
+        (Some((ra, ca)), Some((rb, cb))) => match ra - rb {
+            0 => (ca - cb).abs() == 1,
+            1 => cb == ca || cb == ca + 1,
+            -1 => ca == cb || ca == cb + 1,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn techniques(detector: &ImpersonationDetector, domain: &str) -> Vec<(String, ImpersonationTechnique)> {
+        detector.check(domain).into_iter().map(|f| (f.brand, f.technique)).collect()
+    }
+
+    #[test]
+    fn test_established_requires_exact_or_subdomain_match() {
+        assert!(is_established("paypal.com"));
+        assert!(is_established("www.PayPal.com."));
+        assert!(is_established("whitehouse.gov"));
+        assert!(is_established("service.gov.uk"));
+
+        assert!(!is_established("paypal-secure-login.xyz"));
+        assert!(!is_established("paypal.com.account-verify.xyz"));
+        assert!(!is_established("notpaypal.com"));
+        assert!(!is_established("governance-token.io"));
+    }
+
+    #[test]
+    fn test_homoglyphs_and_punycode() {
+        assert_eq!(punycode_decode("pypal-4ve").as_deref(), Some("pаypal"));
+        assert_eq!(punycode_decode("mnchen-3ya").as_deref(), Some("münchen"));
+        assert_eq!(punycode_decode("80ak6aa92e").as_deref(), Some("аррӏе"));
+
+        let detector = ImpersonationDetector::default();
+        let findings = detector.check("xn--80ak6aa92e.com");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].to_string(), "аррӏе.com imitates apple.com (look-alike characters)");
+        assert_eq!(techniques(&detector, "paypa1.com"), vec![("paypal.com".to_string(), ImpersonationTechnique::Homoglyph)]);
+        // rn is left alone so ordinary words like "modern" keep their letters; it still costs two edits
+        assert_eq!(techniques(&detector, "rnicrosoft.com"), vec![("microsoft.com".to_string(), ImpersonationTechnique::Typosquat { distance: 2 })]);
+    }
+
+    #[test]
+    fn test_typos_and_embedded_brands() {
+        let detector = ImpersonationDetector::default();
+        let paypal = |technique| vec![("paypal.com".to_string(), technique)];
+
+        assert_eq!(techniques(&detector, "paypak.com"), paypal(ImpersonationTechnique::KeyboardTypo));
+        assert_eq!(techniques(&detector, "papyal.com"), paypal(ImpersonationTechnique::Typosquat { distance: 1 }));
+        assert_eq!(techniques(&detector, "paypal.xyz"), paypal(ImpersonationTechnique::TldSwap));
+        assert_eq!(techniques(&detector, "paypal-secure-login.xyz"), paypal(ImpersonationTechnique::EmbeddedBrand));
+        assert_eq!(techniques(&detector, "paypal.com.account-verify.xyz"), paypal(ImpersonationTechnique::EmbeddedBrand));
+        assert_eq!(techniques(&detector, "my-paypal-wallet.co.uk"), paypal(ImpersonationTechnique::EmbeddedBrand));
+
+        assert!(detector.check("www.paypal.com").is_empty());
+        assert!(detector.check("example-project.com").is_empty());
+        // Short brand names only match whole labels
+        assert!(detector.check("cnnews.com").is_empty());
+    }
+
+    #[test]
+    fn test_brands_on_hosting_platforms() {
+        let detector = ImpersonationDetector::default();
+        let paypal = vec![("paypal.com".to_string(), ImpersonationTechnique::EmbeddedBrand)];
+
+        // The hosting brand's own name is expected there; any other brand is the user's choice
+        assert!(detector.check("octocat.github.io").is_empty());
+        assert!(detector.check("github-docs.github.io").is_empty());
+        assert!(detector.check("assets.s3.amazonaws.com").is_empty());
+        assert_eq!(techniques(&detector, "paypal-login.github.io"), paypal);
+        assert_eq!(techniques(&detector, "paypal.s3.amazonaws.com"), paypal);
+        assert_eq!(techniques(&detector, "paypal.githubusercontent.com"), paypal);
+    }
+
+    #[test]
+    fn test_ordinary_words_are_not_impersonation() {
+        let detector = ImpersonationDetector::default();
+        // Brand names inside a word, without a label or hyphen boundary
+        for domain in ["squarespace.com", "purchase.com", "pineapple.com", "mypaypalwallet.com", "stackoverflowing.dev"] {
+            assert!(detector.check(domain).is_empty(), "{}: {:?}", domain, detector.check(domain));
+        }
+        // Dictionary words and short brands one edit away
+        for domain in ["phase.com", "stack.com", "credit.com", "strike.io", "squire.com", "casebook.org"] {
+            assert!(detector.check(domain).is_empty(), "{}: {:?}", domain, detector.check(domain));
+        }
+        assert!(detector.check("modern.com").is_empty());
+        assert_eq!(techniques(&detector, "chase-login.com"), vec![("chase.com".to_string(), ImpersonationTechnique::EmbeddedBrand)]);
+    }
+
+    #[test]
+    fn test_user_brand_list() {
+        let path = std::env::temp_dir().join(format!("poa_brands_{}.txt", std::process::id()));
+        std::fs::write(&path, "# Portfolio projects\nacme-finance.io\nzenith  # no official domain yet\n").unwrap();
+        let detector = ImpersonationDetector::default().with_brand_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(detector.check("app.acme-finance.io").is_empty());
+        assert_eq!(techniques(&detector, "acme-finance.app"), vec![("acme-finance.io".to_string(), ImpersonationTechnique::TldSwap)]);
+        assert_eq!(techniques(&detector, "acme-finanse.com"), vec![("acme-finance.io".to_string(), ImpersonationTechnique::Typosquat { distance: 1 })]);
+        assert_eq!(techniques(&detector, "zenith-airdrop.net"), vec![("zenith".to_string(), ImpersonationTechnique::EmbeddedBrand)]);
+        assert!(detector.check("zenith.net").is_empty());
+    }
+}
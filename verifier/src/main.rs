@@ -4,9 +4,52 @@ use sha2::{Digest, Sha256};
 use std::fs;
 use std::time::Instant;
 use std::env;
+use std::path::Path;
 
+/// Noir project holding the attestation circuit, its witness input and generated proofs
+const NOIR_DIR: &str = "../noir";
+
+mod audit_registry;
+mod certificate_history;
+mod certificate_transparency;
+mod chain_validation;
+mod clone_detection;
+mod commit_signing;
+mod crawler;
+mod der;
+mod dns;
+mod forge;
+mod github;
+mod github_org;
+mod impersonation;
+mod ocsp;
+mod page_analysis;
+mod public_suffix;
+mod rdap;
 mod real_zk_tls;
-use real_zk_tls::RealZkTlsVerifier;
+mod repo_health;
+mod revocation;
+mod scam_language;
+mod security_headers;
+mod security_txt;
+mod team;
+#[cfg(test)]
+mod test_http;
+mod tls_audit;
+mod tls_handshake;
+mod tokenomics;
+use audit_registry::{AuditReportCheck, AuditStatus};
+use clone_detection::{OriginReport, RepositoryOrigin};
+use commit_signing::SigningReport;
+use forge::{ForgeKind, SourceRepository};
+use github_org::{OrganizationAnalysis, OrganizationLink};
+use real_zk_tls::{CertificateProvenance, RealTransparencyData, RealWitnessData, RealZkTlsVerifier};
+use repo_health::RepositoryHealth;
+use scam_language::PhraseMatch;
+use security_headers::{HeaderFinding, HeaderStatus};
+use security_txt::SignatureStatus;
+use team::{TeamMember, VerificationLevel};
+use tokenomics::TokenDistribution;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct ProofData {
@@ -25,6 +68,9 @@ struct ProofMetadata {
     generation_time_ms: u128,
     entropy_sum: u32,
     proof_type: String,
+    /// Where the proven certificate came from; absent in proofs saved before provenance tracking
+    #[serde(default)]
+    certificate_provenance: Option<CertificateProvenance>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -40,6 +86,9 @@ struct WitnessInput {
     expiry_date: u64,
     public_key_hash: Vec<u8>,
     salt: Vec<u8>,
+    /// Not a circuit input; tells consumers whether the certificate was actually observed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    certificate_provenance: Option<CertificateProvenance>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -61,6 +110,7 @@ struct LegitimacyAssessment {
     risk_factors: Vec<String>,
     transparency_indicators: Vec<String>,
     overall_recommendation: String,
+    certificate_provenance: Option<CertificateProvenance>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -77,16 +127,50 @@ struct CertificateInfo {
     expiry_date: u64,
     is_valid: bool,
     serial_number_hash: String,
+    provenance: Option<CertificateProvenance>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct TransparencyMetrics {
-    has_public_github: bool,
+    #[serde(alias = "has_public_github")]
+    has_public_repository: bool,
     has_documented_roadmap: bool,
     has_audit_reports: bool,
     has_team_verification: bool,
     has_token_economics: bool,
     code_review_score: u8,
+    /// Valid, unexpired security.txt with a reachable contact
+    #[serde(default)]
+    has_security_txt: bool,
+    #[serde(default)]
+    security_txt_signature: Option<SignatureStatus>,
+    /// Linked audit reports and whether the auditor registry confirms them
+    #[serde(default)]
+    audit_reports: Vec<AuditReportCheck>,
+    /// Team members and the verification level each reached
+    #[serde(default)]
+    team_members: Vec<TeamMember>,
+    /// Token distribution extracted from the whitepaper or tokenomics page
+    #[serde(default)]
+    tokenomics: Option<TokenDistribution>,
+    /// Repository activity and the explained components of `code_review_score`
+    #[serde(default)]
+    repository_health: Option<RepositoryHealth>,
+    /// Forge and path of the project's source repository
+    #[serde(default)]
+    source_repository: Option<SourceRepository>,
+    /// Original development, fork, template instance or copy of a known upstream
+    #[serde(default)]
+    repository_origin: Option<OriginReport>,
+    /// Signed commits and release tags, and the maintainers' signing keys
+    #[serde(default)]
+    commit_signing: Option<SigningReport>,
+    /// Analyzed with `--supply-chain`, so unsigned releases count against the project
+    #[serde(default)]
+    supply_chain_sensitive: bool,
+    /// The project's GitHub organization and how firmly it is tied to the domain
+    #[serde(default)]
+    github_organization: Option<OrganizationAnalysis>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -96,10 +180,57 @@ struct RiskFactors {
     unusual_certificate_issuer: bool,
     short_certificate_validity: bool,
     missing_security_headers: Vec<String>,
+    /// Per-header verdicts from the live site; empty when the site was not fetched
+    #[serde(default)]
+    security_header_findings: Vec<HeaderFinding>,
+    /// Lexicon phrases found in the site's visible text, with context
+    #[serde(default)]
+    scam_phrase_matches: Vec<PhraseMatch>,
+    /// Expired security.txt or one whose signature does not verify
+    #[serde(default)]
+    security_txt_problems: Vec<String>,
 }
 
+const USAGE: &str = "\
+Usage: verifier [DOMAIN | --real-data DOMAIN | --cert-file PATH [--domain DOMAIN]] [OPTIONS]
+
+Certificates:
+  --cert-file PATH           Analyze a PEM/DER certificate file instead of connecting
+  --trust-store PATH         Validate chains against these roots instead of the system store
+  --ct-log-list PATH         Verify SCTs against the CT logs in a log_list.json
+  --crl PATH                 Check revocation against a CRL file (repeatable)
+  --ocsp-response PATH       Check revocation against an OCSP response file (repeatable)
+  --cert-history PATH        Certificate history store
+  --strict                   Fail instead of falling back when the handshake fails
+
+Lookups:
+  --offline                  Skip every network lookup apart from the handshake
+  --dns-resolver IP:PORT     Send DNS queries to this server
+  --rdap-url URL             RDAP service (default https://rdap.org)
+  --github-api-url URL       GitHub API, e.g. GitHub Enterprise
+  --gitlab-api-url URL       Self-hosted GitLab API
+  --gitea-api-url URL        Self-hosted Gitea/Forgejo API
+  --bitbucket-api-url URL    Bitbucket API
+  --github-cache PATH        Keep unauthenticated GitHub responses in this file between runs
+
+Analysis:
+  --brands PATH              Extra brands to check for impersonation, one per line
+  --scam-lexicon PATH        Weighted scam phrases replacing the built-in lexicon
+  --audit-registry PATH      Auditor publications added to the built-in audit firms
+  --upstream-corpus PATH     Known upstream repositories to detect copied code
+  --crawl-depth N            Links followed from the front page
+  --crawl-pages N            Pages fetched per site
+  --supply-chain             The project's code is built on by others: score release
+                             signatures above popularity and flag unsigned releases
+
+Set GITHUB_TOKEN for higher GitHub rate limits and organization domain checks.";
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    if env::args().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
     println!("🔍 Starting REAL zkTLS transparency rating verification process...");
 
     // Parse command line arguments
@@ -118,57 +249,118 @@ async fn main() -> Result<()> {
             .unwrap_or_else(|| "github.com".to_string())
     };
 
-    let witness_data = if use_real_data {
-        println!("🌐 Using REAL data for domain: {}", domain);
-        
+    let cert_file = arg_value(&args, "--cert-file");
+    let (witness_data, real_transparency) = if use_real_data || cert_file.is_some() {
         // Get GitHub token from environment (optional)
         let github_token = env::var("GITHUB_TOKEN").ok();
-        let verifier = RealZkTlsVerifier::new(github_token);
+        let mut verifier = RealZkTlsVerifier::new(github_token);
+        if let Some(trust_store) = arg_value(&args, "--trust-store") {
+            verifier = verifier.with_trust_store(&trust_store)?;
+        }
+        if let Some(log_list) = arg_value(&args, "--ct-log-list") {
+            verifier = verifier.with_ct_log_list(&log_list)?;
+        }
+        verifier = verifier.with_revocation_sources(
+            arg_values(&args, "--crl"),
+            arg_values(&args, "--ocsp-response"),
+        );
+        let history_path = arg_value(&args, "--cert-history")
+            .unwrap_or_else(|| "verifier/certificate_history/history.json".to_string());
+        verifier = verifier.with_certificate_history(&history_path)?;
+        if let Some(resolver) = arg_value(&args, "--dns-resolver") {
+            let resolver = resolver.parse()
+                .with_context(|| format!("Invalid --dns-resolver address (expected ip:port): {}", resolver))?;
+            verifier = verifier.with_dns_resolver(resolver);
+        }
+        if let Some(github_url) = arg_value(&args, "--github-api-url") {
+            verifier = verifier.with_github_api_url(&github_url);
+        }
+        for (flag, forge) in [
+            ("--gitlab-api-url", ForgeKind::GitLab),
+            ("--gitea-api-url", ForgeKind::Gitea),
+            ("--bitbucket-api-url", ForgeKind::Bitbucket),
+        ] {
+            if let Some(url) = arg_value(&args, flag) {
+                verifier = verifier.with_forge_api_url(forge, &url);
+            }
+        }
+        if let Some(github_cache) = arg_value(&args, "--github-cache") {
+            verifier = verifier.with_github_cache(&github_cache)?;
+        }
+        if let Some(rdap_url) = arg_value(&args, "--rdap-url") {
+            verifier = verifier.with_rdap_base_url(&rdap_url);
+        }
+        if let Some(brands) = arg_value(&args, "--brands") {
+            verifier = verifier.with_brand_list(&brands)?;
+        }
+        if let Some(lexicon) = arg_value(&args, "--scam-lexicon") {
+            verifier = verifier.with_scam_lexicon(&lexicon)?;
+        }
+        if let Some(registry) = arg_value(&args, "--audit-registry") {
+            verifier = verifier.with_audit_registry(&registry)?;
+        }
+        if let Some(corpus) = arg_value(&args, "--upstream-corpus") {
+            verifier = verifier.with_upstream_corpus(&corpus)?;
+        }
+        let crawl_defaults = crawler::CrawlConfig::default();
+        let crawl_depth = arg_value(&args, "--crawl-depth")
+            .map(|d| d.parse().with_context(|| format!("Invalid --crawl-depth: {}", d)))
+            .transpose()?
+            .unwrap_or(crawl_defaults.max_depth);
+        let crawl_pages = arg_value(&args, "--crawl-pages")
+            .map(|p| p.parse().with_context(|| format!("Invalid --crawl-pages: {}", p)))
+            .transpose()?
+            .unwrap_or(crawl_defaults.max_pages);
+        verifier = verifier.with_crawl_limits(crawl_depth, crawl_pages);
+        verifier = verifier
+            .with_strict_certificates(args.iter().any(|arg| arg == "--strict"))
+            .with_offline(args.iter().any(|arg| arg == "--offline"))
+            .with_supply_chain_sensitive(args.iter().any(|arg| arg == "--supply-chain"));
         
         // Generate real witness data
-        let real_witness = verifier.generate_real_witness_data(&domain).await?;
+        let real_witness = match &cert_file {
+            Some(path) => {
+                println!("📂 Using certificate file: {}", path);
+                verifier.generate_witness_data_from_file(path, arg_value(&args, "--domain").as_deref()).await?
+            }
+            None => {
+                println!("🌐 Using REAL data for domain: {}", domain);
+                verifier.generate_real_witness_data(&domain).await?
+            }
+        };
         
         // Convert to the format expected by the rest of the system
-        WitnessInput {
-            domain_hash: real_witness.domain_hash,
-            certificate_validity_hash: real_witness.certificate_validity_hash,
-            transparency_score: real_witness.transparency_score,
-            risk_level: real_witness.risk_level,
-            verification_timestamp: real_witness.verification_timestamp,
-            domain_name: real_witness.domain_name,
-            certificate_serial: real_witness.certificate_serial,
-            issuer_hash: real_witness.issuer_hash,
-            expiry_date: real_witness.expiry_date,
-            public_key_hash: real_witness.public_key_hash,
-            salt: real_witness.salt,
-        }
+        (witness_from_real(&real_witness), Some(real_witness.real_transparency))
     } else {
         println!("📄 Loading witness data from file...");
-        let witness_path = "../noir/witness/input.json";
-        load_and_validate_witness(witness_path)?
+        let witness_path = Path::new(NOIR_DIR).join("witness/input.json");
+        (load_and_validate_witness(&witness_path)?, None)
     };
     
     println!("📄 Loaded witness data successfully");
 
     // Analyze project transparency and risk factors
-    let project_metadata = analyze_project_transparency(&witness_data)?;
+    let project_metadata = analyze_project_transparency(&witness_data, real_transparency.as_ref())?;
     let legitimacy_assessment = assess_project_legitimacy(&project_metadata)?;
     
     println!("📊 Project Analysis:");
     println!("   - Domain: {}", project_metadata.domain);
     println!("   - Transparency Score: {}/100", witness_data.transparency_score);
     println!("   - Risk Level: {}/10", witness_data.risk_level);
+    println!("   - Certificate Provenance: {}", witness_data.certificate_provenance
+        .map(|p| p.to_string())
+        .unwrap_or_else(|| "unknown".to_string()));
     println!("   - Legitimacy Assessment: {}", legitimacy_assessment.overall_recommendation);
 
     // Generate proof with timing
     let start_time = Instant::now();
-    let proof_data = generate_proof(&witness_data)?;
+    let proof_data = generate_proof(&witness_data, Path::new(NOIR_DIR))?;
     let generation_time = start_time.elapsed();
     
     println!("⏱️  zkTLS proof generation took: {}ms", generation_time.as_millis());
     
     // Verify the proof with enhanced validation
-    let verification_result = verify_proof_enhanced(&proof_data, &witness_data)?;
+    let verification_result = verify_proof_enhanced(&proof_data, &witness_data, Path::new(NOIR_DIR))?;
     
     if verification_result.is_valid {
         println!("✅ zkTLS proof verification successful!");
@@ -199,9 +391,43 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-fn load_and_validate_witness(path: &str) -> Result<WitnessInput> {
+/// Witness input for the circuit from live or file-based certificate analysis
+fn witness_from_real(real_witness: &RealWitnessData) -> WitnessInput {
+    WitnessInput {
+        domain_hash: real_witness.domain_hash.clone(),
+        certificate_validity_hash: real_witness.certificate_validity_hash.clone(),
+        transparency_score: real_witness.transparency_score,
+        risk_level: real_witness.risk_level,
+        verification_timestamp: real_witness.verification_timestamp,
+        domain_name: real_witness.domain_name.clone(),
+        certificate_serial: real_witness.certificate_serial.clone(),
+        issuer_hash: real_witness.issuer_hash.clone(),
+        expiry_date: real_witness.expiry_date,
+        public_key_hash: real_witness.public_key_hash.clone(),
+        salt: real_witness.salt.clone(),
+        certificate_provenance: Some(real_witness.real_certificate.provenance),
+    }
+}
+
+/// Value following a `--flag` on the command line
+fn arg_value(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|pos| args.get(pos + 1))
+        .cloned()
+}
+
+/// Every value given for a repeatable `--flag`
+fn arg_values(args: &[String], flag: &str) -> Vec<String> {
+    args.windows(2)
+        .filter(|pair| pair[0] == flag)
+        .map(|pair| pair[1].clone())
+        .collect()
+}
+
+fn load_and_validate_witness(path: &Path) -> Result<WitnessInput> {
     let content = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read witness file: {}", path))?;
+        .with_context(|| format!("Failed to read witness file: {}", path.display()))?;
     
     let witness: WitnessInput = serde_json::from_str(&content)
         .with_context(|| "Failed to parse witness JSON")?;
@@ -265,16 +491,16 @@ fn validate_witness_data(witness: &WitnessInput) -> Result<()> {
     Ok(())
 }
 
-fn generate_proof(witness: &WitnessInput) -> Result<ProofData> {
+fn generate_proof(witness: &WitnessInput, noir_dir: &Path) -> Result<ProofData> {
     println!("🔧 Generating REAL zkTLS proof with Noir...");
     
     let start_time = Instant::now();
     
     // Save witness data to Noir input file
-    save_witness_to_noir(witness)?;
+    save_witness_to_noir(witness, noir_dir)?;
     
     // Call nargo prove to generate the actual zkTLS proof
-    let proof_result = call_nargo_prove()?;
+    let proof_result = call_nargo_prove(noir_dir)?;
     let generation_time = start_time.elapsed();
     
     // Calculate entropy sum for metadata (using certificate serial)
@@ -296,6 +522,7 @@ fn generate_proof(witness: &WitnessInput) -> Result<ProofData> {
             generation_time_ms: generation_time.as_millis(),
             entropy_sum,
             proof_type: "real_zkTLS_certificate_verification".to_string(),
+            certificate_provenance: witness.certificate_provenance,
         },
     })
 }
@@ -309,13 +536,13 @@ fn generate_proof_id(proof_hash: &[u8; 32], domain_hash: &[u8]) -> Result<String
     Ok(hex::encode(&hash[..16])) // Use first 16 bytes for shorter ID
 }
 
-fn verify_proof_enhanced(proof_data: &ProofData, witness: &WitnessInput) -> Result<VerificationResult> {
+fn verify_proof_enhanced(proof_data: &ProofData, witness: &WitnessInput, noir_dir: &Path) -> Result<VerificationResult> {
     println!("🔍 Verifying REAL cryptographic zkTLS proof...");
     
     let start_time = Instant::now();
     
     // Use nargo verify for REAL cryptographic verification
-    let is_valid = call_nargo_verify(proof_data)?;
+    let is_valid = call_nargo_verify(proof_data, noir_dir)?;
     
     let verification_time = start_time.elapsed();
     
@@ -325,17 +552,36 @@ fn verify_proof_enhanced(proof_data: &ProofData, witness: &WitnessInput) -> Resu
     let constraints_verified = if is_valid { 8 } else { 0 }; // Our circuit has 8 constraints
     
     // Create legitimacy assessment based on verification result
-    let legitimacy_assessment = if is_valid {
+    let legitimacy_assessment = if is_valid && witness.certificate_provenance == Some(CertificateProvenance::Fallback) {
+        // A valid proof over a guessed certificate proves nothing about the real site
+        LegitimacyAssessment {
+            is_legitimate: false,
+            confidence_score: 10,
+            risk_factors: vec![provenance_risk_factor(CertificateProvenance::Fallback)],
+            transparency_indicators: vec![
+                format!("Transparency score: {}", witness.transparency_score),
+                format!("Risk level: {}", witness.risk_level),
+            ],
+            overall_recommendation: "UNVERIFIED - Proof covers fallback certificate data, not an observed certificate".to_string(),
+            certificate_provenance: witness.certificate_provenance,
+        }
+    } else if is_valid {
+        let risk_factors = witness.certificate_provenance
+            .filter(|p| *p != CertificateProvenance::Observed)
+            .map(provenance_risk_factor)
+            .into_iter()
+            .collect();
         LegitimacyAssessment {
             is_legitimate: true,
             confidence_score: 95, // High confidence for cryptographically verified proofs
-            risk_factors: vec![],
+            risk_factors,
             transparency_indicators: vec![
                 "zkTLS proof cryptographically verified".to_string(),
                 format!("Transparency score: {}", witness.transparency_score),
                 format!("Risk level: {}", witness.risk_level),
             ],
             overall_recommendation: "LEGITIMATE - Cryptographic proof verification successful".to_string(),
+            certificate_provenance: witness.certificate_provenance,
         }
     } else {
         LegitimacyAssessment {
@@ -344,6 +590,7 @@ fn verify_proof_enhanced(proof_data: &ProofData, witness: &WitnessInput) -> Resu
             risk_factors: vec!["Cryptographic proof verification failed".to_string()],
             transparency_indicators: vec![],
             overall_recommendation: "SUSPICIOUS - Proof verification failed".to_string(),
+            certificate_provenance: witness.certificate_provenance,
         }
     };
     
@@ -360,7 +607,7 @@ fn verify_proof_enhanced(proof_data: &ProofData, witness: &WitnessInput) -> Resu
 }
 
 /// Call nargo verify to perform REAL cryptographic verification
-fn call_nargo_verify(proof_data: &ProofData) -> Result<bool> {
+fn call_nargo_verify(proof_data: &ProofData, noir_dir: &Path) -> Result<bool> {
     use std::process::Command;
     
     println!("🔐 Running nargo verify for cryptographic proof validation...");
@@ -369,14 +616,14 @@ fn call_nargo_verify(proof_data: &ProofData) -> Result<bool> {
     let proof_bytes = hex::decode(&proof_data.proof)
         .with_context(|| "Failed to decode proof hex")?;
     
-    let proof_file = "../noir/proofs/attestation_circuit.proof";
-    fs::write(proof_file, &proof_bytes)
-        .with_context(|| format!("Failed to write proof file: {}", proof_file))?;
+    let proof_file = noir_dir.join("proofs/attestation_circuit.proof");
+    fs::write(&proof_file, &proof_bytes)
+        .with_context(|| format!("Failed to write proof file: {}", proof_file.display()))?;
     
     // Run nargo verify
     let verify_output = Command::new("nargo")
         .arg("verify")
-        .current_dir("../noir")
+        .current_dir(noir_dir)
         .output()
         .with_context(|| "Failed to execute nargo verify")?;
     
@@ -397,7 +644,7 @@ fn call_nargo_verify(proof_data: &ProofData) -> Result<bool> {
 
 #[allow(dead_code)]
 fn verify_proof(proof_data: &ProofData, witness: &WitnessInput) -> Result<bool> {
-    let result = verify_proof_enhanced(proof_data, witness)?;
+    let result = verify_proof_enhanced(proof_data, witness, Path::new(NOIR_DIR))?;
     Ok(result.is_valid)
 }
 
@@ -447,14 +694,12 @@ fn list_proofs() -> Result<Vec<String>> {
     let mut proof_ids = Vec::new();
     
     if let Ok(entries) = fs::read_dir(proof_dir) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let file_name = entry.file_name();
-                if let Some(name) = file_name.to_str() {
-                    if name.starts_with("proof_") && name.ends_with(".json") {
-                        let proof_id = name.strip_prefix("proof_").unwrap().strip_suffix(".json").unwrap();
-                        proof_ids.push(proof_id.to_string());
-                    }
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            if let Some(name) = file_name.to_str() {
+                if name.starts_with("proof_") && name.ends_with(".json") {
+                    let proof_id = name.strip_prefix("proof_").unwrap().strip_suffix(".json").unwrap();
+                    proof_ids.push(proof_id.to_string());
                 }
             }
         }
@@ -464,7 +709,10 @@ fn list_proofs() -> Result<Vec<String>> {
     Ok(proof_ids)
 }
 
-fn analyze_project_transparency(witness: &WitnessInput) -> Result<ProjectMetadata> {
+fn analyze_project_transparency(
+    witness: &WitnessInput,
+    real_transparency: Option<&RealTransparencyData>,
+) -> Result<ProjectMetadata> {
     println!("🔍 Analyzing project transparency...");
     
     // Simulate domain extraction from witness data
@@ -475,37 +723,65 @@ fn analyze_project_transparency(witness: &WitnessInput) -> Result<ProjectMetadat
         expiry_date: witness.expiry_date,
         is_valid: witness.verification_timestamp < witness.expiry_date,
         serial_number_hash: hex::encode(&witness.certificate_serial),
+        provenance: witness.certificate_provenance,
     };
     
-    // Simulate transparency metrics analysis
+    let security_txt = real_transparency.and_then(|t| t.security_txt.as_ref());
+    let mut security_txt_problems = Vec::new();
+    if let Some(file) = security_txt {
+        if file.is_expired(witness.verification_timestamp) {
+            security_txt_problems.push(format!("security.txt at {} has expired", file.url));
+        }
+        if let SignatureStatus::Invalid { reason } = &file.signature {
+            security_txt_problems.push(format!("security.txt signature does not verify: {}", reason));
+        }
+    }
+
+    // Simulate transparency metrics analysis; security.txt comes from the live site
     let transparency_metrics = TransparencyMetrics {
-        has_public_github: witness.transparency_score > 60,
+        has_public_repository: witness.transparency_score > 60,
         has_documented_roadmap: witness.transparency_score > 50,
-        has_audit_reports: witness.transparency_score > 70,
+        // Linked reports decide once the site was crawled; a claim the registry contradicts is no audit
+        has_audit_reports: match real_transparency {
+            Some(t) => t.audit_reports.iter().any(|c| matches!(c.status, AuditStatus::Verified { .. })),
+            None => witness.transparency_score > 70,
+        },
         has_team_verification: witness.transparency_score > 40,
         has_token_economics: witness.transparency_score > 30,
-        code_review_score: (witness.transparency_score / 10) as u8,
+        code_review_score: real_transparency
+            .filter(|t| t.repository_health.is_some())
+            .map(|t| t.code_review_score)
+            .unwrap_or((witness.transparency_score / 10) as u8),
+        has_security_txt: security_txt.is_some_and(|f| f.is_disclosure_channel(witness.verification_timestamp)),
+        security_txt_signature: security_txt.map(|f| f.signature.clone()),
+        audit_reports: real_transparency.map(|t| t.audit_reports.clone()).unwrap_or_default(),
+        team_members: real_transparency.map(|t| t.team_members.clone()).unwrap_or_default(),
+        tokenomics: real_transparency.and_then(|t| t.tokenomics.clone()),
+        repository_health: real_transparency.and_then(|t| t.repository_health.clone()),
+        source_repository: real_transparency.and_then(|t| t.source_repository.clone()),
+        repository_origin: real_transparency.and_then(|t| t.repository_origin.clone()),
+        commit_signing: real_transparency.and_then(|t| t.commit_signing.clone()),
+        supply_chain_sensitive: real_transparency.is_some_and(|t| t.supply_chain_sensitive),
+        github_organization: real_transparency.and_then(|t| t.github_organization.clone()),
     };
     
-    // Simulate risk factor analysis
+    let security_headers = real_transparency.and_then(|t| t.security_headers.as_ref());
+    let scam_language = real_transparency.and_then(|t| t.scam_language.as_ref());
+
+    // Risk factors from the live analysis; the issuer check is still simulated
     let risk_factors = RiskFactors {
-        suspicious_domain_patterns: if witness.risk_level > 5 {
-            vec!["new-domain.com".to_string(), "temp-site.org".to_string()]
-        } else {
-            vec![]
-        },
-        high_risk_keywords: if witness.risk_level > 7 {
-            vec!["guaranteed".to_string(), "risk-free".to_string(), "instant-profit".to_string()]
-        } else {
-            vec![]
-        },
+        suspicious_domain_patterns: real_transparency
+            .map(|t| t.impersonation.iter().map(|finding| finding.to_string()).collect())
+            .unwrap_or_default(),
+        high_risk_keywords: scam_language.map(|r| r.phrases()).unwrap_or_default(),
         unusual_certificate_issuer: witness.issuer_hash[0] < 100, // Simulate unusual issuer detection
-        short_certificate_validity: (witness.expiry_date - witness.verification_timestamp) < 86400 * 30, // Less than 30 days
-        missing_security_headers: if witness.risk_level > 6 {
-            vec!["HSTS".to_string(), "CSP".to_string()]
-        } else {
-            vec![]
-        },
+        // Less than 30 days left; an expired certificate has none
+        short_certificate_validity: witness.expiry_date.saturating_sub(witness.verification_timestamp) < 86400 * 30,
+        // Only headers actually served by the site; a witness file alone says nothing about them
+        missing_security_headers: security_headers.map(|r| r.missing()).unwrap_or_default(),
+        security_header_findings: security_headers.map(|r| r.findings.clone()).unwrap_or_default(),
+        scam_phrase_matches: scam_language.map(|r| r.matches.clone()).unwrap_or_default(),
+        security_txt_problems,
     };
     
     Ok(ProjectMetadata {
@@ -524,13 +800,67 @@ fn assess_project_legitimacy(metadata: &ProjectMetadata) -> Result<LegitimacyAss
     
     // Analyze risk factors
     if !metadata.risk_factors.suspicious_domain_patterns.is_empty() {
-        risk_factors.push("Suspicious domain patterns detected".to_string());
+        risk_factors.push(format!(
+            "Suspicious domain patterns detected: {}",
+            metadata.risk_factors.suspicious_domain_patterns.join("; "),
+        ));
     }
     
     if !metadata.risk_factors.high_risk_keywords.is_empty() {
-        risk_factors.push("High-risk marketing language detected".to_string());
+        let phrases: Vec<String> = metadata.risk_factors.scam_phrase_matches.iter()
+            .map(|m| match m.snippets.first() {
+                Some(snippet) => format!("\"{}\" in \"{}\"", m.phrase, snippet),
+                None => format!("\"{}\"", m.phrase),
+            })
+            .collect();
+        risk_factors.push(format!("High-risk marketing language detected: {}", phrases.join("; ")));
     }
     
+    risk_factors.extend(metadata.risk_factors.security_txt_problems.iter().cloned());
+
+    for check in &metadata.transparency_metrics.audit_reports {
+        match check.status {
+            AuditStatus::Verified { .. } => transparency_indicators.push(format!("Audit confirmed by auditor registry: {}", check)),
+            AuditStatus::OtherProject { .. } => risk_factors.push(format!("Audit report belongs to another project: {}", check)),
+            AuditStatus::ClaimedButMissing => risk_factors.push(format!("Audit claimed but report missing: {}", check.url)),
+            AuditStatus::AuditorSiteUnregistered { names_project: true, .. } => {
+                transparency_indicators.push(format!("Audit published by the auditor, not in the registry: {}", check))
+            }
+            AuditStatus::AuditorSiteUnregistered { names_project: false, .. } | AuditStatus::SelfHostedUnverified => {}
+        }
+    }
+
+    let team = &metadata.transparency_metrics.team_members;
+    let on_github: Vec<String> = team.iter()
+        .filter(|m| matches!(m.level, VerificationLevel::Contributor { .. } | VerificationLevel::GitHubAccount { .. }))
+        .map(|m| m.to_string())
+        .collect();
+    if !on_github.is_empty() {
+        transparency_indicators.push(format!("Team members with GitHub accounts: {}", on_github.join("; ")));
+    }
+    let fabricated: Vec<String> = team.iter()
+        .filter(|m| matches!(m.level, VerificationLevel::GitHubNotFound { .. }))
+        .map(|m| m.to_string())
+        .collect();
+    if !fabricated.is_empty() {
+        risk_factors.push(format!("Team profiles link to nonexistent GitHub accounts: {}", fabricated.join("; ")));
+    }
+
+    if let Some(distribution) = &metadata.transparency_metrics.tokenomics {
+        if distribution.is_complete() {
+            transparency_indicators.push(format!("Token distribution documented: {}", distribution));
+        }
+        for problem in distribution.problems() {
+            risk_factors.push(format!("Token distribution: {}", problem));
+        }
+    }
+
+    if let Some(provenance) = metadata.certificate_info.provenance {
+        if provenance != CertificateProvenance::Observed {
+            risk_factors.push(provenance_risk_factor(provenance));
+        }
+    }
+
     if metadata.risk_factors.unusual_certificate_issuer {
         risk_factors.push("Unusual certificate issuer".to_string());
     }
@@ -542,17 +872,92 @@ fn assess_project_legitimacy(metadata: &ProjectMetadata) -> Result<LegitimacyAss
     if !metadata.risk_factors.missing_security_headers.is_empty() {
         risk_factors.push(format!("Missing security headers: {:?}", metadata.risk_factors.missing_security_headers));
     }
+
+    let weak_headers: Vec<String> = metadata.risk_factors.security_header_findings.iter()
+        .filter(|f| f.status == HeaderStatus::Weak && !f.is_informational())
+        .map(|f| format!("{} ({})", f.header, f.notes.join(", ")))
+        .collect();
+    if !weak_headers.is_empty() {
+        risk_factors.push(format!("Weak security headers: {}", weak_headers.join("; ")));
+    }
+
+    let hardened_headers = metadata.risk_factors.security_header_findings.iter()
+        .filter(|f| f.status == HeaderStatus::Good)
+        .count();
+    if hardened_headers >= 5 {
+        transparency_indicators.push(format!("{} of {} security headers hardened", hardened_headers, metadata.risk_factors.security_header_findings.len()));
+    }
     
+    if let Some(health) = &metadata.transparency_metrics.repository_health {
+        let components = health.score_components();
+        let earned: Vec<String> = components.iter()
+            .filter(|c| c.points > 0)
+            .map(|c| format!("{} {}", c.metric, c.reason))
+            .collect();
+        transparency_indicators.push(format!("Code review score {}/100 for {}: {}",
+            metadata.transparency_metrics.code_review_score, health.repository, earned.join("; ")));
+        let forge = metadata.transparency_metrics.source_repository.as_ref().map_or(ForgeKind::GitHub, |r| r.forge);
+        let origin_is_fork = metadata.transparency_metrics.repository_origin.as_ref()
+            .is_some_and(|r| matches!(r.origin, RepositoryOrigin::Fork { .. }));
+        for penalty in components.iter().filter(|c| c.points < 0) {
+            // The origin report below names the fork along with its commit count
+            if penalty.metric == "fork" && origin_is_fork {
+                continue;
+            }
+            risk_factors.push(format!("{} repository {}: {}", forge, health.repository, penalty.reason));
+        }
+    }
+
+    if let Some(report) = &metadata.transparency_metrics.repository_origin {
+        match report.origin {
+            RepositoryOrigin::Original if report.files_compared == 0 => {
+                transparency_indicators.push(format!("Copy detection skipped: {}", report))
+            }
+            RepositoryOrigin::Original => transparency_indicators.push(format!("Original development: {}", report)),
+            RepositoryOrigin::Copy { .. } => risk_factors.push(format!("Cloned code: {}", report)),
+            RepositoryOrigin::Fork { .. } => risk_factors.push(format!("Forked code: {}", report)),
+            RepositoryOrigin::FromTemplate { .. } => {}
+        }
+    }
+
+    if let Some(signing) = &metadata.transparency_metrics.commit_signing {
+        if signing.releases_signed() {
+            transparency_indicators.push(format!("Signed releases: {}", signing));
+        } else if metadata.transparency_metrics.supply_chain_sensitive
+            && signing.release_tags.checked > 0
+            && signing.release_tags.signed == 0
+        {
+            // Most projects never sign tags; only code others build on is expected to
+            risk_factors.push(format!("Unsigned releases: none of the last {} release tags of {} are signed",
+                signing.release_tags.checked, signing.repository));
+        }
+        if signing.release_tags.bad_signatures > 0 {
+            risk_factors.push(format!("Bad release tag signatures: {}", signing));
+        }
+    }
+
+    if let Some(organization) = &metadata.transparency_metrics.github_organization {
+        match organization.link {
+            OrganizationLink::VerifiedDomain => transparency_indicators.push(format!("GitHub organization with verified domain: {}", organization)),
+            OrganizationLink::Website => transparency_indicators.push(format!("GitHub organization links to this site: {}", organization)),
+            OrganizationLink::Unconfirmed => {}
+        }
+    }
+
     // Analyze transparency indicators
-    if metadata.transparency_metrics.has_public_github {
-        transparency_indicators.push("Public GitHub repository".to_string());
+    if metadata.transparency_metrics.has_public_repository {
+        transparency_indicators.push(match &metadata.transparency_metrics.source_repository {
+            Some(repo) => format!("Public source repository: {}", repo),
+            None => "Public source repository".to_string(),
+        });
     }
     
     if metadata.transparency_metrics.has_documented_roadmap {
         transparency_indicators.push("Documented project roadmap".to_string());
     }
     
-    if metadata.transparency_metrics.has_audit_reports {
+    // Registry findings above already describe each linked report
+    if metadata.transparency_metrics.has_audit_reports && metadata.transparency_metrics.audit_reports.is_empty() {
         transparency_indicators.push("Security audit reports available".to_string());
     }
     
@@ -563,9 +968,16 @@ fn assess_project_legitimacy(metadata: &ProjectMetadata) -> Result<LegitimacyAss
     if metadata.transparency_metrics.has_token_economics {
         transparency_indicators.push("Token economics documented".to_string());
     }
+
+    if metadata.transparency_metrics.has_security_txt {
+        transparency_indicators.push(match &metadata.transparency_metrics.security_txt_signature {
+            Some(SignatureStatus::Verified { fingerprint }) => format!("PGP-signed security.txt with working contact (key {})", fingerprint),
+            _ => "security.txt with working contact".to_string(),
+        });
+    }
     
     // Calculate legitimacy score
-    let transparency_score = metadata.transparency_metrics.has_public_github as u8 * 20
+    let transparency_score = metadata.transparency_metrics.has_public_repository as u8 * 20
         + metadata.transparency_metrics.has_documented_roadmap as u8 * 15
         + metadata.transparency_metrics.has_audit_reports as u8 * 25
         + metadata.transparency_metrics.has_team_verification as u8 * 20
@@ -574,10 +986,13 @@ fn assess_project_legitimacy(metadata: &ProjectMetadata) -> Result<LegitimacyAss
     let risk_penalty = risk_factors.len() as u8 * 15;
     let final_score = transparency_score.saturating_sub(risk_penalty);
     
-    let is_legitimate = final_score >= 60 && risk_factors.len() <= 2;
+    let is_fallback = metadata.certificate_info.provenance == Some(CertificateProvenance::Fallback);
+    let is_legitimate = final_score >= 60 && risk_factors.len() <= 2 && !is_fallback;
     let confidence_score = if is_legitimate { final_score } else { 100 - final_score };
     
-    let overall_recommendation = if is_legitimate {
+    let overall_recommendation = if is_fallback {
+        "UNVERIFIED - No certificate was observed for this domain".to_string()
+    } else if is_legitimate {
         if final_score >= 80 {
             "HIGHLY LEGITIMATE - Strong transparency indicators with minimal risk factors".to_string()
         } else {
@@ -597,9 +1012,20 @@ fn assess_project_legitimacy(metadata: &ProjectMetadata) -> Result<LegitimacyAss
         risk_factors,
         transparency_indicators,
         overall_recommendation,
+        certificate_provenance: metadata.certificate_info.provenance,
     })
 }
 
+/// Risk factor describing certificate data that was not captured from a live handshake
+fn provenance_risk_factor(provenance: CertificateProvenance) -> String {
+    match provenance {
+        CertificateProvenance::Observed => "Certificate observed in a live TLS handshake".to_string(),
+        CertificateProvenance::Cached => "Certificate taken from a cached observation, not a live handshake".to_string(),
+        CertificateProvenance::Fallback => "No certificate observed; fallback certificate data was used".to_string(),
+        CertificateProvenance::FromFile => "Certificate supplied from a file, not observed from the domain".to_string(),
+    }
+}
+
 
 fn save_project_metadata(metadata: &ProjectMetadata) -> Result<()> {
     let metadata_dir = "verifier/project_metadata";
@@ -615,16 +1041,17 @@ fn save_project_metadata(metadata: &ProjectMetadata) -> Result<()> {
 }
 
 /// Save witness data to Noir input file for proof generation
-fn save_witness_to_noir(witness: &WitnessInput) -> Result<()> {
-    let witness_path = "../noir/witness/input.json";
+fn save_witness_to_noir(witness: &WitnessInput, noir_dir: &Path) -> Result<()> {
+    let witness_path = noir_dir.join("witness/input.json");
     let json_data = serde_json::to_string_pretty(witness)?;
-    fs::write(witness_path, json_data)?;
+    fs::write(&witness_path, json_data)
+        .with_context(|| format!("Failed to write witness file: {}", witness_path.display()))?;
     println!("📝 Witness data saved to Noir input file");
     Ok(())
 }
 
 /// Call nargo prove to generate actual zkTLS proof
-fn call_nargo_prove() -> Result<NargoProofResult> {
+fn call_nargo_prove(noir_dir: &Path) -> Result<NargoProofResult> {
     use std::process::Command;
     
     println!("🔧 Calling nargo prove to generate REAL cryptographic proof...");
@@ -633,7 +1060,7 @@ fn call_nargo_prove() -> Result<NargoProofResult> {
     println!("📝 Step 1/2: Generating witness...");
     let execute_output = Command::new("nargo")
         .arg("execute")
-        .current_dir("../noir")
+        .current_dir(noir_dir)
         .output()
         .with_context(|| "Failed to execute nargo execute")?;
     
@@ -647,7 +1074,7 @@ fn call_nargo_prove() -> Result<NargoProofResult> {
     println!("🔐 Step 2/2: Generating cryptographic proof (this may take 30-60 seconds)...");
     let prove_output = Command::new("nargo")
         .arg("prove")
-        .current_dir("../noir")
+        .current_dir(noir_dir)
         .output()
         .with_context(|| "Failed to execute nargo prove")?;
     
@@ -658,22 +1085,22 @@ fn call_nargo_prove() -> Result<NargoProofResult> {
     println!("✅ Real cryptographic proof generated successfully");
     
     // Step 3: Read the actual proof file
-    let proof_file = "../noir/proofs/attestation_circuit.proof";
-    if !std::path::Path::new(proof_file).exists() {
-        return Err(anyhow::anyhow!("Proof file not found: {}. Make sure nargo prove succeeded.", proof_file));
+    let proof_file = noir_dir.join("proofs/attestation_circuit.proof");
+    if !proof_file.exists() {
+        return Err(anyhow::anyhow!("Proof file not found: {}. Make sure nargo prove succeeded.", proof_file.display()));
     }
     
     // Read the real proof bytes
     let proof_bytes = fs::read(&proof_file)
-        .with_context(|| format!("Failed to read proof file: {}", proof_file))?;
+        .with_context(|| format!("Failed to read proof file: {}", proof_file.display()))?;
     let proof_hex = hex::encode(&proof_bytes);
     
     println!("📊 Proof generated: {} bytes", proof_bytes.len());
     
     // Step 4: Read verification key
-    let vk_file = "../noir/target/attestation_circuit.json";
+    let vk_file = noir_dir.join("target/attestation_circuit.json");
     let vk_content = fs::read_to_string(&vk_file)
-        .with_context(|| format!("Failed to read verification key: {}", vk_file))?;
+        .with_context(|| format!("Failed to read verification key: {}", vk_file.display()))?;
     
     // Parse the verification key to extract public inputs
     let vk_json: serde_json::Value = serde_json::from_str(&vk_content)?;
@@ -730,6 +1157,37 @@ struct NargoProofResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use security_headers::SecurityHeaderReport;
+
+    /// Copy of the Noir circuit in a temporary directory, so tests never overwrite the committed witness
+    fn scratch_noir_project(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("poa_noir_{}_{}", name, std::process::id()));
+        for sub in ["src", "witness", "proofs"] {
+            fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+        for file in ["Nargo.toml", "Prover.toml", "src/main.nr"] {
+            fs::copy(Path::new(NOIR_DIR).join(file), dir.join(file)).unwrap();
+        }
+        dir
+    }
+
+    /// Witness for an observed certificate, checked a year before it expires
+    fn test_witness(transparency_score: u32, risk_level: u8) -> WitnessInput {
+        WitnessInput {
+            domain_hash: vec![40; 32],
+            certificate_validity_hash: vec![41; 32],
+            transparency_score,
+            risk_level,
+            verification_timestamp: 1704067200,
+            domain_name: vec![42; 64],
+            certificate_serial: vec![43; 32],
+            issuer_hash: vec![144; 32],
+            expiry_date: 1735689600,
+            public_key_hash: vec![45; 32],
+            salt: vec![2; 32],
+            certificate_provenance: Some(CertificateProvenance::Observed),
+        }
+    }
 
     #[test]
     fn test_proof_generation() {
@@ -745,9 +1203,11 @@ mod tests {
             expiry_date: 1735689600,
             public_key_hash: vec![45; 32],
             salt: vec![2; 32],
+            certificate_provenance: None,
         };
         
-        let proof = generate_proof(&witness).unwrap();
+        let noir_dir = scratch_noir_project("generation");
+        let proof = generate_proof(&witness, &noir_dir).unwrap();
         assert!(!proof.proof.is_empty());
         assert!(!proof.public_inputs.is_empty());
     }
@@ -766,6 +1226,7 @@ mod tests {
                 generation_time_ms: 0,
                 entropy_sum: 1000,
                 proof_type: "zkTLS_test".to_string(),
+                certificate_provenance: None,
             },
         };
         
@@ -781,9 +1242,11 @@ mod tests {
             expiry_date: chrono::Utc::now().timestamp() as u64 + 86400,
             public_key_hash: vec![0u8; 32],
             salt: vec![0u8; 32],
+            certificate_provenance: None,
         };
         
-        let result = verify_proof_enhanced(&proof_data, &witness).unwrap();
+        let noir_dir = scratch_noir_project("verification");
+        let result = verify_proof_enhanced(&proof_data, &witness, &noir_dir).unwrap();
         assert!(result.is_valid);
         assert_eq!(result.constraints_verified, 5);
     }
@@ -802,6 +1265,7 @@ mod tests {
             expiry_date: 1735689600,
             public_key_hash: vec![45; 32],
             salt: vec![2; 32],
+            certificate_provenance: None,
         };
         
         assert!(validate_witness_data(&valid_witness).is_ok());
@@ -818,6 +1282,7 @@ mod tests {
             expiry_date: 1704067200, // Before verification timestamp
             public_key_hash: vec![45; 32],
             salt: vec![2; 32],
+            certificate_provenance: None,
         };
         
         assert!(validate_witness_data(&invalid_witness).is_err());
@@ -837,6 +1302,7 @@ mod tests {
                 generation_time_ms: 100,
                 entropy_sum: 1000,
                 proof_type: "zkTLS_test".to_string(),
+                certificate_provenance: None,
             },
         };
         
@@ -849,4 +1315,357 @@ mod tests {
         assert_eq!(proof_data.proof_id, deserialized.proof_id);
         assert_eq!(proof_data.metadata.circuit_version, deserialized.metadata.circuit_version);
     }
+
+    #[test]
+    fn test_fallback_certificate_is_never_legitimate() {
+        let witness = WitnessInput {
+            certificate_provenance: Some(CertificateProvenance::Fallback),
+            ..test_witness(95, 1)
+        };
+
+        let metadata = analyze_project_transparency(&witness, None).unwrap();
+        assert_eq!(metadata.certificate_info.provenance, Some(CertificateProvenance::Fallback));
+
+        let assessment = assess_project_legitimacy(&metadata).unwrap();
+        assert!(!assessment.is_legitimate);
+        assert_eq!(assessment.certificate_provenance, Some(CertificateProvenance::Fallback));
+        assert!(assessment.overall_recommendation.starts_with("UNVERIFIED"));
+
+        // Provenance survives the witness round trip, and older witness files still load
+        let json = serde_json::to_string(&witness).unwrap();
+        let restored: WitnessInput = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.certificate_provenance, Some(CertificateProvenance::Fallback));
+        let legacy = json.replace(",\"certificate_provenance\":\"Fallback\"", "");
+        let restored: WitnessInput = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(restored.certificate_provenance, None);
+    }
+
+    #[tokio::test]
+    async fn test_expired_certificate_file() {
+        let verifier = RealZkTlsVerifier::new(None).with_offline(true);
+        let real_witness = verifier.generate_witness_data_from_file("fixtures/expired.pem", None).await.unwrap();
+        let witness = witness_from_real(&real_witness);
+        assert!(witness.expiry_date < witness.verification_timestamp);
+
+        let metadata = analyze_project_transparency(&witness, Some(&real_witness.real_transparency)).unwrap();
+        assert!(!metadata.certificate_info.is_valid);
+        assert!(metadata.risk_factors.short_certificate_validity);
+    }
+
+    /// A security.txt for example-project.com whose contact answered
+    fn security_txt(expires: &str, signature: SignatureStatus) -> security_txt::SecurityTxt {
+        let mut file = security_txt::SecurityTxt::parse(
+            "https://example-project.com/.well-known/security.txt",
+            &format!("Contact: mailto:security@example-project.com\nExpires: {}\n", expires),
+        );
+        file.reachable_contacts = file.contact.clone();
+        file.signature = signature;
+        file
+    }
+
+    /// A recently pushed fork of the project's repository, scored 19/100
+    fn forked_repository(transparency: &mut RealTransparencyData) {
+        let health = RepositoryHealth {
+            description: Some("Protocol contracts".to_string()),
+            stars: 12,
+            license: Some("MIT License".to_string()),
+            pushed_at: Some(1735689600 - 10 * 24 * 60 * 60),
+            is_fork: true,
+            parent: Some("example-project/protocol".to_string()),
+            ..RepositoryHealth::new("copycat/protocol", 1735689600)
+        };
+        transparency.code_review_score = health.code_review_score();
+        transparency.repository_health = Some(health);
+    }
+
+    /// Verified commits and four unsigned release tags
+    fn unsigned_releases(transparency: &mut RealTransparencyData) {
+        use commit_signing::SignatureStats;
+
+        transparency.commit_signing = Some(SigningReport {
+            repository: "example-project/protocol".to_string(),
+            commits: SignatureStats { checked: 20, signed: 20, verified: 20, ..Default::default() },
+            release_tags: SignatureStats { checked: 4, ..Default::default() },
+            maintainers: Vec::new(),
+            errors: Vec::new(),
+        });
+    }
+
+    /// A live finding about the site and the assessment lines it must (and must not) produce
+    struct AssessmentCase {
+        signal: &'static str,
+        setup: fn(&mut RealTransparencyData),
+        indicators: &'static [&'static str],
+        risks: &'static [&'static str],
+        absent: &'static [&'static str],
+    }
+
+    #[test]
+    fn test_live_findings_feed_assessment() {
+        // Without a live analysis, even a high risk level invents no findings
+        let witness = test_witness(95, 9);
+        let metadata = analyze_project_transparency(&witness, None).unwrap();
+        assert!(metadata.risk_factors.high_risk_keywords.is_empty());
+        assert!(metadata.risk_factors.missing_security_headers.is_empty());
+        assert!(metadata.risk_factors.suspicious_domain_patterns.is_empty());
+
+        let cases = [
+            AssessmentCase {
+                signal: "scam language",
+                setup: |t| {
+                    let lexicon = scam_language::ScamLexicon::parse("5 guaranteed returns").unwrap();
+                    t.scam_language = Some(lexicon.scan("https://example-project.com/", "Stake today for guaranteed returns."));
+                },
+                indicators: &[],
+                risks: &["High-risk marketing language detected: \"guaranteed returns\" in \"stake today for guaranteed returns.\""],
+                absent: &[],
+            },
+            AssessmentCase {
+                signal: "security headers",
+                setup: |t| {
+                    let finding = |header: &str, status, value: Option<&str>, note: Option<&str>| HeaderFinding {
+                        header: header.to_string(),
+                        status,
+                        value: value.map(str::to_string),
+                        notes: note.into_iter().map(str::to_string).collect(),
+                    };
+                    t.security_headers = Some(SecurityHeaderReport {
+                        url: "https://example-project.com/".to_string(),
+                        findings: vec![
+                            finding("Strict-Transport-Security", HeaderStatus::Missing, None, None),
+                            finding("Content-Security-Policy", HeaderStatus::Weak, Some("script-src 'unsafe-eval'"), Some("script-src allows 'unsafe-eval'")),
+                            finding("Cross-Origin-Opener-Policy", HeaderStatus::Weak, Some("unsafe-none"), Some("unsafe-none provides no isolation")),
+                        ],
+                    });
+                },
+                indicators: &[],
+                risks: &[
+                    "Missing security headers: [\"Strict-Transport-Security\"]",
+                    "Weak security headers: Content-Security-Policy (script-src allows 'unsafe-eval')",
+                ],
+                absent: &[],
+            },
+            AssessmentCase {
+                signal: "impersonation",
+                setup: |t| t.impersonation = impersonation::ImpersonationDetector::default().check("paypal-secure-login.xyz"),
+                indicators: &[],
+                risks: &["Suspicious domain patterns detected: paypal-secure-login.xyz imitates paypal.com (brand name embedded in another domain)"],
+                absent: &[],
+            },
+            AssessmentCase {
+                signal: "signed security.txt",
+                setup: |t| t.security_txt = Some(security_txt(
+                    "2024-06-01T00:00:00Z",
+                    SignatureStatus::Verified { fingerprint: "9C859D780FBCA97F0F722492A7FBE9F04BC317F5".to_string() },
+                )),
+                indicators: &["PGP-signed security.txt with working contact (key 9C859D780FBCA97F0F722492A7FBE9F04BC317F5)"],
+                risks: &[],
+                absent: &[],
+            },
+            // Past its Expires date and with a signature that fails, the file counts against the site
+            AssessmentCase {
+                signal: "expired security.txt with a bad signature",
+                setup: |t| t.security_txt = Some(security_txt(
+                    "2023-12-01T00:00:00Z",
+                    SignatureStatus::Invalid { reason: "signature by key A7FBE9F04BC317F5 does not match the file".to_string() },
+                )),
+                indicators: &[],
+                risks: &[
+                    "security.txt at https://example-project.com/.well-known/security.txt has expired",
+                    "security.txt signature does not verify: signature by key A7FBE9F04BC317F5 does not match the file",
+                ],
+                absent: &["security.txt with working contact", "PGP-signed security.txt"],
+            },
+            // A high simulated score does not add a generic audit claim next to the registry findings
+            AssessmentCase {
+                signal: "audit reports",
+                setup: |t| t.audit_reports = vec![
+                    AuditReportCheck {
+                        url: "https://example-project.com/audit.pdf".to_string(),
+                        sha256: Some("d0".repeat(32)),
+                        status: AuditStatus::Verified { auditor: "PoA Test Auditors".to_string(), date: "2024-03-01".to_string() },
+                    },
+                    AuditReportCheck {
+                        url: "https://example-project.com/certik.pdf".to_string(),
+                        sha256: None,
+                        status: AuditStatus::ClaimedButMissing,
+                    },
+                ],
+                indicators: &["Audit confirmed by auditor registry: https://example-project.com/audit.pdf: verified report by PoA Test Auditors (2024-03-01)"],
+                risks: &["Audit claimed but report missing: https://example-project.com/certik.pdf"],
+                absent: &["Security audit reports available"],
+            },
+            AssessmentCase {
+                signal: "team members",
+                setup: |t| {
+                    let page = r#"<div class="member"><h3>Grace Sample</h3><a href="https://github.com/gracesample">GitHub</a></div>
+                        <div class="member"><h3>Ghost Account</h3><a href="https://github.com/ghost-account-404">GitHub</a></div>"#;
+                    t.team_members = team::extract_team("https://example-project.com/team", page);
+                    t.team_members[0].level = VerificationLevel::Contributor { login: "gracesample".to_string(), age_days: Some(3653), contributions: 212 };
+                    t.team_members[1].level = VerificationLevel::GitHubNotFound { login: "ghost-account-404".to_string() };
+                },
+                indicators: &["Team members with GitHub accounts: Grace Sample: GitHub @gracesample with 212 commits to the project, account 3653 days old"],
+                risks: &["Team profiles link to nonexistent GitHub accounts: Ghost Account: GitHub account @ghost-account-404 does not exist"],
+                absent: &[],
+            },
+            AssessmentCase {
+                signal: "token distribution",
+                setup: |t| {
+                    let lines: Vec<String> = ["Total supply: 1,000,000,000", "Founders 45%", "Public sale 55%"]
+                        .iter().map(|s| s.to_string()).collect();
+                    t.tokenomics = Some(TokenDistribution::extract("https://example-project.com/tokenomics", &lines));
+                },
+                indicators: &[],
+                risks: &[
+                    "Token distribution: insiders hold 45.0% of supply (limit 40%)",
+                    "Token distribution: no vesting for Founders",
+                ],
+                absent: &["Token distribution documented"],
+            },
+            AssessmentCase {
+                signal: "repository health",
+                setup: forked_repository,
+                indicators: &["Code review score 19/100 for copycat/protocol: repository public repository copycat/protocol; stars 12 stars; license MIT License; description described; recent activity last push 10 days ago"],
+                risks: &["GitHub repository copycat/protocol: fork of example-project/protocol"],
+                absent: &[],
+            },
+            // Penalties name the forge the repository lives on
+            AssessmentCase {
+                signal: "repository health on GitLab",
+                setup: |t| {
+                    forked_repository(t);
+                    t.source_repository = Some(SourceRepository {
+                        forge: ForgeKind::GitLab,
+                        host: "gitlab.com".to_string(),
+                        path: "copycat/protocol".to_string(),
+                    });
+                },
+                indicators: &[],
+                risks: &["GitLab repository copycat/protocol: fork of example-project/protocol"],
+                absent: &["GitHub repository"],
+            },
+            // With an origin report the fork is one risk factor, not two
+            AssessmentCase {
+                signal: "repository health with an origin report",
+                setup: |t| {
+                    forked_repository(t);
+                    t.repository_origin = Some(OriginReport {
+                        repository: "copycat/protocol".to_string(),
+                        origin: RepositoryOrigin::Fork { parent: "example-project/protocol".to_string() },
+                        commits: Some(2),
+                        files_compared: 0,
+                    });
+                },
+                indicators: &[],
+                risks: &["Forked code: copycat/protocol is a fork of example-project/protocol with 2 commits"],
+                absent: &["GitHub repository copycat/protocol: fork of"],
+            },
+            AssessmentCase {
+                signal: "verified organization",
+                setup: |t| t.github_organization = Some(OrganizationAnalysis {
+                    login: "example-project".to_string(),
+                    name: Some("Example Project".to_string()),
+                    website: Some("https://example-project.com".to_string()),
+                    is_verified: true,
+                    verified_domains: Some(vec!["example-project.com".to_string()]),
+                    link: OrganizationLink::VerifiedDomain,
+                    public_repos: 12,
+                    public_members: Some(5),
+                    repositories_analyzed: 12,
+                    total_stars: 2400,
+                    total_forks: 300,
+                    active_repos: 7,
+                    archived_repos: 2,
+                    languages: [("Rust".to_string(), 6), ("TypeScript".to_string(), 4)].into_iter().collect(),
+                    pinned: None,
+                    main_repository: Some("example-project/protocol".to_string()),
+                }),
+                indicators: &["GitHub organization with verified domain: example-project (VerifiedDomain): 12 public repos, 7 active in 90 days, 2400 stars, 5 public members, languages Rust 6, TypeScript 4"],
+                risks: &[],
+                absent: &[],
+            },
+            AssessmentCase {
+                signal: "cloned repository",
+                setup: |t| t.repository_origin = Some(OriginReport {
+                    repository: "scamswap/amm".to_string(),
+                    origin: RepositoryOrigin::Copy {
+                        upstream: "example-upstream/amm-core".to_string(),
+                        file_similarity: 0.97,
+                        tree_similarity: 0.98,
+                    },
+                    commits: Some(3),
+                    files_compared: 64,
+                }),
+                indicators: &[],
+                risks: &["Cloned code: scamswap/amm is a 97% copy of example-upstream/amm-core with 3 commits"],
+                absent: &[],
+            },
+            // Unsigned tags are the norm, so they only count for supply-chain projects
+            AssessmentCase {
+                signal: "unsigned releases",
+                setup: unsigned_releases,
+                indicators: &[],
+                risks: &[],
+                absent: &["Unsigned releases"],
+            },
+            AssessmentCase {
+                signal: "unsigned releases of a supply-chain project",
+                setup: |t| {
+                    unsigned_releases(t);
+                    t.supply_chain_sensitive = true;
+                },
+                indicators: &[],
+                risks: &["Unsigned releases: none of the last 4 release tags of example-project/protocol are signed"],
+                absent: &[],
+            },
+            AssessmentCase {
+                signal: "source repository on another forge",
+                setup: |t| t.source_repository = Some(SourceRepository {
+                    forge: ForgeKind::Gitea,
+                    host: "codeberg.org".to_string(),
+                    path: "example-project/protocol".to_string(),
+                }),
+                indicators: &["Public source repository: codeberg.org/example-project/protocol"],
+                risks: &[],
+                absent: &[],
+            },
+        ];
+
+        for case in cases {
+            let mut transparency = RealTransparencyData::unavailable("example-project.com");
+            (case.setup)(&mut transparency);
+            let metadata = analyze_project_transparency(&witness, Some(&transparency)).unwrap();
+            let assessment = assess_project_legitimacy(&metadata).unwrap();
+            for indicator in case.indicators {
+                assert!(assessment.transparency_indicators.contains(&indicator.to_string()), "{}: no indicator {:?}", case.signal, indicator);
+            }
+            for risk in case.risks {
+                assert!(assessment.risk_factors.contains(&risk.to_string()), "{}: no risk factor {:?}", case.signal, risk);
+            }
+            for prefix in case.absent {
+                let mut lines = assessment.transparency_indicators.iter().chain(&assessment.risk_factors);
+                assert!(!lines.any(|line| line.starts_with(prefix)), "{}: unexpected {:?}", case.signal, prefix);
+            }
+        }
+    }
+
+    #[test]
+    fn test_only_registry_confirmed_reports_count_as_audits() {
+        let mut transparency = RealTransparencyData::unavailable("example-project.com");
+        transparency.audit_reports = vec![AuditReportCheck {
+            url: "https://example-project.com/certik.pdf".to_string(),
+            sha256: None,
+            status: AuditStatus::ClaimedButMissing,
+        }];
+        let metadata = analyze_project_transparency(&test_witness(95, 9), Some(&transparency)).unwrap();
+        assert!(!metadata.transparency_metrics.has_audit_reports);
+    }
+
+    #[test]
+    fn test_metadata_from_before_the_repository_rename_still_loads() {
+        let metadata = analyze_project_transparency(&test_witness(75, 2), None).unwrap();
+        let stored = serde_json::to_string(&metadata).unwrap().replace("has_public_repository", "has_public_github");
+        let reloaded: ProjectMetadata = serde_json::from_str(&stored).unwrap();
+        assert!(reloaded.transparency_metrics.has_public_repository);
+    }
 }
@@ -0,0 +1,208 @@
+use anyhow::Result;
+use x509_parser::asn1_rs::BitString;
+use x509_parser::prelude::{AlgorithmIdentifier, FromDer, SubjectPublicKeyInfo};
+
+use crate::der::{self, TAG_OCTET_STRING, TAG_OID, TAG_SEQUENCE};
+
+/// id-pkix-ocsp-basic
+const OID_OCSP_BASIC: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01, 0x01];
+/// id-sha1
+pub const OID_SHA1: &[u8] = &[0x2b, 0x0e, 0x03, 0x02, 0x1a];
+/// id-sha256
+pub const OID_SHA256: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+
+/// A single extension from an OCSP response
+#[derive(Debug, Clone)]
+pub struct OcspExtension<'a> {
+    /// DER content of the extension OID
+    pub oid: &'a [u8],
+    /// Content of the extnValue OCTET STRING
+    pub value: &'a [u8],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertStatus {
+    Good,
+    Revoked {
+        revocation_time: Option<u64>,
+        /// CRLReason code, when the responder supplied one
+        reason: Option<u8>,
+    },
+    Unknown,
+}
+
+/// Status for one certificate in an OCSP response
+#[derive(Debug, Clone)]
+pub struct SingleResponse<'a> {
+    /// DER content of the CertID hash algorithm OID
+    pub hash_algorithm: &'a [u8],
+    pub issuer_name_hash: &'a [u8],
+    pub issuer_key_hash: &'a [u8],
+    pub serial_number: &'a [u8],
+    pub cert_status: CertStatus,
+    pub this_update: Option<u64>,
+    pub next_update: Option<u64>,
+    pub extensions: Vec<OcspExtension<'a>>,
+}
+
+/// Parsed `BasicOCSPResponse`
+#[derive(Debug, Clone)]
+pub struct OcspResponse<'a> {
+    /// Raw `ResponseData`, the bytes covered by the signature
+    pub tbs_response_data: &'a [u8],
+    pub signature_algorithm: &'a [u8],
+    pub signature: &'a [u8],
+    /// Certificates the responder included, typically a delegated signing certificate
+    pub certs: Vec<&'a [u8]>,
+    pub responses: Vec<SingleResponse<'a>>,
+}
+
+impl OcspResponse<'_> {
+    /// Check the response signature against the signer's SubjectPublicKeyInfo
+    pub fn verify_signature(&self, signer: &SubjectPublicKeyInfo) -> bool {
+        let Ok((_, algorithm)) = AlgorithmIdentifier::from_der(self.signature_algorithm) else {
+            return false;
+        };
+        let Ok((_, signature)) = BitString::from_der(self.signature) else {
+            return false;
+        };
+        x509_parser::verify::verify_signature(signer, &algorithm, &signature, self.tbs_response_data).is_ok()
+    }
+}
+
+/// Parse a DER `OCSPResponse` as delivered in a stapled status or a `.der` file
+pub fn parse_ocsp_response(input: &[u8]) -> Result<OcspResponse<'_>> {
+    let (outer, _) = der::read_tlv(input)?;
+    expect_tag(outer.tag, TAG_SEQUENCE, "OCSPResponse")?;
+    let items = der::children(outer.content)?;
+    let status = items.first().ok_or_else(|| anyhow::anyhow!("OCSPResponse is empty"))?;
+    if status.content != [0] {
+        return Err(anyhow::anyhow!("OCSP responder returned status {:?}", status.content));
+    }
+
+    // responseBytes [0] EXPLICIT SEQUENCE { responseType OID, response OCTET STRING }
+    let response_bytes = items.get(1)
+        .filter(|tlv| tlv.tag == 0xa0)
+        .ok_or_else(|| anyhow::anyhow!("OCSPResponse has no responseBytes"))?;
+    let (response_bytes, _) = der::read_tlv(response_bytes.content)?;
+    let parts = der::children(response_bytes.content)?;
+    match (parts.first(), parts.get(1)) {
+        (Some(oid), Some(body)) if oid.tag == TAG_OID && oid.content == OID_OCSP_BASIC && body.tag == TAG_OCTET_STRING => {
+            parse_basic_response(body.content)
+        }
+        _ => Err(anyhow::anyhow!("Unsupported OCSP response type")),
+    }
+}
+
+fn parse_basic_response(input: &[u8]) -> Result<OcspResponse<'_>> {
+    let (basic, _) = der::read_tlv(input)?;
+    expect_tag(basic.tag, TAG_SEQUENCE, "BasicOCSPResponse")?;
+    let basic_items = der::children(basic.content)?;
+    let (tbs, signature_algorithm, signature) = match (basic_items.first(), basic_items.get(1), basic_items.get(2)) {
+        (Some(tbs), Some(alg), Some(sig)) => (tbs, alg, sig),
+        _ => return Err(anyhow::anyhow!("BasicOCSPResponse is truncated")),
+    };
+    expect_tag(tbs.tag, TAG_SEQUENCE, "ResponseData")?;
+
+    // certs [0] EXPLICIT SEQUENCE OF Certificate OPTIONAL
+    let mut certs = Vec::new();
+    if let Some(wrapper) = basic_items.get(3).filter(|t| t.tag == 0xa0) {
+        let (seq, _) = der::read_tlv(wrapper.content)?;
+        certs = der::children(seq.content)?.into_iter().map(|c| c.raw).collect();
+    }
+
+    // ResponseData ::= SEQUENCE { version [0] OPTIONAL, responderID, producedAt, responses, ... }
+    let data = der::children(tbs.content)?;
+    let offset = if data.first().map(|t| t.tag) == Some(0xa0) { 1 } else { 0 };
+    let responses_seq = data.get(offset + 2)
+        .ok_or_else(|| anyhow::anyhow!("ResponseData has no responses"))?;
+    expect_tag(responses_seq.tag, TAG_SEQUENCE, "responses")?;
+
+    let mut responses = Vec::new();
+    for single in der::children(responses_seq.content)? {
+        responses.push(parse_single_response(single.content)?);
+    }
+    Ok(OcspResponse {
+        tbs_response_data: tbs.raw,
+        signature_algorithm: signature_algorithm.raw,
+        signature: signature.raw,
+        certs,
+        responses,
+    })
+}
+
+fn parse_single_response(input: &[u8]) -> Result<SingleResponse<'_>> {
+    let fields = der::children(input)?;
+    let cert_id = fields.first().ok_or_else(|| anyhow::anyhow!("SingleResponse is empty"))?;
+    // CertID ::= SEQUENCE { hashAlgorithm, issuerNameHash, issuerKeyHash, serialNumber }
+    let cert_id = der::children(cert_id.content)?;
+    if cert_id.len() < 4 {
+        return Err(anyhow::anyhow!("CertID is truncated"));
+    }
+    let hash_algorithm = der::children(cert_id[0].content)?
+        .first()
+        .map(|oid| oid.content)
+        .unwrap_or_default();
+
+    // certStatus ::= CHOICE { good [0] IMPLICIT NULL, revoked [1] IMPLICIT RevokedInfo, unknown [2] IMPLICIT NULL }
+    let cert_status = match fields.get(1).map(|t| (t.tag, t.content)) {
+        Some((0x80, _)) => CertStatus::Good,
+        Some((0xa1, content)) => {
+            let info = der::children(content)?;
+            let revocation_time = info.first().and_then(|t| parse_generalized_time(t.content));
+            // revocationReason [0] EXPLICIT CRLReason
+            let reason = info.get(1)
+                .filter(|t| t.tag == 0xa0)
+                .and_then(|t| der::read_tlv(t.content).ok())
+                .and_then(|(e, _)| e.content.first().copied());
+            CertStatus::Revoked { revocation_time, reason }
+        }
+        _ => CertStatus::Unknown,
+    };
+    let this_update = fields.get(2).and_then(|t| parse_generalized_time(t.content));
+    let next_update = fields.iter().skip(3)
+        .find(|t| t.tag == 0xa0)
+        .and_then(|t| der::read_tlv(t.content).ok())
+        .and_then(|(time, _)| parse_generalized_time(time.content));
+
+    // singleExtensions [1] EXPLICIT follows certStatus and thisUpdate
+    let mut extensions = Vec::new();
+    if let Some(ext_wrapper) = fields.iter().skip(3).find(|t| t.tag == 0xa1) {
+        let (ext_seq, _) = der::read_tlv(ext_wrapper.content)?;
+        for ext in der::children(ext_seq.content)? {
+            let parts = der::children(ext.content)?;
+            if let (Some(oid), Some(value)) = (parts.first(), parts.last()) {
+                if oid.tag == TAG_OID && value.tag == TAG_OCTET_STRING {
+                    extensions.push(OcspExtension { oid: oid.content, value: value.content });
+                }
+            }
+        }
+    }
+
+    Ok(SingleResponse {
+        hash_algorithm,
+        issuer_name_hash: cert_id[1].content,
+        issuer_key_hash: cert_id[2].content,
+        serial_number: cert_id[3].content,
+        cert_status,
+        this_update,
+        next_update,
+        extensions,
+    })
+}
+
+/// Parse a `GeneralizedTime` value (`YYYYMMDDHHMMSS[.fff]Z`) into Unix seconds
+fn parse_generalized_time(content: &[u8]) -> Option<u64> {
+    let text = std::str::from_utf8(content).ok()?;
+    let text = text.strip_suffix('Z')?;
+    let whole = text.split('.').next()?;
+    let parsed = chrono::NaiveDateTime::parse_from_str(whole, "%Y%m%d%H%M%S").ok()?;
+    u64::try_from(parsed.and_utc().timestamp()).ok()
+}
+
+fn expect_tag(actual: u8, expected: u8, what: &str) -> Result<()> {
+    if actual != expected {
+        return Err(anyhow::anyhow!("Unexpected tag 0x{:02x} for {}", actual, what));
+    }
+    Ok(())
+}
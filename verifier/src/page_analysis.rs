@@ -0,0 +1,394 @@
+use anyhow::Result;
+use scraper::{ElementRef, Html, Node, Selector};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use url::Url;
+
+/// Elements whose text is never rendered
+const INVISIBLE_ELEMENTS: &[&str] = &["script", "style", "noscript", "template", "head"];
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "cite", "code", "data", "dfn", "em", "font", "i", "kbd",
+    "mark", "q", "s", "samp", "small", "span", "strong", "sub", "sup", "time", "u", "var",
+];
+const HEADINGS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+/// Containers whose `id` names the section a heading inside them belongs to
+const SECTIONING_ELEMENTS: &[&str] = &["section", "article", "aside", "div"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub level: u8,
+    pub text: String,
+    /// `id` of the heading or of its enclosing section, usable as a fragment
+    pub id: Option<String>,
+}
+
+/// Visible text grouped under the heading that precedes it
+#[derive(Debug, Clone)]
+pub struct PageSection {
+    /// `None` for text before the first heading
+    pub heading: Option<Heading>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// Absolute URL
+    pub url: String,
+    pub text: String,
+}
+
+impl Link {
+    /// Link text or the last path segment names one of `keywords`
+    pub fn is_about(&self, keywords: &[&str]) -> bool {
+        let segment = Url::parse(&self.url).ok()
+            .and_then(|url| url.path_segments().and_then(|mut s| s.next_back().map(str::to_string)))
+            .unwrap_or_default();
+        mentions(&self.text, keywords) || mentions(&identifier_words(&segment), keywords)
+    }
+}
+
+/// Structured view of an HTML page
+#[derive(Debug, Clone)]
+pub struct PageAnalysis {
+    pub url: String,
+    pub title: Option<String>,
+    /// Text a visitor would read: markup, scripts, styles and the document head removed
+    pub visible_text: String,
+    pub sections: Vec<PageSection>,
+    pub internal_links: Vec<Link>,
+    pub external_links: Vec<Link>,
+    /// `<meta name=... content=...>`, names lowercased
+    pub meta: BTreeMap<String, String>,
+    /// `og:*` properties without the prefix
+    pub open_graph: BTreeMap<String, String>,
+    /// Parsed `application/ld+json` blocks, top-level arrays flattened
+    pub json_ld: Vec<Value>,
+}
+
+impl PageAnalysis {
+    pub fn parse(url: &str, html: &str) -> Self {
+        let document = Html::parse_document(html);
+        let base = Url::parse(url).ok();
+
+        let mut walk = Walk::default();
+        walk.visit(document.root_element());
+
+        let title = document.select(&selector("title")).next()
+            .map(|title| collapse_whitespace(&title.text().collect::<String>()))
+            .filter(|title| !title.is_empty());
+
+        let (mut internal_links, mut external_links) = (Vec::new(), Vec::new());
+        for anchor in document.select(&selector("a[href]")) {
+            let href = anchor.value().attr("href").unwrap_or_default();
+            let Some(target) = base.as_ref().and_then(|base| base.join(href).ok()) else { continue };
+            if !matches!(target.scheme(), "http" | "https") {
+                continue;
+            }
+            let link = Link { url: target.to_string(), text: collapse_whitespace(&visible_text_of(anchor)) };
+            if same_site(base.as_ref(), &target) {
+                internal_links.push(link);
+            } else {
+                external_links.push(link);
+            }
+        }
+
+        let mut meta = BTreeMap::new();
+        let mut open_graph = BTreeMap::new();
+        for tag in document.select(&selector("meta[content]")) {
+            let content = tag.value().attr("content").unwrap_or_default().trim().to_string();
+            if let Some(property) = tag.value().attr("property").and_then(|p| p.strip_prefix("og:")) {
+                open_graph.insert(property.to_string(), content);
+            } else if let Some(name) = tag.value().attr("name") {
+                meta.insert(name.to_ascii_lowercase(), content);
+            }
+        }
+
+        let json_ld = document.select(&selector(r#"script[type="application/ld+json"]"#))
+            .filter_map(|script| serde_json::from_str::<Value>(&script.text().collect::<String>()).ok())
+            .flat_map(|value| match value {
+                Value::Array(items) => items,
+                other => vec![other],
+            })
+            .collect();
+
+        Self {
+            url: url.to_string(),
+            title,
+            visible_text: walk.text,
+            sections: walk.sections,
+            internal_links,
+            external_links,
+            meta,
+            open_graph,
+            json_ld,
+        }
+    }
+
+    /// Meta description, falling back to the OpenGraph one
+    pub fn description(&self) -> Option<&str> {
+        self.meta.get("description").or_else(|| self.open_graph.get("description")).map(String::as_str)
+    }
+
+    pub fn headings(&self) -> impl Iterator<Item = &Heading> {
+        self.sections.iter().filter_map(|section| section.heading.as_ref())
+    }
+
+    /// First section whose heading text or anchor names one of `keywords`
+    pub fn section_about(&self, keywords: &[&str]) -> Option<&PageSection> {
+        self.sections.iter().find(|section| {
+            section.heading.as_ref().is_some_and(|heading| {
+                mentions(&heading.text, keywords)
+                    || heading.id.as_deref().is_some_and(|id| mentions(&identifier_words(id), keywords))
+            })
+        })
+    }
+
+    /// The page as a whole is about one of `keywords`: its title or a heading names it
+    pub fn is_about(&self, keywords: &[&str]) -> bool {
+        self.title.as_deref().is_some_and(|title| mentions(title, keywords))
+            || self.section_about(keywords).is_some()
+    }
+
+    /// Every `@type` declared in the JSON-LD blocks, including nested and `@graph` entities
+    pub fn json_ld_types(&self) -> Vec<String> {
+        fn collect(value: &Value, out: &mut Vec<String>) {
+            match value {
+                Value::Object(map) => {
+                    match map.get("@type") {
+                        Some(Value::String(t)) => out.push(t.clone()),
+                        Some(Value::Array(types)) => out.extend(types.iter().filter_map(|t| t.as_str().map(str::to_string))),
+                        _ => {}
+                    }
+                    map.values().for_each(|v| collect(v, out));
+                }
+                Value::Array(items) => items.iter().for_each(|v| collect(v, out)),
+                _ => {}
+            }
+        }
+        let mut types = Vec::new();
+        self.json_ld.iter().for_each(|value| collect(value, &mut types));
+        types
+    }
+}
+
+/// Parse a fetched page; unsuccessful and non-HTML responses are an error
+pub async fn parse_response(response: reqwest::Response) -> Result<PageAnalysis> {
+    let url = response.url().to_string();
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("{} returned {}", url, response.status()));
+    }
+    let content_type = response.headers().get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("text/html")
+        .to_ascii_lowercase();
+    if !content_type.contains("html") {
+        return Err(anyhow::anyhow!("{} is {}, not HTML", url, content_type));
+    }
+    let html = response.text().await?;
+    Ok(PageAnalysis::parse(&url, &html))
+}
+
+/// `text` contains one of `keywords` as whole words, case-insensitively
+pub fn mentions(text: &str, keywords: &[&str]) -> bool {
+    let text = collapse_whitespace(text).to_lowercase();
+    let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    keywords.iter().any(|keyword| {
+        let keyword = keyword.to_lowercase();
+        text.match_indices(&keyword).any(|(start, _)| {
+            !is_word(text[..start].chars().next_back()) && !is_word(text[start + keyword.len()..].chars().next())
+        })
+    })
+}
+
+#[derive(Default)]
+struct Walk {
+    text: String,
+    sections: Vec<PageSection>,
+    section_ids: Vec<Option<String>>,
+}
+
+impl Walk {
+    fn visit(&mut self, element: ElementRef) {
+        let name = element.value().name();
+        if INVISIBLE_ELEMENTS.contains(&name) {
+            return;
+        }
+        if let Some(level) = HEADINGS.iter().position(|h| *h == name) {
+            let text = collapse_whitespace(&visible_text_of(element));
+            self.push_text(&format!(" {} ", text), false);
+            let id = element.value().id().map(str::to_string)
+                .or_else(|| self.section_ids.iter().rev().flatten().next().cloned());
+            self.sections.push(PageSection {
+                heading: Some(Heading { level: level as u8 + 1, text, id }),
+                text: String::new(),
+            });
+            return;
+        }
+
+        // Block boundaries separate words; inline markup like <b> must not split them
+        let block = !INLINE_ELEMENTS.contains(&name);
+        let sectioning = SECTIONING_ELEMENTS.contains(&name);
+        if sectioning {
+            self.section_ids.push(element.value().id().map(str::to_string));
+        }
+        if block {
+            self.push_text(" ", true);
+        }
+        for child in element.children() {
+            if let Some(child) = ElementRef::wrap(child) {
+                self.visit(child);
+            } else if let Node::Text(fragment) = child.value() {
+                self.push_text(fragment, true);
+            }
+        }
+        if block {
+            self.push_text(" ", true);
+        }
+        if sectioning {
+            self.section_ids.pop();
+        }
+    }
+
+    fn push_text(&mut self, text: &str, in_section: bool) {
+        self.text.push_str(text);
+        if in_section {
+            if self.sections.is_empty() {
+                self.sections.push(PageSection { heading: None, text: String::new() });
+            }
+            if let Some(section) = self.sections.last_mut() {
+                section.text.push_str(text);
+            }
+        }
+    }
+}
+
+/// Visible text below `element`, used for headings and link labels
+fn visible_text_of(element: ElementRef) -> String {
+    let mut walk = Walk::default();
+    for child in element.children() {
+        if let Some(child) = ElementRef::wrap(child) {
+            walk.visit(child);
+        } else if let Node::Text(fragment) = child.value() {
+            walk.text.push_str(fragment);
+        }
+    }
+    walk.text
+}
+
+pub fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("static selector is valid")
+}
+
+pub fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// `token-economics` or `teamSection` as separate words
+fn identifier_words(identifier: &str) -> String {
+    let mut words = String::new();
+    let mut previous_lower = false;
+    for c in identifier.chars() {
+        if c == '-' || c == '_' || c == '.' {
+            words.push(' ');
+        } else {
+            if c.is_uppercase() && previous_lower {
+                words.push(' ');
+            }
+            words.push(c);
+        }
+        previous_lower = c.is_lowercase();
+    }
+    words
+}
+
+pub fn same_site(base: Option<&Url>, target: &Url) -> bool {
+    let strip = |host: &str| host.trim_start_matches("www.").to_ascii_lowercase();
+    match (base.and_then(Url::host_str), target.host_str()) {
+        (Some(a), Some(b)) => strip(a) == strip(b),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const PAGE: &str = r#"<html>
+        <head>
+            <title>Example Project</title>
+            <meta name="Description" content="A transparent DeFi protocol">
+            <meta property="og:title" content="Example Project">
+            <meta property="og:image" content="https://example-project.com/card.png">
+            <script type="application/ld+json">[{"@context": "https://schema.org", "@type": "Organization",
+                "founder": {"@type": "Person", "name": "Ada Example"}}]</script>
+            <script>const audit = "fake audit badge"; const token = 1;</script>
+        </head>
+        <body>
+            <nav><a href="/people">Meet the <b>team</b></a> <a href="https://certik.com/projects/example">CertiK</a></nav>
+            <p>Welcome to <em>Example</em> Project.</p>
+            <section id="token-economics">
+                <h2>Distribution</h2>
+                <p>40% community, 20% treasury.</p>
+            </section>
+            <h2 id="plan">What's next: Roadmap 2025</h2>
+            <p>Mainnet in Q3.</p>
+        </body>
+    </html>"#;
+
+    #[test]
+    fn test_parse_structure() {
+        let page = PageAnalysis::parse("https://www.example-project.com/", PAGE);
+
+        assert_eq!(page.title.as_deref(), Some("Example Project"));
+        assert_eq!(page.meta.get("description").map(String::as_str), Some("A transparent DeFi protocol"));
+        assert_eq!(page.open_graph.get("image").map(String::as_str), Some("https://example-project.com/card.png"));
+        assert_eq!(page.json_ld_types(), vec!["Organization", "Person"]);
+
+        assert_eq!(page.internal_links, vec![Link { url: "https://www.example-project.com/people".to_string(), text: "Meet the team".to_string() }]);
+        assert_eq!(page.external_links[0].url, "https://certik.com/projects/example");
+
+        let headings: Vec<(u8, &str, Option<&str>)> = page.headings()
+            .map(|h| (h.level, h.text.as_str(), h.id.as_deref()))
+            .collect();
+        assert_eq!(headings, vec![(2, "Distribution", Some("token-economics")), (2, "What's next: Roadmap 2025", Some("plan"))]);
+        assert_eq!(collapse_whitespace(&page.sections[1].text), "40% community, 20% treasury.");
+    }
+
+    #[test]
+    fn test_visible_text_skips_markup_and_scripts() {
+        let page = PageAnalysis::parse("https://example-project.com/", PAGE);
+        let text = collapse_whitespace(&page.visible_text);
+        assert!(text.contains("Welcome to Example Project."));
+        assert!(text.contains("Roadmap 2025 Mainnet in Q3."));
+        assert!(!text.contains("fake audit badge"));
+        assert!(!text.contains("A transparent DeFi protocol"));
+    }
+
+    #[test]
+    fn test_sections_match_semantically() {
+        let page = PageAnalysis::parse("https://example-project.com/", PAGE);
+
+        assert!(page.section_about(&["roadmap"]).is_some());
+        // Matched through the enclosing section's id
+        assert_eq!(page.section_about(&["token economics"]).unwrap().heading.as_ref().unwrap().text, "Distribution");
+        // Only the script mentions audits
+        assert!(page.section_about(&["audit"]).is_none());
+        assert!(!page.is_about(&["audit"]));
+        assert!(page.internal_links[0].is_about(&["team"]));
+        assert!(!mentions("teams of bots", &["team"]));
+    }
+
+    #[tokio::test]
+    async fn test_parse_response() {
+        let server = test_http::serve(vec![
+            ("/", MockResponse::ok(PAGE).with_header("Content-Type", "text/html")),
+        ]).await;
+
+        let client = reqwest::Client::new();
+        let fetch = |path: &str| client.get(format!("{}{}", server.base_url, path)).send();
+        let page = parse_response(fetch("/").await.unwrap()).await.unwrap();
+        assert_eq!(page.internal_links[0].url, format!("{}/people", server.base_url));
+        assert!(parse_response(fetch("/missing").await.unwrap()).await.is_err());
+    }
+}
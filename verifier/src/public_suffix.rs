@@ -0,0 +1,65 @@
+use std::net::IpAddr;
+use std::sync::OnceLock;
+
+use publicsuffix::{List, Psl};
+
+/// Snapshot of https://publicsuffix.org/list/public_suffix_list.dat
+const PUBLIC_SUFFIX_LIST: &str = include_str!("../data/public_suffix_list.dat");
+
+/// Marker separating registry-operated suffixes from privately operated ones like github.io
+const PRIVATE_DOMAINS_MARKER: &str = "// ===BEGIN PRIVATE DOMAINS===";
+
+fn icann_list() -> &'static List {
+    static LIST: OnceLock<List> = OnceLock::new();
+    LIST.get_or_init(|| {
+        let icann = PUBLIC_SUFFIX_LIST.split(PRIVATE_DOMAINS_MARKER).next().unwrap_or_default();
+        icann.parse().expect("bundled public suffix list is valid")
+    })
+}
+
+fn full_list() -> &'static List {
+    static LIST: OnceLock<List> = OnceLock::new();
+    LIST.get_or_init(|| PUBLIC_SUFFIX_LIST.parse().expect("bundled public suffix list is valid"))
+}
+
+/// The site a host belongs to: like `registered_domain`, but each github.io user is a site of their own
+pub fn site_domain(host: &str) -> String {
+    registrable_with(full_list(), host)
+}
+
+/// The domain registered with a registry (private suffixes like github.io are ignored), as RDAP knows it
+pub fn registered_domain(host: &str) -> String {
+    registrable_with(icann_list(), host)
+}
+
+fn registrable_with(list: &List, host: &str) -> String {
+    let host = normalize(host);
+    if host.parse::<IpAddr>().is_ok() {
+        return host;
+    }
+    list.domain(host.as_bytes())
+        .map(|domain| String::from_utf8_lossy(domain.as_bytes()).into_owned())
+        .unwrap_or(host)
+}
+
+fn normalize(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registrable_domains() {
+        assert_eq!(registered_domain("www.example.co.uk"), "example.co.uk");
+        assert_eq!(registered_domain("App.Example.com."), "example.com");
+        assert_eq!(registered_domain("docs.alice.github.io"), "github.io");
+        assert_eq!(registered_domain("api.example.com.au"), "example.com.au");
+        assert_eq!(registered_domain("127.0.0.1"), "127.0.0.1");
+        assert_eq!(registered_domain("localhost"), "localhost");
+
+        assert_eq!(site_domain("docs.alice.github.io"), "alice.github.io");
+        assert_eq!(site_domain("app.example.co.uk"), "example.co.uk");
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::public_suffix;
+
+/// Bootstrap service that redirects to the authoritative RDAP server for each TLD
+pub const DEFAULT_RDAP_BASE_URL: &str = "https://rdap.org";
+
+/// EPP status values showing a domain is suspended or on its way out
+const DISTRESS_STATUSES: &[&str] = &[
+    "client hold",
+    "server hold",
+    "redemption period",
+    "pending delete",
+    "pending restore",
+];
+
+/// Registration data for a domain from RDAP
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainRegistration {
+    /// URL the data was retrieved from
+    pub source: String,
+    pub registered_at: Option<u64>,
+    pub last_changed: Option<u64>,
+    pub expires_at: Option<u64>,
+    pub registrar: Option<String>,
+    pub status: Vec<String>,
+    /// The host is a site under a private suffix like github.io, so the registration is the platform's
+    #[serde(default)]
+    pub hosting_platform: bool,
+}
+
+impl DomainRegistration {
+    /// Whole days since registration, if the registry published a registration date
+    pub fn age_days(&self, now: u64) -> Option<u64> {
+        self.registered_at.map(|registered| now.saturating_sub(registered) / (24 * 60 * 60))
+    }
+
+    /// Statuses signalling a held, expiring or deleted registration
+    pub fn distress_statuses(&self) -> Vec<String> {
+        self.status.iter()
+            .filter(|s| DISTRESS_STATUSES.contains(&s.to_ascii_lowercase().as_str()))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Look up the registered domain of `host` at `{base_url}/domain/{domain}`; registries only know
+/// `example.co.uk`, not `www.example.co.uk`, and for `alice.github.io` they only know `github.io`
+pub async fn lookup_domain(base_url: &str, host: &str) -> Result<DomainRegistration> {
+    let domain = public_suffix::registered_domain(host);
+    let url = format!("{}/domain/{}", base_url.trim_end_matches('/'), domain);
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+    let response = client
+        .get(&url)
+        .header("Accept", "application/rdap+json, application/json")
+        .send()
+        .await
+        .with_context(|| format!("RDAP request failed: {}", url))?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("RDAP server returned {} for {}", response.status(), url));
+    }
+    let body: Value = response.json().await
+        .with_context(|| format!("Invalid RDAP response from {}", url))?;
+    Ok(DomainRegistration {
+        hosting_platform: public_suffix::site_domain(host) != domain,
+        ..parse_domain_response(&url, &body)
+    })
+}
+
+/// Extract registration events, registrar and status from an RDAP domain object
+pub fn parse_domain_response(source: &str, body: &Value) -> DomainRegistration {
+    let event = |action: &str| -> Option<u64> {
+        body["events"].as_array()?
+            .iter()
+            .find(|e| e["eventAction"].as_str() == Some(action))
+            .and_then(|e| e["eventDate"].as_str())
+            .and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok())
+            .and_then(|date| u64::try_from(date.timestamp()).ok())
+    };
+
+    let registrar = body["entities"].as_array().and_then(|entities| {
+        entities.iter()
+            .find(|e| e["roles"].as_array().is_some_and(|roles| roles.iter().any(|r| r == "registrar")))
+            .and_then(|e| vcard_name(e).or_else(|| e["handle"].as_str().map(str::to_string)))
+    });
+
+    DomainRegistration {
+        source: source.to_string(),
+        registered_at: event("registration"),
+        last_changed: event("last changed"),
+        expires_at: event("expiration"),
+        registrar,
+        status: body["status"].as_array()
+            .map(|s| s.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+            .unwrap_or_default(),
+        hosting_platform: false,
+    }
+}
+
+/// The `fn` property of a jCard `vcardArray`
+fn vcard_name(entity: &Value) -> Option<String> {
+    entity["vcardArray"][1].as_array()?
+        .iter()
+        .find(|prop| prop[0] == "fn")
+        .and_then(|prop| prop[3].as_str())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const FIXTURE: &str = include_str!("../fixtures/rdap_domain.json");
+
+    #[test]
+    fn test_parse_domain_response() {
+        let body: Value = serde_json::from_str(FIXTURE).unwrap();
+        let registration = parse_domain_response("fixture", &body);
+
+        assert_eq!(registration.registered_at, Some(1615832525));
+        assert_eq!(registration.expires_at, Some(1805134925));
+        assert_eq!(registration.last_changed, Some(1707555600));
+        assert_eq!(registration.registrar.as_deref(), Some("Example Registrar, Inc."));
+        assert_eq!(registration.status.len(), 3);
+        assert!(registration.distress_statuses().is_empty());
+        assert_eq!(registration.age_days(1615832525 + 29 * 86400 + 5), Some(29));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_domain_against_fixture_server() {
+        let server = test_http::serve(vec![
+            ("/domain/example-project.com", MockResponse::ok(FIXTURE).with_header("Content-Type", "application/rdap+json")),
+            ("/domain/example-project.com", MockResponse::ok(FIXTURE)),
+        ]).await;
+
+        let registration = lookup_domain(&server.base_url, "example-project.com").await.unwrap();
+        assert_eq!(registration.source, format!("{}/domain/example-project.com", server.base_url));
+        assert_eq!(registration.registered_at, Some(1615832525));
+        assert!(server.requests.lock().unwrap()[0].contains("application/rdap+json"));
+
+        // Subdomains are looked up at their registered domain
+        let registration = lookup_domain(&server.base_url, "app.example-project.com").await.unwrap();
+        assert_eq!(registration.source, format!("{}/domain/example-project.com", server.base_url));
+        assert!(!registration.hosting_platform);
+
+        let missing = lookup_domain(&server.base_url, "unregistered.example").await;
+        assert!(missing.unwrap_err().to_string().contains("404"));
+    }
+
+    #[tokio::test]
+    async fn test_github_pages_site_gets_the_platform_registration() {
+        let server = test_http::serve(vec![("/domain/github.io", MockResponse::ok(FIXTURE))]).await;
+
+        let registration = lookup_domain(&server.base_url, "alice.github.io").await.unwrap();
+        assert_eq!(registration.source, format!("{}/domain/github.io", server.base_url));
+        assert!(registration.hosting_platform);
+    }
+
+    #[test]
+    fn test_held_domain() {
+        let body = serde_json::json!({ "status": ["active", "client hold"], "events": [] });
+        let registration = parse_domain_response("inline", &body);
+        assert_eq!(registration.registered_at, None);
+        assert_eq!(registration.distress_statuses(), vec!["client hold"]);
+    }
+}
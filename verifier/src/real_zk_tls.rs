@@ -1,43 +1,426 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
-use regex::Regex;
+use x509_parser::extensions::GeneralName;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use crate::audit_registry::{self, AuditRegistry, AuditReportCheck, AuditStatus};
+use crate::certificate_history::{self, CertificateAnomaly, CertificateHistory, CertificateObservation};
+use crate::certificate_transparency::{self, CertificateTransparencyReport, CtLogList};
+use crate::chain_validation::{self, ChainValidationReport, TrustStore, ValidationFailure};
+use crate::clone_detection::{self, OriginReport, RepositoryOrigin, UpstreamCorpus};
+use crate::commit_signing::{self, SigningReport};
+use crate::dns::{self, CaaAuthorization, DnsReport, DnsResolver, DnssecStatus, RecordData};
+use crate::forge::{ForgeClient, ForgeKind, SourceRepository};
+use crate::github::{self, GitHubClient};
+use crate::github_org::{self, OrganizationAnalysis, OrganizationLink};
+use crate::impersonation::{self, ImpersonationDetector, ImpersonationFinding};
+use crate::rdap::{self, DomainRegistration};
+use crate::repo_health::{self, RepositoryHealth};
+use crate::crawler::{self, CrawlConfig, Indicator};
+use crate::scam_language::{self, ScamLanguageReport, ScamLexicon};
+use crate::revocation::{self, CertificateRevokedError, RevocationReport, RevocationSources};
+use crate::security_headers::{self, SecurityHeaderReport};
+use crate::security_txt::{self, SecurityTxt, SignatureStatus};
+use crate::team::{self, TeamMember, VerificationLevel};
+use crate::tls_audit::{self, TlsConfigurationAudit, TlsWeakness};
+use crate::tls_handshake;
+use crate::tokenomics::{self, TokenDistribution, TokenomicsProblem};
+
+/// Where the certificate data in a `RealTlsCertificate` came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CertificateProvenance {
+    /// Captured from a live TLS handshake
+    Observed,
+    /// Loaded from a previously stored observation
+    Cached,
+    /// Synthesized because no certificate could be obtained; never trusted
+    Fallback,
+    /// Parsed from a PEM/DER file supplied by the user
+    FromFile,
+}
+
+impl std::fmt::Display for CertificateProvenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            CertificateProvenance::Observed => "observed",
+            CertificateProvenance::Cached => "cached",
+            CertificateProvenance::Fallback => "fallback",
+            CertificateProvenance::FromFile => "from file",
+        };
+        f.write_str(name)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RealTlsCertificate {
     pub domain: String,
+    pub subject: String,
     pub issuer: String,
     pub serial_number: Vec<u8>,
     pub not_before: u64,
     pub not_after: u64,
+    /// DER-encoded SubjectPublicKeyInfo of the leaf certificate
     pub public_key: Vec<u8>,
+    pub subject_alt_names: Vec<String>,
+    /// DER-encoded certificates as presented by the server, leaf first
+    pub presented_chain: Vec<Vec<u8>>,
+    pub chain_validation: Option<ChainValidationReport>,
+    pub certificate_transparency: Option<CertificateTransparencyReport>,
+    pub revocation: Option<RevocationReport>,
+    pub provenance: CertificateProvenance,
+    /// Changes relative to earlier observations of this domain, when a history store is configured
+    #[serde(default)]
+    pub history_anomalies: Vec<CertificateAnomaly>,
+    /// Negotiated TLS parameters and legacy support, for live handshakes only
+    #[serde(default)]
+    pub tls_configuration: Option<TlsConfigurationAudit>,
     pub is_valid: bool,
     pub verification_timestamp: u64,
 }
 
+impl RealTlsCertificate {
+    /// Build a certificate record from a DER-encoded X.509 leaf certificate
+    pub fn from_der(
+        domain: &str,
+        der: &[u8],
+        provenance: CertificateProvenance,
+        is_valid: bool,
+        verification_timestamp: u64,
+    ) -> Result<Self> {
+        let (_, cert) = X509Certificate::from_der(der)
+            .map_err(|e| anyhow::anyhow!("Failed to parse X.509 certificate: {}", e))?;
+
+        let subject_alt_names = match cert.subject_alternative_name() {
+            Ok(Some(san)) => san.value.general_names.iter()
+                .filter_map(|name| match name {
+                    GeneralName::DNSName(dns) => Some(dns.to_string()),
+                    GeneralName::IPAddress(ip) => Some(format_ip_address(ip)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(Self {
+            domain: domain.to_string(),
+            subject: cert.subject().to_string(),
+            issuer: cert.issuer().to_string(),
+            serial_number: cert.raw_serial().to_vec(),
+            not_before: cert.validity().not_before.timestamp().max(0) as u64,
+            not_after: cert.validity().not_after.timestamp().max(0) as u64,
+            public_key: cert.public_key().raw.to_vec(),
+            subject_alt_names,
+            presented_chain: vec![der.to_vec()],
+            chain_validation: None,
+            certificate_transparency: None,
+            revocation: None,
+            provenance,
+            history_anomalies: Vec::new(),
+            tls_configuration: None,
+            is_valid,
+            verification_timestamp,
+        })
+    }
+}
+
+/// Domain a certificate was issued for: its first exact DNS SAN, else its first wildcard SAN, else the
+/// subject common name. A wildcard is kept as is so the name check matches it against the certificate.
+fn certificate_domain(der: &[u8]) -> Result<String> {
+    let (_, cert) = X509Certificate::from_der(der)
+        .map_err(|e| anyhow::anyhow!("Failed to parse X.509 certificate: {}", e))?;
+    let sans: Vec<&str> = match cert.subject_alternative_name() {
+        Ok(Some(san)) => san.value.general_names.iter().filter_map(|name| match name {
+            GeneralName::DNSName(dns) => Some(*dns),
+            _ => None,
+        }).collect(),
+        _ => Vec::new(),
+    };
+    sans.iter().find(|name| !name.starts_with("*.")).or(sans.first())
+        .map(|name| name.to_string())
+        .or_else(|| {
+            cert.subject().iter_common_name()
+                .next()
+                .and_then(|cn| cn.as_str().ok())
+                .map(str::to_string)
+        })
+        .ok_or_else(|| anyhow::anyhow!("Certificate names no domain; pass one explicitly"))
+}
+
+/// GitHub account names a project on `domain` is likely to use
+fn github_username_candidates(domain: &str) -> Vec<String> {
+    vec![
+        domain.replace(".com", "").replace(".", "-"),
+        domain.replace(".com", "").replace(".", ""),
+        domain.split('.').next().unwrap_or(domain).to_string(),
+    ]
+}
+
+fn format_ip_address(bytes: &[u8]) -> String {
+    match bytes.len() {
+        4 => std::net::Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]).to_string(),
+        16 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(bytes);
+            std::net::Ipv6Addr::from(octets).to_string()
+        }
+        _ => hex::encode(bytes),
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RealTransparencyData {
     pub domain: String,
-    pub has_public_github: bool,
+    /// A public repository on GitHub, GitLab, Gitea/Codeberg or Bitbucket
+    #[serde(alias = "has_public_github")]
+    pub has_public_repository: bool,
     pub has_documented_roadmap: bool,
     pub has_audit_reports: bool,
     pub has_team_verification: bool,
     pub has_token_economics: bool,
     pub code_review_score: u8,
+    /// Stars and forks on the repository's forge, whichever it is
     pub github_stars: u32,
     pub github_forks: u32,
     pub last_commit: Option<u64>,
     pub license: Option<String>,
+    /// Security headers served by the site's front page
+    #[serde(default)]
+    pub security_headers: Option<SecurityHeaderReport>,
+    #[serde(default)]
+    pub dns: Option<DnsReport>,
+    /// RDAP registration data: creation date, registrar and status flags
+    #[serde(default)]
+    pub registration: Option<DomainRegistration>,
+    /// Brands the domain looks like without belonging to them
+    #[serde(default)]
+    pub impersonation: Vec<ImpersonationFinding>,
+    /// High-risk phrases in the front page's visible text
+    #[serde(default)]
+    pub scam_language: Option<ScamLanguageReport>,
+    /// Page where each roadmap/audit/team/tokenomics indicator was found
+    #[serde(default)]
+    pub indicator_sources: BTreeMap<Indicator, String>,
+    /// RFC 9116 security.txt: disclosure contacts, expiry and signature status
+    #[serde(default)]
+    pub security_txt: Option<SecurityTxt>,
+    /// Linked audit reports matched against the auditor registry
+    #[serde(default)]
+    pub audit_reports: Vec<AuditReportCheck>,
+    /// People named on the team page and how far each could be verified
+    #[serde(default)]
+    pub team_members: Vec<TeamMember>,
+    /// Supply, allocations, vesting and contract addresses from the whitepaper or tokenomics page
+    #[serde(default)]
+    pub tokenomics: Option<TokenDistribution>,
+    /// Where the project's code is hosted
+    #[serde(default)]
+    pub source_repository: Option<SourceRepository>,
+    /// Commit, contributor, issue and release activity behind `code_review_score`
+    #[serde(default)]
+    pub repository_health: Option<RepositoryHealth>,
+    /// Whether the repository is original work, a fork, a template instance or a copy of a known upstream
+    #[serde(default)]
+    pub repository_origin: Option<OriginReport>,
+    /// Signed and verified commits and release tags, and the maintainers' signing keys
+    #[serde(default)]
+    pub commit_signing: Option<SigningReport>,
+    /// The project's GitHub organization and activity across its public repositories
+    #[serde(default)]
+    pub github_organization: Option<OrganizationAnalysis>,
+    /// Analyzed with `with_supply_chain_sensitive`: release signing weighs more than popularity
+    #[serde(default)]
+    pub supply_chain_sensitive: bool,
+}
+
+impl RealTransparencyData {
+    /// Transparency data for a domain that could not be analyzed; every indicator is absent
+    pub fn unavailable(domain: &str) -> Self {
+        Self {
+            domain: domain.to_string(),
+            has_public_repository: false,
+            has_documented_roadmap: false,
+            has_audit_reports: false,
+            has_team_verification: false,
+            has_token_economics: false,
+            code_review_score: 0,
+            github_stars: 0,
+            github_forks: 0,
+            last_commit: None,
+            license: None,
+            security_headers: None,
+            dns: None,
+            registration: None,
+            impersonation: Vec::new(),
+            scam_language: None,
+            indicator_sources: BTreeMap::new(),
+            security_txt: None,
+            audit_reports: Vec::new(),
+            team_members: Vec::new(),
+            tokenomics: None,
+            source_repository: None,
+            repository_health: None,
+            repository_origin: None,
+            commit_signing: None,
+            github_organization: None,
+            supply_chain_sensitive: false,
+        }
+    }
 }
 
 pub struct RealZkTlsVerifier {
-    github_token: Option<String>,
+    github: GitHubClient,
+    forges: ForgeClient,
+    trust_store: Option<TrustStore>,
+    ct_log_list: Option<CtLogList>,
+    revocation_sources: RevocationSources,
+    strict_certificates: bool,
+    offline: bool,
+    certificate_history: Option<Mutex<CertificateHistory>>,
+    dns_resolver: Option<std::net::SocketAddr>,
+    rdap_base_url: String,
+    impersonation: ImpersonationDetector,
+    scam_lexicon: ScamLexicon,
+    crawl_config: CrawlConfig,
+    audit_registry: AuditRegistry,
+    upstream_corpus: UpstreamCorpus,
+    supply_chain_sensitive: bool,
 }
 
 impl RealZkTlsVerifier {
     pub fn new(github_token: Option<String>) -> Self {
-        Self { github_token }
+        Self {
+            github: GitHubClient::new(github::DEFAULT_GITHUB_API_URL, github_token),
+            forges: ForgeClient::default(),
+            trust_store: None,
+            ct_log_list: None,
+            revocation_sources: RevocationSources::default(),
+            strict_certificates: false,
+            offline: false,
+            certificate_history: None,
+            dns_resolver: None,
+            rdap_base_url: rdap::DEFAULT_RDAP_BASE_URL.to_string(),
+            impersonation: ImpersonationDetector::default(),
+            scam_lexicon: ScamLexicon::default(),
+            crawl_config: CrawlConfig::default(),
+            audit_registry: AuditRegistry::default(),
+            upstream_corpus: UpstreamCorpus::default(),
+            supply_chain_sensitive: false,
+        }
+    }
+
+    /// Record every certificate in the history store at `path` and flag changes against it
+    pub fn with_certificate_history(mut self, path: &str) -> Result<Self> {
+        self.certificate_history = Some(Mutex::new(CertificateHistory::load(path)?));
+        Ok(self)
+    }
+
+    /// Send DNS queries to `server` instead of the system resolver
+    pub fn with_dns_resolver(mut self, server: std::net::SocketAddr) -> Self {
+        self.dns_resolver = Some(server);
+        self
+    }
+
+    /// Query RDAP at `base_url` instead of the rdap.org bootstrap service
+    pub fn with_rdap_base_url(mut self, base_url: &str) -> Self {
+        self.rdap_base_url = base_url.to_string();
+        self
+    }
+
+    /// Query the GitHub API at `base_url`, e.g. a GitHub Enterprise server or a local mock
+    pub fn with_github_api_url(mut self, base_url: &str) -> Self {
+        self.github = self.github.with_base_url(base_url);
+        self
+    }
+
+    /// Query `forge` at `base_url`, e.g. a self-hosted GitLab or Gitea instance or a local mock
+    pub fn with_forge_api_url(mut self, forge: ForgeKind, base_url: &str) -> Self {
+        self.forges = self.forges.with_api_url(forge, base_url);
+        self
+    }
+
+    /// Cache GitHub responses in `path` and revalidate them with conditional requests
+    pub fn with_github_cache(mut self, path: &str) -> Result<Self> {
+        self.github = self.github.with_cache(path)?;
+        Ok(self)
+    }
+
+    /// Also flag domains imitating the brands listed in `path`, one domain or name per line
+    pub fn with_brand_list(mut self, path: &str) -> Result<Self> {
+        self.impersonation = self.impersonation.with_brand_file(path)?;
+        Ok(self)
+    }
+
+    /// Scan page text with the weighted phrases in `path` instead of the built-in lexicon
+    pub fn with_scam_lexicon(mut self, path: &str) -> Result<Self> {
+        self.scam_lexicon = ScamLexicon::from_file(path)?;
+        Ok(self)
+    }
+
+    /// Verify linked audit reports against the auditor publications listed in `path` as well as the built-in firms
+    pub fn with_audit_registry(mut self, path: &str) -> Result<Self> {
+        self.audit_registry.extend(AuditRegistry::from_file(path)?);
+        Ok(self)
+    }
+
+    /// Compare the project's repository against the known upstream repositories listed in `path`
+    pub fn with_upstream_corpus(mut self, path: &str) -> Result<Self> {
+        self.upstream_corpus = UpstreamCorpus::from_file(path)?;
+        Ok(self)
+    }
+
+    /// Weigh signed releases above popularity, for projects whose code others build on
+    pub fn with_supply_chain_sensitive(mut self, sensitive: bool) -> Self {
+        self.supply_chain_sensitive = sensitive;
+        self
+    }
+
+    /// Follow site links at most `max_depth` hops and fetch at most `max_pages` pages
+    pub fn with_crawl_limits(mut self, max_depth: usize, max_pages: usize) -> Self {
+        self.crawl_config.max_depth = max_depth;
+        self.crawl_config.max_pages = max_pages;
+        self
+    }
+
+    fn resolver(&self) -> DnsResolver {
+        self.dns_resolver.map(DnsResolver::new).unwrap_or_else(DnsResolver::system)
+    }
+
+    /// Skip every network lookup apart from the certificate handshake itself
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Fail instead of substituting a fallback certificate when the handshake fails
+    pub fn with_strict_certificates(mut self, strict: bool) -> Self {
+        self.strict_certificates = strict;
+        self
+    }
+
+    /// Validate certificate chains against the roots in `path` instead of the native trust store
+    pub fn with_trust_store(mut self, path: &str) -> Result<Self> {
+        self.trust_store = Some(TrustStore::from_file(path)?);
+        Ok(self)
+    }
+
+    /// Verify SCT signatures against the CT logs listed in a `log_list.json` file
+    pub fn with_ct_log_list(mut self, path: &str) -> Result<Self> {
+        self.ct_log_list = Some(CtLogList::from_file(path)?);
+        Ok(self)
+    }
+
+    /// Check certificates against local CRL and OCSP response files in addition to stapled OCSP
+    pub fn with_revocation_sources(mut self, crl_files: Vec<String>, ocsp_files: Vec<String>) -> Self {
+        self.revocation_sources = RevocationSources { crl_files, ocsp_files };
+        self
+    }
+
+    fn trust_store(&self) -> TrustStore {
+        self.trust_store.clone().unwrap_or_else(TrustStore::native)
     }
 
     /// Fetch real TLS certificate for a domain using actual TLS connection
@@ -61,14 +444,27 @@ impl RealZkTlsVerifier {
                 println!("✅ Successfully fetched real TLS certificate for {}", clean_domain);
                 Ok(cert_data)
             }
+            Err(e) if e.is::<CertificateRevokedError>() => {
+                // A revoked certificate is a hard failure, never replaced by fallback data
+                println!("🚫 {}", e);
+                Err(e)
+            }
+            Err(e) if self.strict_certificates => {
+                Err(e.context(format!("Strict mode: no certificate observed for {}", clean_domain)))
+            }
             Err(e) => {
                 println!("⚠️ Failed to fetch real certificate for {}: {}", clean_domain, e);
-                println!("🔄 Falling back to domain-based validation...");
-                
-                // Fallback: basic domain validation
-                let (issuer, is_valid) = self.validate_domain_basic(&clean_domain);
-                
-                // Generate fallback certificate data
+                match self.cached_certificate(&clean_domain, now) {
+                    Ok(Some(cert)) => {
+                        println!("🗄️ Using the last certificate observed for {}", clean_domain);
+                        return Ok(cert);
+                    }
+                    Ok(None) => {}
+                    Err(e) => println!("⚠️ Cached certificate for {} is unusable: {}", clean_domain, e),
+                }
+                println!("🔄 Substituting an untrusted fallback certificate (use --strict to fail instead)...");
+
+                // Placeholder data only: it is marked as fallback and never reported as valid
                 let serial_number = format!("{:016x}", now).into_bytes();
                 let not_before = now - (365 * 24 * 60 * 60);
                 let not_after = now + (90 * 24 * 60 * 60);
@@ -76,140 +472,429 @@ impl RealZkTlsVerifier {
 
                 Ok(RealTlsCertificate {
                     domain: clean_domain.to_string(),
-                    issuer: issuer.to_string(),
+                    subject: format!("CN={}", clean_domain),
+                    issuer: "Unknown CA (fallback)".to_string(),
                     serial_number,
                     not_before,
                     not_after,
                     public_key,
-                    is_valid,
+                    subject_alt_names: vec![clean_domain.to_string()],
+                    presented_chain: Vec::new(),
+                    chain_validation: None,
+                    certificate_transparency: None,
+                    revocation: None,
+                    provenance: CertificateProvenance::Fallback,
+                    history_anomalies: Vec::new(),
+                    tls_configuration: None,
+                    is_valid: false,
                     verification_timestamp: now,
                 })
             }
         }
     }
 
-    /// Fetch real TLS certificate using HTTP requests (simplified but real)
+    /// Fetch the real peer certificate by performing a TLS handshake with the domain
     async fn fetch_real_certificate(&self, domain: &str) -> Result<RealTlsCertificate> {
-        println!("🔗 Attempting real TLS connection to: {}", domain);
-        
-        // Try to make a real HTTPS request to the domain
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .danger_accept_invalid_certs(false) // Only accept valid certificates
-            .build()?;
-
-        let url = format!("https://{}", domain);
+        println!("🔗 Attempting real TLS handshake with: {}", domain);
+
+        let trust_store = self.trust_store();
+        let timeout = std::time::Duration::from_secs(10);
+        let capture = tls_handshake::capture_handshake(
+            domain,
+            443,
+            timeout,
+            trust_store.to_root_store(),
+        ).await?;
         let current_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
 
-        // Attempt the HTTPS request
-        match client.get(&url).send().await {
-            Ok(response) => {
-                println!("✅ Successfully connected to {} with valid TLS", domain);
-                
-                // Extract headers that indicate certificate info
-                let server_header = response.headers()
-                    .get("server")
-                    .and_then(|h| h.to_str().ok())
-                    .unwrap_or("Unknown")
-                    .to_string();
-                
-                // Generate realistic certificate data based on successful connection
-                let issuer = if server_header.contains("nginx") || server_header.contains("apache") {
-                    "Let's Encrypt"
-                } else if domain.contains("github.com") || domain.contains("google.com") {
-                    "DigiCert"
-                } else {
-                    "Trusted CA"
-                };
+        let mut cert = self.inspect_certificates(domain, &capture, &trust_store, CertificateProvenance::Observed, current_time)?;
+        if let Some(history) = &self.certificate_history {
+            if let Err(e) = certificate_history::save(history).await {
+                println!("⚠️ Could not save certificate history: {}", e);
+            }
+        }
 
-                let serial_number = format!("{:016x}", current_time).into_bytes();
-                let not_before = current_time - (365 * 24 * 60 * 60); // 1 year ago
-                let not_after = current_time + (90 * 24 * 60 * 60); // 90 days from now
-                let public_key = format!("real_key_{}", domain).into_bytes();
+        let audit = tls_audit::audit_configuration(domain, 443, timeout, &capture).await;
+        println!("🔐 TLS configuration:");
+        println!("   - Protocol: {}", audit.protocol_version.as_deref().unwrap_or("unknown"));
+        println!("   - Cipher suite: {}", audit.cipher_suite.as_deref().unwrap_or("unknown"));
+        println!("   - ALPN: {}", audit.alpn_protocol.as_deref().unwrap_or("none"));
+        println!("   - Key exchange: {}", audit.key_exchange_group.as_deref().unwrap_or("unknown"));
+        println!("   - Leaf key: {} {} bits", audit.leaf_key_type, audit.leaf_key_bits);
+        for weakness in audit.weaknesses() {
+            println!("   ⚠️ {}", weakness);
+        }
+        cert.tls_configuration = Some(audit);
+        Ok(cert)
+    }
 
-                println!("📜 Real TLS connection details:");
-                println!("   - Domain: {}", domain);
-                println!("   - Server: {}", server_header);
-                println!("   - Issuer: {}", issuer);
-                println!("   - Connection successful: true");
+    /// Replay the most recent chain stored in the history for `domain`
+    fn cached_certificate(&self, domain: &str, now: u64) -> Result<Option<RealTlsCertificate>> {
+        let Some(history) = &self.certificate_history else {
+            return Ok(None);
+        };
+        let chain = match history.lock().unwrap().latest(domain) {
+            Some(observation) => observation.chain_der()?,
+            None => return Ok(None),
+        };
+        if chain.is_empty() {
+            return Ok(None);
+        }
+        let capture = tls_handshake::HandshakeCapture::from_chain(chain);
+        self.inspect_certificates(domain, &capture, &self.trust_store(), CertificateProvenance::Cached, now)
+            .map(Some)
+    }
 
-                Ok(RealTlsCertificate {
-                    domain: domain.to_string(),
-                    issuer: issuer.to_string(),
-                    serial_number,
-                    not_before,
-                    not_after,
-                    public_key,
-                    is_valid: true, // If we got here, the certificate is valid
-                    verification_timestamp: current_time,
-                })
+    /// Build a certificate record from a PEM/DER certificate or chain file instead of a live host.
+    /// Without `domain`, the first SAN (or the subject CN) of the leaf is used.
+    pub fn load_certificate_file(&self, path: &str, domain: Option<&str>) -> Result<RealTlsCertificate> {
+        println!("📂 Loading certificate from file: {}", path);
+
+        let chain = chain_validation::load_certificates(path)?;
+        let leaf = chain.first()
+            .ok_or_else(|| anyhow::anyhow!("No certificates found in {}", path))?;
+        let domain = match domain {
+            Some(domain) => domain.to_string(),
+            None => certificate_domain(leaf)?,
+        };
+        let current_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        // A file carries no handshake: no verifier verdict, TLS-extension SCTs or stapled OCSP
+        let capture = tls_handshake::HandshakeCapture::from_chain(chain);
+        self.inspect_certificates(&domain, &capture, &self.trust_store(), CertificateProvenance::FromFile, current_time)
+    }
+
+    /// Validate, revocation-check and CT-check a presented chain and build the certificate record
+    fn inspect_certificates(
+        &self,
+        domain: &str,
+        capture: &tls_handshake::HandshakeCapture,
+        trust_store: &TrustStore,
+        provenance: CertificateProvenance,
+        current_time: u64,
+    ) -> Result<RealTlsCertificate> {
+        let leaf = capture.peer_certificates.first()
+            .ok_or_else(|| anyhow::anyhow!("No peer certificate presented by {}", domain))?;
+
+        let mut report = chain_validation::validate_chain(domain, &capture.peer_certificates, trust_store, current_time);
+        if let Some(error) = &capture.verification_error {
+            // Never report a chain as trusted when the handshake verifier disagreed
+            if report.is_trusted {
+                if let Some(hop) = report.hops.first_mut() {
+                    hop.failures.push(ValidationFailure::HandshakeRejected(error.clone()));
+                }
+                report.is_trusted = false;
             }
-            Err(e) => {
-                println!("❌ Failed to connect to {}: {}", domain, e);
-                Err(anyhow::anyhow!("TLS connection failed: {}", e))
+        }
+
+        let revocation_report = revocation::check_revocation(
+            &capture.peer_certificates,
+            capture.ocsp_response.as_deref(),
+            &self.revocation_sources,
+            current_time,
+        )?;
+        for check in revocation_report.unverified_revocations() {
+            println!("⚠️ Ignoring revocation of {} claimed by {:?}: signature does not verify", check.subject, check.source);
+        }
+        if let Some(check) = revocation_report.revoked() {
+            let revoked = CertificateRevokedError {
+                domain: domain.to_string(),
+                detail: format!("{} reported by {:?}", check.subject, check.source),
+            };
+            // A file is being inspected, not trusted: its revocation is a finding to score
+            if provenance != CertificateProvenance::FromFile {
+                return Err(revoked.into());
             }
+            println!("🚫 {}", revoked);
+        }
+        let revoked = revocation_report.revoked().is_some();
+
+        let mut cert = RealTlsCertificate::from_der(
+            domain,
+            leaf,
+            provenance,
+            report.is_trusted && !revoked,
+            current_time,
+        )?;
+        cert.presented_chain = capture.peer_certificates.clone();
+
+        let ct_report = certificate_transparency::verify_scts(
+            leaf,
+            certificate_transparency::issuer_of(leaf, &capture.peer_certificates),
+            &capture.scts,
+            capture.ocsp_response.as_deref(),
+            self.ct_log_list.as_ref(),
+        )?;
+
+        println!("📜 Real TLS certificate details:");
+        println!("   - Domain: {}", cert.domain);
+        println!("   - Provenance: {}", cert.provenance);
+        println!("   - Subject: {}", cert.subject);
+        println!("   - Issuer: {}", cert.issuer);
+        println!("   - Serial: {}", hex::encode(&cert.serial_number));
+        println!("   - SANs: {}", cert.subject_alt_names.join(", "));
+        println!("🔗 Certificate path ({} trust store):", report.trust_store);
+        for hop in &report.hops {
+            println!("   - [{:?}] {} ({} {} bits, {})", hop.role, hop.subject, hop.key_type, hop.key_size, hop.signature_algorithm);
+        }
+        for reason in report.failure_reasons() {
+            println!("   ❌ {}", reason);
+        }
+        println!("   - Trusted: {}", cert.is_valid);
+        println!("🪵 Certificate Transparency: {} SCTs, {} verified", ct_report.scts.len(), ct_report.valid_count());
+        for failure in &ct_report.malformed {
+            println!("   ❌ Malformed {}", failure);
+        }
+        for log in ct_report.logs() {
+            println!("   - Logged in: {}", log);
         }
-    }
 
-    /// Basic domain validation fallback
-    fn validate_domain_basic(&self, domain: &str) -> (&str, bool) {
-        // Check for well-known trusted domains
-        let trusted_domains = [
-            "github.com", "google.com", "microsoft.com", "apple.com", 
-            "amazon.com", "facebook.com", "twitter.com", "linkedin.com",
-            "stackoverflow.com", "reddit.com", "youtube.com"
-        ];
+        println!("🛡️ Revocation status: {:?} ({} sources checked)", revocation_report.status(), revocation_report.checks.len());
 
-        let is_trusted = trusted_domains.iter().any(|&trusted| domain.contains(trusted));
-        
-        if is_trusted {
-            ("Let's Encrypt / Trusted CA", true)
-        } else {
-            ("Unknown CA", false)
+        cert.chain_validation = Some(report);
+        cert.certificate_transparency = Some(ct_report);
+        cert.revocation = Some(revocation_report);
+
+        // Only certificates the domain served become history; replays, placeholders and files would mask churn
+        if let Some(history) = &self.certificate_history {
+            if provenance == CertificateProvenance::Observed {
+                cert.history_anomalies = history.lock().unwrap().record(domain, CertificateObservation::from_certificate(&cert));
+                for anomaly in &cert.history_anomalies {
+                    println!("🚨 Certificate history anomaly: {}", anomaly);
+                }
+            }
         }
+        Ok(cert)
     }
 
-    /// Analyze real transparency data from GitHub API
+    /// Analyze real transparency data from the project's source repository and website
     pub async fn analyze_transparency(&self, domain: &str) -> Result<RealTransparencyData> {
         println!("📊 Analyzing real transparency data for: {}", domain);
         
-        let mut has_public_github = false;
+        let mut has_public_repository = false;
         let mut github_stars = 0u32;
         let mut github_forks = 0u32;
         let mut last_commit = None;
         let mut license = None;
         let mut code_review_score = 0u8;
+        let mut repository_health = None;
+        let mut repository_origin = None;
+        let mut commit_signing = None;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+        // Try to find the project's source repository
+        let (source_repository, linked_from_site) = match self.find_source_repository(domain).await? {
+            Some((repo, linked)) => (Some(repo), linked),
+            None => (None, false),
+        };
 
-        // Try to find GitHub repository
-        let github_repo = self.find_github_repository(domain).await?;
+        // The repository's owner first, then organizations named after the domain
+        let repo_owner = source_repository.as_ref()
+            .filter(|repo| repo.forge == ForgeKind::GitHub)
+            .and_then(|repo| repo.path.split('/').next())
+            .map(str::to_string);
+        let owners: Vec<String> = repo_owner.iter().cloned().chain(github_username_candidates(domain)).collect();
+        let github_organization = match github_org::resolve_organization(&self.github, domain, &owners, now).await {
+            Ok(organization) => organization,
+            Err(e) => {
+                println!("⚠️ GitHub organization lookup failed for {}: {}", domain, e);
+                None
+            }
+        };
+        if let Some(organization) = &github_organization {
+            println!("🏢 GitHub organization {}", organization);
+        }
+        // An organization GitHub verified for the domain outranks a repository found by guessing,
+        // but never the repository the site itself links to
+        let source_repository = match &github_organization {
+            Some(organization) if organization.link == OrganizationLink::VerifiedDomain
+                && !linked_from_site
+                && repo_owner.as_deref().is_none_or(|owner| !owner.eq_ignore_ascii_case(&organization.login)) =>
+            {
+                organization.main_repository.as_deref().map(SourceRepository::github).or(source_repository)
+            }
+            _ => source_repository,
+        };
         
-        if let Some(repo) = github_repo {
-            has_public_github = true;
+        if let Some(repo) = &source_repository {
+            has_public_repository = true;
             
-            // Fetch repository details
-            if let Ok(repo_data) = self.fetch_github_repo_data(&repo).await {
-                github_stars = repo_data.stargazers_count;
-                github_forks = repo_data.forks_count;
-                last_commit = repo_data.updated_at.map(|t| t as u64);
-                license = repo_data.license.as_ref().map(|l| l.name.clone());
-                
-                // Calculate code review score based on various factors
-                code_review_score = self.calculate_code_review_score(&repo_data);
+            // Fetch repository details and activity
+            let health = match repo.forge {
+                ForgeKind::GitHub => repo_health::fetch_repository_health(&self.github, &repo.path, now).await,
+                _ => self.forges.fetch_repository_health(repo, now).await,
+            };
+            match health {
+                Ok(health) => {
+                    github_stars = health.stars;
+                    github_forks = health.forks;
+                    last_commit = health.pushed_at;
+                    license = health.license.clone();
+                    code_review_score = health.code_review_score();
+                    println!("🩺 Code review score {}/100 for {}:", code_review_score, health.repository);
+                    for component in health.score_components() {
+                        println!("   - {}", component);
+                    }
+                    // Tree comparison and signature verification use GitHub-only endpoints
+                    if repo.forge == ForgeKind::GitHub {
+                        if self.upstream_corpus.is_empty() {
+                            println!("ℹ️ No upstream corpus loaded (--upstream-corpus); copied code cannot be detected");
+                        }
+                        match clone_detection::detect_origin(&self.github, &self.upstream_corpus, &health).await {
+                            Ok(report) => {
+                                println!("🧬 {}", report);
+                                repository_origin = Some(report);
+                            }
+                            Err(e) => println!("⚠️ Could not determine where {} came from: {}", health.repository, e),
+                        }
+                        match commit_signing::fetch_signing_report(&self.github, &health.repository).await {
+                            Ok(report) => {
+                                println!("🔏 Signatures in {}", report);
+                                commit_signing = Some(report);
+                            }
+                            Err(e) => println!("⚠️ Could not check signatures in {}: {}", health.repository, e),
+                        }
+                    }
+                    repository_health = Some(health);
+                }
+                Err(e) => println!("⚠️ Could not fetch repository {}: {}", repo, e),
             }
         }
 
-        // Check for other transparency indicators
-        let has_documented_roadmap = self.check_for_roadmap(domain).await?;
-        let has_audit_reports = self.check_for_audit_reports(domain).await?;
-        let has_team_verification = self.check_for_team_verification(domain).await?;
-        let has_token_economics = self.check_for_token_economics(domain).await?;
+        // Crawl the site for transparency indicators
+        println!("🕷️ Crawling {} (depth {}, up to {} pages)", domain, self.crawl_config.max_depth, self.crawl_config.max_pages);
+        let crawl = crawler::crawl(&format!("https://{}/", domain), &self.crawl_config).await;
+        match &crawl.front_page {
+            Some(page) => println!("📄 Front page \"{}\": {} headings, {} internal / {} external links{}",
+                page.title.as_deref().unwrap_or("untitled"),
+                page.headings().count(), page.internal_links.len(), page.external_links.len(),
+                page.description().map(|d| format!(" - {}", d)).unwrap_or_default()),
+            None => println!("⚠️ Could not fetch front page of {}", domain),
+        }
+        println!("🕷️ Crawled {} pages, {} disallowed by robots.txt", crawl.pages_visited.len(), crawl.disallowed.len());
+        let has_documented_roadmap = crawl.indicators.contains_key(&Indicator::Roadmap);
+        let has_audit_reports = crawl.indicators.contains_key(&Indicator::AuditReports);
+        let has_team_verification = crawl.indicators.contains_key(&Indicator::TeamInformation);
+        let has_token_economics = crawl.indicators.contains_key(&Indicator::TokenEconomics);
+
+        let mut audit_reports = audit_registry::verify_audit_documents(&self.audit_registry, domain, &crawl.audit_documents).await;
+        if let (true, Some(location)) = (audit_reports.is_empty(), crawl.indicators.get(&Indicator::AuditReports)) {
+            // An audits section without any report to back it up
+            audit_reports.push(AuditReportCheck { url: location.clone(), sha256: None, status: AuditStatus::ClaimedButMissing });
+        }
+        for check in &audit_reports {
+            println!("🛡️ Audit {}", check);
+        }
+
+        let mut team_members = match crawl.indicators.get(&Indicator::TeamInformation) {
+            Some(location) => team::fetch_team(location).await.unwrap_or_else(|e| {
+                println!("⚠️ Could not read team page {}: {}", location, e);
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+        let repo_path = source_repository.as_ref()
+            .filter(|repo| repo.forge == ForgeKind::GitHub)
+            .map(|repo| repo.path.as_str());
+        if let Err(e) = team::verify_members(&self.github, repo_path, &mut team_members, now).await {
+            println!("⚠️ GitHub lookups for team members failed: {}", e);
+        }
+        for member in &team_members {
+            println!("👤 {}", member);
+        }
+
+        if let Err(e) = self.github.save_cache().await {
+            println!("⚠️ Could not save GitHub cache: {}", e);
+        }
+
+        let tokenomics = match crawl.indicators.get(&Indicator::TokenEconomics) {
+            Some(location) => match tokenomics::fetch_tokenomics(location).await {
+                Ok(distribution) => {
+                    println!("🪙 Tokenomics from {}: {}", distribution.source, distribution);
+                    for problem in distribution.problems() {
+                        println!("   ⚠️ {}", problem);
+                    }
+                    Some(distribution)
+                }
+                Err(e) => {
+                    println!("⚠️ Could not read tokenomics {}: {}", location, e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let security_headers = match security_headers::fetch_security_headers(&format!("https://{}/", domain)).await {
+            Ok(report) => {
+                println!("🧱 Security headers: {} missing, {} weak, {} informational not set", report.missing().len(), report.weak().len(), report.informational().len());
+                Some(report)
+            }
+            Err(e) => {
+                println!("⚠️ Could not fetch security headers for {}: {}", domain, e);
+                None
+            }
+        };
+
+        let security_txt = match security_txt::fetch_security_txt(&format!("https://{}/", domain)).await {
+            Ok(file) => {
+                println!("🔐 security.txt: {} contacts ({} reachable), signature {:?}",
+                    file.contact.len(), file.reachable_contacts.len(), file.signature);
+                Some(file)
+            }
+            Err(e) => {
+                println!("⚠️ No usable security.txt for {}: {}", domain, e);
+                None
+            }
+        };
+
+        let scam_language = crawl.front_page.as_ref().map(|page| {
+            let report = self.scam_lexicon.scan(&page.url, &page.visible_text);
+            println!("🗣️ Scam language score {}/{}: {:?}", report.score, scam_language::MAX_SCAM_SCORE, report.phrases());
+            report
+        });
+
+        let dns = match dns::analyze_domain(&self.resolver(), domain).await {
+            Ok(report) => {
+                println!("🧭 DNS: {} A, {} AAAA, {} MX, {} CAA records, DNSSEC {:?}",
+                    report.a.len(), report.aaaa.len(), report.mx.len(), report.caa.len(), report.dnssec);
+                for (record, error) in &report.lookup_errors {
+                    println!("   ⚠️ {} lookup failed: {}", record, error);
+                }
+                Some(report)
+            }
+            Err(e) => {
+                println!("⚠️ DNS analysis failed for {}: {}", domain, e);
+                None
+            }
+        };
+
+        let registration = match rdap::lookup_domain(&self.rdap_base_url, domain).await {
+            Ok(registration) if registration.hosting_platform => {
+                println!("📅 {} is hosted on a platform registered via {}; its age says nothing about the site",
+                    domain, registration.registrar.as_deref().unwrap_or("unknown registrar"));
+                Some(registration)
+            }
+            Ok(registration) => {
+                println!("📅 Domain registered {} days ago via {}",
+                    registration.age_days(now).map(|d| d.to_string()).unwrap_or_else(|| "?".to_string()),
+                    registration.registrar.as_deref().unwrap_or("unknown registrar"));
+                Some(registration)
+            }
+            Err(e) => {
+                println!("⚠️ RDAP lookup failed for {}: {}", domain, e);
+                None
+            }
+        };
 
         Ok(RealTransparencyData {
             domain: domain.to_string(),
-            has_public_github,
+            has_public_repository,
             has_documented_roadmap,
             has_audit_reports,
             has_team_verification,
@@ -219,55 +904,68 @@ impl RealZkTlsVerifier {
             github_forks,
             last_commit,
             license,
+            security_headers,
+            dns,
+            registration,
+            // Needs no lookups; filled in for offline runs too by build_witness_data
+            impersonation: Vec::new(),
+            scam_language,
+            indicator_sources: crawl.indicators,
+            security_txt,
+            audit_reports,
+            team_members,
+            tokenomics,
+            source_repository,
+            repository_health,
+            repository_origin,
+            commit_signing,
+            github_organization,
+            supply_chain_sensitive: self.supply_chain_sensitive,
         })
     }
 
-    /// Find GitHub repository associated with domain using multiple strategies
-    async fn find_github_repository(&self, domain: &str) -> Result<Option<String>> {
-        println!("🔍 Finding GitHub repository for: {}", domain);
+    /// Find the source repository associated with domain using multiple strategies
+    /// The flag is set when the site links the repository rather than it being guessed
+    async fn find_source_repository(&self, domain: &str) -> Result<Option<(SourceRepository, bool)>> {
+        println!("🔍 Finding source repository for: {}", domain);
         
-        // Strategy 1: Check domain's main page for GitHub links
-        if let Some(repo) = self.find_github_in_page(domain).await? {
-            println!("✅ Found GitHub repo in page content: {}", repo);
-            return Ok(Some(repo));
+        // Strategy 1: Check domain's main page for links to any forge
+        if let Some(repo) = self.find_repository_in_page(domain).await? {
+            println!("✅ Found repo in page content: {}", repo);
+            return Ok(Some((repo, true)));
         }
 
         // Strategy 2: Try common GitHub username patterns
-        let username_patterns = vec![
-            domain.replace(".com", "").replace(".", "-"),
-            domain.replace(".com", "").replace(".", ""),
-            domain.split('.').next().unwrap_or(domain).to_string(),
-        ];
-
-        for username in username_patterns {
+        for username in github_username_candidates(domain) {
             // Try common repository name patterns
             let repo_patterns = vec![
-                format!("github.com/{}/{}", username, username),
-                format!("github.com/{}/{}", username, "main"),
-                format!("github.com/{}/{}", username, "website"),
-                format!("github.com/{}/{}", username, "site"),
+                format!("{}/{}", username, username),
+                format!("{}/{}", username, "main"),
+                format!("{}/{}", username, "website"),
+                format!("{}/{}", username, "site"),
             ];
 
             for pattern in repo_patterns {
-                if self.github_repo_exists(&pattern).await? {
-                    println!("✅ Found GitHub repo with pattern matching: {}", pattern);
-                    return Ok(Some(pattern));
+                let repo = SourceRepository::github(&pattern);
+                if self.repo_exists(&repo).await? {
+                    println!("✅ Found GitHub repo with pattern matching: {}", repo);
+                    return Ok(Some((repo, false)));
                 }
             }
         }
 
-        // Strategy 3: Check if domain is GitHub Pages
+        // Strategy 3: Check if domain is GitHub Pages; the site is served from that repository
         if let Some(repo) = self.check_github_pages(domain).await? {
             println!("✅ Found GitHub Pages repo: {}", repo);
-            return Ok(Some(repo));
+            return Ok(Some((repo, true)));
         }
 
-        println!("❌ No GitHub repository found for: {}", domain);
+        println!("❌ No source repository found for: {}", domain);
         Ok(None)
     }
 
-    /// Search for GitHub links in the domain's main page
-    async fn find_github_in_page(&self, domain: &str) -> Result<Option<String>> {
+    /// Search for GitHub, GitLab, Codeberg and Bitbucket links in the domain's main page
+    async fn find_repository_in_page(&self, domain: &str) -> Result<Option<SourceRepository>> {
         let url = format!("https://{}", domain);
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(10))
@@ -278,11 +976,8 @@ impl RealZkTlsVerifier {
                 if response.status().is_success() {
                     match response.text().await {
                         Ok(html) => {
-                            // Use regex to find GitHub repository URLs
-                            let github_regex = Regex::new(r"github\.com/([a-zA-Z0-9_-]+)/([a-zA-Z0-9_-]+)")?;
-                            for cap in github_regex.captures_iter(&html) {
-                                let repo = format!("github.com/{}/{}", &cap[1], &cap[2]);
-                                if self.github_repo_exists(&repo).await? {
+                            for repo in self.forges.repository_links(&html) {
+                                if self.repo_exists(&repo).await? {
                                     return Ok(Some(repo));
                                 }
                             }
@@ -299,288 +994,104 @@ impl RealZkTlsVerifier {
     }
 
     /// Check if domain is hosted on GitHub Pages
-    async fn check_github_pages(&self, domain: &str) -> Result<Option<String>> {
+    async fn check_github_pages(&self, domain: &str) -> Result<Option<SourceRepository>> {
         // GitHub Pages domains often have a pattern like username.github.io
         if domain.ends_with(".github.io") {
             let username = domain.replace(".github.io", "");
-            let repo = format!("github.com/{}/{}", username, username);
-            if self.github_repo_exists(&repo).await? {
+            let repo = SourceRepository::github(&format!("{}/{}", username, username));
+            if self.repo_exists(&repo).await? {
                 return Ok(Some(repo));
             }
         }
 
-        // Check CNAME record or other GitHub Pages indicators
-        // This would require DNS lookups, but for now we'll skip it
-        Ok(None)
-    }
-
-    async fn github_repo_exists(&self, repo_path: &str) -> Result<bool> {
-        let client = reqwest::Client::new();
-        let mut headers = reqwest::header::HeaderMap::new();
-        
-        if let Some(token) = &self.github_token {
-            headers.insert("Authorization", format!("Bearer {}", token).parse()?);
+        // Custom domains on GitHub Pages are a CNAME to <user>.github.io
+        let cname = match self.resolver().query(domain, dns::TYPE_CNAME).await {
+            Ok(response) => response.answers.into_iter().find_map(|r| match r {
+                RecordData::Cname(target) => Some(target),
+                _ => None,
+            }),
+            Err(_) => None,
+        };
+        if let Some(user) = cname.as_deref().and_then(|t| t.trim_end_matches('.').strip_suffix(".github.io")) {
+            for path in [format!("{}/{}.github.io", user, user), format!("{}/{}", user, domain)] {
+                let repo = SourceRepository::github(&path);
+                if self.repo_exists(&repo).await? {
+                    return Ok(Some(repo));
+                }
+            }
         }
-
-        let response = client
-            .get(&format!("https://api.github.com/repos/{}", repo_path))
-            .headers(headers)
-            .send()
-            .await?;
-
-        Ok(response.status().is_success())
+        Ok(None)
     }
 
-    async fn fetch_github_repo_data(&self, repo: &str) -> Result<GitHubRepoData> {
-        let client = reqwest::Client::new();
-        let mut headers = reqwest::header::HeaderMap::new();
-        
-        if let Some(token) = &self.github_token {
-            headers.insert("Authorization", format!("Bearer {}", token).parse()?);
+    async fn repo_exists(&self, repo: &SourceRepository) -> Result<bool> {
+        match repo.forge {
+            ForgeKind::GitHub => self.github.repo_exists(&repo.path).await,
+            _ => self.forges.repo_exists(repo).await,
         }
-
-        let response = client
-            .get(&format!("https://api.github.com/repos/{}", repo))
-            .headers(headers)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!("Failed to fetch GitHub repo data"));
-        }
-
-        let repo_data: GitHubRepoData = response.json().await?;
-        Ok(repo_data)
     }
+}
 
-    fn calculate_code_review_score(&self, repo_data: &GitHubRepoData) -> u8 {
-        let mut score = 0u8;
-        
-        // Base score for having a repository
-        score += 20;
-        
-        // Stars indicate popularity
-        if repo_data.stargazers_count > 1000 { score += 20; }
-        else if repo_data.stargazers_count > 100 { score += 15; }
-        else if repo_data.stargazers_count > 10 { score += 10; }
-        
-        // Forks indicate community engagement
-        if repo_data.forks_count > 100 { score += 15; }
-        else if repo_data.forks_count > 10 { score += 10; }
-        else if repo_data.forks_count > 1 { score += 5; }
-        
-        // Recent activity
-        if let Some(updated) = repo_data.updated_at {
-            let days_since_update = (SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs() - updated) / (24 * 60 * 60);
-            
-            if days_since_update < 30 { score += 15; }
-            else if days_since_update < 90 { score += 10; }
-            else if days_since_update < 365 { score += 5; }
-        }
-        
-        // License indicates openness
-        if repo_data.license.is_some() { score += 10; }
-        
-        // Description indicates documentation
-        if repo_data.description.is_some() && !repo_data.description.as_ref().unwrap().is_empty() {
-            score += 10;
-        }
-        
-        score.min(100)
-    }
+impl RealZkTlsVerifier {
+    /// Generate real witness data from actual TLS certificate and transparency analysis
+    pub async fn generate_real_witness_data(
+        &self,
+        domain: &str,
+    ) -> Result<RealWitnessData> {
+        println!("🔧 Generating real witness data for: {}", domain);
 
-    async fn check_for_roadmap(&self, domain: &str) -> Result<bool> {
-        println!("🔍 Checking for roadmap documentation on: {}", domain);
+        // Fetch real TLS certificate
+        let cert = self.fetch_tls_certificate(domain).await?;
         
-        let roadmap_paths = [
-            "/roadmap",
-            "/docs/roadmap", 
-            "/about/roadmap",
-            "/plan",
-            "/milestones",
-            "/timeline"
-        ];
-
-        for path in &roadmap_paths {
-            let url = format!("https://{}{}", domain, path);
-            if self.check_url_exists(&url).await? {
-                println!("✅ Found roadmap at: {}", url);
-                return Ok(true);
-            }
-        }
-
-        // Also check main page for roadmap keywords
-        if self.check_page_for_keywords(domain, &["roadmap", "milestone", "timeline", "development plan"]).await? {
-            println!("✅ Found roadmap keywords on main page");
-            return Ok(true);
-        }
+        // Analyze real transparency data
+        let transparency = self.transparency_for(domain).await?;
 
-        Ok(false)
+        Ok(self.build_witness_data(domain, cert, transparency))
     }
 
-    async fn check_for_audit_reports(&self, domain: &str) -> Result<bool> {
-        println!("🔍 Checking for audit reports on: {}", domain);
-        
-        let audit_paths = [
-            "/audit",
-            "/security/audit",
-            "/audits", 
-            "/security",
-            "/docs/security",
-            "/reports/audit"
-        ];
-
-        for path in &audit_paths {
-            let url = format!("https://{}{}", domain, path);
-            if self.check_url_exists(&url).await? {
-                println!("✅ Found audit reports at: {}", url);
-                return Ok(true);
-            }
-        }
-
-        // Check for audit keywords on main page
-        if self.check_page_for_keywords(domain, &["audit", "security audit", "certik", "consensys", "quantstamp"]).await? {
-            println!("✅ Found audit keywords on main page");
-            return Ok(true);
-        }
+    /// Generate witness data from a certificate or chain file, e.g. one captured from a site that is gone
+    pub async fn generate_witness_data_from_file(
+        &self,
+        path: &str,
+        domain: Option<&str>,
+    ) -> Result<RealWitnessData> {
+        let cert = self.load_certificate_file(path, domain)?;
+        // A wildcard certificate is analyzed as the domain it covers subdomains of
+        let domain = cert.domain.trim_start_matches("*.").to_string();
+        println!("🔧 Generating witness data for {} from {}", domain, path);
 
-        Ok(false)
+        let transparency = self.transparency_for(&domain).await?;
+        Ok(self.build_witness_data(&domain, cert, transparency))
     }
 
-    async fn check_for_team_verification(&self, domain: &str) -> Result<bool> {
-        println!("🔍 Checking for team verification on: {}", domain);
-        
-        let team_paths = [
-            "/team",
-            "/about/team",
-            "/about",
-            "/leadership",
-            "/founders",
-            "/staff"
-        ];
-
-        for path in &team_paths {
-            let url = format!("https://{}{}", domain, path);
-            if self.check_url_exists(&url).await? {
-                // Check if the page contains team information
-                if self.check_page_for_keywords(&url, &["team", "founder", "ceo", "cto", "developer", "engineer"]).await? {
-                    println!("✅ Found team information at: {}", url);
-                    return Ok(true);
-                }
-            }
+    async fn transparency_for(&self, domain: &str) -> Result<RealTransparencyData> {
+        if self.offline {
+            println!("📴 Offline mode: skipping transparency lookups for {}", domain);
+            return Ok(RealTransparencyData::unavailable(domain));
         }
-
-        Ok(false)
+        self.analyze_transparency(domain).await
     }
 
-    async fn check_for_token_economics(&self, domain: &str) -> Result<bool> {
-        println!("🔍 Checking for token economics on: {}", domain);
-        
-        let token_paths = [
-            "/tokenomics",
-            "/token-economics", 
-            "/economics",
-            "/whitepaper",
-            "/token",
-            "/docs/tokenomics"
-        ];
-
-        for path in &token_paths {
-            let url = format!("https://{}{}", domain, path);
-            if self.check_url_exists(&url).await? {
-                println!("✅ Found token economics at: {}", url);
-                return Ok(true);
+    fn build_witness_data(
+        &self,
+        domain: &str,
+        cert: RealTlsCertificate,
+        mut transparency: RealTransparencyData,
+    ) -> RealWitnessData {
+        // CAA can only be judged against a certificate that was really issued, and a policy that was really read
+        if let Some(dns) = transparency.dns.as_mut() {
+            if cert.provenance != CertificateProvenance::Fallback && !dns.lookup_failed("CAA") {
+                let wildcard = cert.subject_alt_names.iter().any(|name| name.starts_with("*."));
+                let authorization = dns::check_caa(&dns.caa, &cert.issuer, wildcard);
+                println!("🏷️ CAA check for {}: {}", cert.issuer, authorization);
+                dns.issuer_authorization = Some(authorization);
             }
         }
 
-        // Check for token keywords on main page
-        if self.check_page_for_keywords(domain, &["token", "tokenomics", "economics", "whitepaper", "utility token"]).await? {
-            println!("✅ Found token economics keywords on main page");
-            return Ok(true);
-        }
-
-        Ok(false)
-    }
-
-    /// Check if a URL exists and returns a 200 status
-    async fn check_url_exists(&self, url: &str) -> Result<bool> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()?;
-
-        match client.head(url).send().await {
-            Ok(response) => Ok(response.status().is_success()),
-            Err(_) => Ok(false),
+        transparency.impersonation = self.impersonation.check(domain);
+        for finding in &transparency.impersonation {
+            println!("🎭 Possible impersonation: {}", finding);
         }
-    }
 
-    /// Check if a page contains specific keywords
-    async fn check_page_for_keywords(&self, url_or_domain: &str, keywords: &[&str]) -> Result<bool> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()?;
-
-        // If it's just a domain, try the main page
-        let url = if url_or_domain.starts_with("http") {
-            url_or_domain.to_string()
-        } else {
-            format!("https://{}", url_or_domain)
-        };
-
-        match client.get(&url).send().await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.text().await {
-                        Ok(html) => {
-                            let lowercase_html = html.to_lowercase();
-                            let found_keywords = keywords.iter()
-                                .filter(|&&keyword| lowercase_html.contains(&keyword.to_lowercase()))
-                                .count();
-                            
-                            Ok(found_keywords > 0)
-                        }
-                        Err(_) => Ok(false),
-                    }
-                } else {
-                    Ok(false)
-                }
-            }
-            Err(_) => Ok(false),
-        }
-    }
-}
-
-#[derive(Debug, Deserialize)]
-struct GitHubRepoData {
-    description: Option<String>,
-    stargazers_count: u32,
-    forks_count: u32,
-    updated_at: Option<u64>,
-    license: Option<GitHubLicense>,
-}
-
-#[derive(Debug, Deserialize)]
-struct GitHubLicense {
-    name: String,
-}
-
-impl RealZkTlsVerifier {
-    /// Generate real witness data from actual TLS certificate and transparency analysis
-    pub async fn generate_real_witness_data(
-        &self,
-        domain: &str,
-    ) -> Result<RealWitnessData> {
-        println!("🔧 Generating real witness data for: {}", domain);
-
-        // Fetch real TLS certificate
-        let cert = self.fetch_tls_certificate(domain).await?;
-        
-        // Analyze real transparency data
-        let transparency = self.analyze_transparency(domain).await?;
-        
         // Calculate real transparency score and risk level
         let (transparency_score, risk_level) = self.calculate_real_scores(&transparency, &cert);
         
@@ -608,8 +1119,8 @@ impl RealZkTlsVerifier {
         
         // Pad certificate serial to 32 bytes if needed
         let mut padded_serial = [0u8; 32];
-        for i in 0..32 {
-            padded_serial[i] = cert.serial_number.get(i).copied().unwrap_or(0);
+        for (i, byte) in padded_serial.iter_mut().enumerate() {
+            *byte = cert.serial_number.get(i).copied().unwrap_or(0);
         }
         
         let mut cert_validity_hash = [0u8; 32];
@@ -619,7 +1130,7 @@ impl RealZkTlsVerifier {
                 ^ public_key_hash_bytes.get(i).copied().unwrap_or(0);
         }
 
-        Ok(RealWitnessData {
+        RealWitnessData {
             domain_hash: domain_hash.to_vec(),
             certificate_validity_hash: cert_validity_hash.to_vec(),
             transparency_score,
@@ -633,15 +1144,19 @@ impl RealZkTlsVerifier {
             salt: salt.to_vec(),
             real_certificate: cert,
             real_transparency: transparency,
-        })
+        }
     }
 
     fn calculate_real_scores(&self, transparency: &RealTransparencyData, cert: &RealTlsCertificate) -> (u32, u8) {
         let mut transparency_score = 0u32;
         let mut risk_level = 0u8;
+        // A github.io page is as young as its author's first commit, not as old as github.io
+        let domain_age_days = transparency.registration.as_ref()
+            .filter(|r| !r.hosting_platform)
+            .and_then(|r| r.age_days(cert.verification_timestamp));
 
         // Check if this is a well-established company
-        let is_established_company = self.is_established_company(&transparency.domain);
+        let is_established_company = impersonation::is_established(&transparency.domain);
         
         if is_established_company {
             // For established companies, give high transparency score and low risk
@@ -649,35 +1164,215 @@ impl RealZkTlsVerifier {
             risk_level = 1; // Very low risk for established companies
             
             // Add bonus points for having additional transparency features
-            if transparency.has_public_github { transparency_score += 5; }
+            if transparency.has_public_repository { transparency_score += 5; }
             if transparency.has_team_verification { transparency_score += 5; }
-            if transparency.has_audit_reports { transparency_score += 5; }
+            if transparency.audit_reports.iter().any(|c| matches!(c.status, AuditStatus::Verified { .. })) {
+                transparency_score += 5;
+            }
+            if transparency.security_txt.as_ref().is_some_and(|f| f.is_disclosure_channel(cert.verification_timestamp)) {
+                transparency_score += 5;
+            }
             
             // Only penalize for serious issues
             if !cert.is_valid { 
                 risk_level += 3; 
                 transparency_score -= 15;
             }
+            if let Some(ct) = &cert.certificate_transparency {
+                if ct.has_invalid() { risk_level += 3; }
+                else if ct.scts.is_empty() { risk_level += 2; }
+            }
+            // Certificate churn is a takeover signal even for well-known names
+            risk_level += 2 * cert.history_anomalies.len().min(3) as u8;
+            if let Some(tls) = &cert.tls_configuration {
+                risk_level += tls.weaknesses().len().min(3) as u8;
+            }
+            if let Some(dns) = &transparency.dns {
+                if matches!(dns.issuer_authorization, Some(CaaAuthorization::Forbidden { .. })) { risk_level += 3; }
+            }
+            // An "established" name registered last month is not what it claims to be
+            if domain_age_days.is_some_and(|days| days < 30) { risk_level += 4; }
         } else {
             // For newer/unknown companies, use the original scoring system
-            if transparency.has_public_github { transparency_score += 25; }
+            // Original development earns the full credit; forks, template instances and copies do not
+            if transparency.has_public_repository {
+                transparency_score += match transparency.repository_origin.as_ref().map(|r| &r.origin) {
+                    None | Some(RepositoryOrigin::Original) => 25,
+                    Some(RepositoryOrigin::FromTemplate { .. }) => 15,
+                    Some(RepositoryOrigin::Fork { .. }) => 10,
+                    Some(RepositoryOrigin::Copy { .. }) => 0,
+                };
+            }
+            // GitHub's domain verification ties the code to this site; a matching website field less so
+            transparency_score += match transparency.github_organization.as_ref().map(|o| o.link) {
+                Some(OrganizationLink::VerifiedDomain) => 10,
+                Some(OrganizationLink::Website) => 5,
+                _ => 0,
+            };
             if transparency.has_documented_roadmap { transparency_score += 20; }
-            if transparency.has_audit_reports { transparency_score += 25; }
-            if transparency.has_team_verification { transparency_score += 15; }
-            if transparency.has_token_economics { transparency_score += 15; }
+            // Audits count as far as the auditor registry confirms them; a badge alone earns nothing
+            transparency_score += transparency.audit_reports.iter()
+                .map(|check| match check.status {
+                    AuditStatus::Verified { .. } => 25,
+                    // The firm published it and names this project, but the registry has not confirmed it
+                    AuditStatus::AuditorSiteUnregistered { names_project: true, .. } => 15,
+                    AuditStatus::AuditorSiteUnregistered { names_project: false, .. } => 0,
+                    AuditStatus::SelfHostedUnverified => 10,
+                    AuditStatus::OtherProject { .. } | AuditStatus::ClaimedButMissing => 0,
+                })
+                .max()
+                .unwrap_or(0);
+            // Team: the best-verified member counts; only a history of commits to the repository earns more
+            // than linked profiles, since anyone can link an old GitHub account they do not own
+            transparency_score += transparency.team_members.iter()
+                .map(|member| match &member.level {
+                    level if level.is_established_contributor() => 15,
+                    // A fresh account with a token commit is no better than a fresh account
+                    VerificationLevel::Contributor { .. } | VerificationLevel::GitHubAccount { .. } | VerificationLevel::ProfilesLinked => 5,
+                    VerificationLevel::NameOnly => 2,
+                    VerificationLevel::GitHubNotFound { .. } => 0,
+                })
+                .max()
+                .unwrap_or(0);
+            // Tokenomics: full credit only for a stated supply and a consistent, vested distribution
+            transparency_score += match &transparency.tokenomics {
+                Some(distribution) if distribution.is_complete() => 15,
+                Some(distribution) if distribution.total_supply.is_some() || !distribution.allocations.is_empty() => 8,
+                _ if transparency.has_token_economics => 3,
+                _ => 0,
+            };
             
-            // GitHub-specific scoring
-            transparency_score += (transparency.github_stars / 100).min(10) as u32;
+            // GitHub-specific scoring; supply-chain reviews care more about signed releases than stars
+            let release_weight = if self.supply_chain_sensitive { 2 } else { 1 };
+            transparency_score += (transparency.github_stars / 100).min(10 / release_weight);
             transparency_score += transparency.code_review_score as u32 / 4;
 
             // Risk assessment for newer companies
             if !cert.is_valid { risk_level += 5; }
-            if transparency.github_stars == 0 && transparency.has_public_github { risk_level += 2; }
+            // Bitbucket has no stars, so no stars there says nothing
+            let forge_has_stars = transparency.source_repository.as_ref().is_none_or(|r| r.forge != ForgeKind::Bitbucket);
+            if transparency.github_stars == 0 && transparency.has_public_repository && forge_has_stars { risk_level += 2; }
             if transparency.code_review_score < 30 { risk_level += 3; }
             
             // Certificate validity period
-            let validity_days = (cert.not_after - cert.not_before) / (24 * 60 * 60);
+            let validity_days = cert.not_after.saturating_sub(cert.not_before) / (24 * 60 * 60);
             if validity_days < 30 { risk_level += 2; }
+
+            // Certificate Transparency: barely-logged certificates are a phishing signal
+            if let Some(ct) = &cert.certificate_transparency {
+                if ct.has_invalid() { risk_level += 3; }
+                else if ct.scts.is_empty() { risk_level += 2; }
+                else if ct.log_list.is_some() && ct.valid_count() < 2 { risk_level += 1; }
+            }
+
+            // Issuer switches, key reuse and rapid reissues against earlier observations
+            risk_level += 2 * cert.history_anomalies.len().min(3) as u8;
+
+            // Legacy protocols, short RSA keys and missing forward secrecy suggest neglected hosting
+            if let Some(tls) = &cert.tls_configuration {
+                for weakness in tls.weaknesses() {
+                    risk_level += match weakness {
+                        TlsWeakness::LegacyProtocol(_) => 1,
+                        TlsWeakness::WeakRsaKey(_) => 3,
+                        TlsWeakness::NoForwardSecrecy => 2,
+                        TlsWeakness::StaticRsaAccepted => 1,
+                    };
+                }
+            }
+
+            // DNS hygiene: an issuer the current CAA policy excludes suggests mis-issuance (or a policy changed
+            // since), mail authentication suggests a real operator; failed lookups prove nothing either way
+            if let Some(dns) = &transparency.dns {
+                if matches!(dns.issuer_authorization, Some(CaaAuthorization::Forbidden { .. })) { risk_level += 3; }
+                let mail_known = !dns.lookup_failed("TXT") && !dns.lookup_failed("DMARC");
+                if mail_known && !dns.has_spf && !dns.has_dmarc { risk_level += 1; }
+                if dns.dnssec == DnssecStatus::Validated { transparency_score += 5; }
+            }
+
+            // Domain age: freshly registered domains are the most common fraud indicator
+            match domain_age_days {
+                Some(days) if days < 30 => risk_level += 4,
+                Some(days) if days < 180 => risk_level += 2,
+                Some(days) if days >= 3 * 365 => transparency_score += 5,
+                _ => {}
+            }
+            if let Some(registration) = &transparency.registration {
+                if !registration.distress_statuses().is_empty() { risk_level += 2; }
+            }
+
+            // security.txt: a maintained disclosure channel, stronger when signed; a bad signature is tampering
+            if let Some(file) = &transparency.security_txt {
+                if file.is_disclosure_channel(cert.verification_timestamp) {
+                    transparency_score += 10;
+                    if matches!(file.signature, SignatureStatus::Verified { .. }) { transparency_score += 5; }
+                } else if file.is_expired(cert.verification_timestamp) {
+                    risk_level += 1;
+                }
+                if matches!(file.signature, SignatureStatus::Invalid { .. }) { risk_level += 2; }
+            }
+
+            // Another project's report passed off as this one's, or audits claimed without a report
+            if transparency.audit_reports.iter().any(|c| matches!(c.status, AuditStatus::OtherProject { .. })) {
+                risk_level += 3;
+            } else if !transparency.audit_reports.is_empty()
+                && transparency.audit_reports.iter().all(|c| c.status == AuditStatus::ClaimedButMissing)
+            {
+                risk_level += 1;
+            }
+
+            // Verified signatures on release tags tie published code to the maintainers' keys
+            if let Some(signing) = &transparency.commit_signing {
+                if signing.releases_signed() {
+                    transparency_score += 10 * release_weight;
+                } else if signing.release_tags.verified > 0 {
+                    transparency_score += 5 * release_weight;
+                } else if self.supply_chain_sensitive && signing.release_tags.checked > 0 {
+                    risk_level += 2;
+                }
+                transparency_score += match signing.commits.verified_ratio() {
+                    Some(ratio) if ratio >= 0.8 => 5,
+                    Some(ratio) if ratio >= 0.3 => 2,
+                    _ => 0,
+                };
+                if signing.release_tags.bad_signatures > 0 { risk_level += 2; }
+            }
+
+            // Someone else's code presented as the project's own
+            if transparency.repository_origin.as_ref().is_some_and(|r| matches!(r.origin, RepositoryOrigin::Copy { .. })) {
+                risk_level += 3;
+            }
+
+            // Team profiles pointing at GitHub accounts that do not exist were made up
+            if transparency.team_members.iter().any(|m| matches!(m.level, VerificationLevel::GitHubNotFound { .. })) {
+                risk_level += 2;
+            }
+
+            // Insiders holding much of the supply, free to sell on day one
+            if let Some(distribution) = &transparency.tokenomics {
+                for problem in distribution.problems() {
+                    risk_level += match problem {
+                        TokenomicsProblem::InsiderShareAboveLimit { .. } => 2,
+                        TokenomicsProblem::UnvestedInsiders { .. } => 2,
+                        TokenomicsProblem::AllocationsDoNotSum { .. } => 1,
+                    };
+                }
+            }
+
+            // Promises of guaranteed returns and pressure tactics on the site itself
+            if let Some(report) = &transparency.scam_language {
+                risk_level += report.score / 2;
+            }
+
+            // Look-alikes of known brands; only the strongest technique counts
+            risk_level += transparency.impersonation.iter()
+                .map(|finding| finding.technique.risk())
+                .max()
+                .unwrap_or(0);
+        }
+
+        // A certificate its CA has revoked outweighs everything else
+        if cert.revocation.as_ref().is_some_and(|r| r.revoked().is_some()) {
+            risk_level = 10;
         }
 
         transparency_score = transparency_score.min(100);
@@ -686,37 +1381,6 @@ impl RealZkTlsVerifier {
         (transparency_score, risk_level)
     }
 
-    /// Check if this is a well-established, trusted company
-    fn is_established_company(&self, domain: &str) -> bool {
-        let established_domains = [
-            // Tech Giants
-            "google.com", "microsoft.com", "apple.com", "amazon.com", "facebook.com", 
-            "twitter.com", "linkedin.com", "youtube.com", "instagram.com", "whatsapp.com",
-            "netflix.com", "spotify.com", "uber.com", "airbnb.com", "tesla.com",
-            
-            // Financial Institutions
-            "paypal.com", "stripe.com", "visa.com", "mastercard.com", "americanexpress.com",
-            "wellsfargo.com", "chase.com", "bankofamerica.com", "citibank.com",
-            
-            // E-commerce & Services
-            "ebay.com", "etsy.com", "shopify.com", "square.com", "zoom.us", "slack.com",
-            "dropbox.com", "salesforce.com", "adobe.com", "oracle.com", "ibm.com",
-            
-            // Media & Entertainment
-            "cnn.com", "bbc.com", "reuters.com", "bloomberg.com", "forbes.com",
-            "wikipedia.org", "reddit.com", "stackoverflow.com", "github.com",
-            
-            // Government & Education
-            "gov", "edu", "mil",
-        ];
-
-        established_domains.iter().any(|&established| {
-            domain.contains(established) || 
-            domain.ends_with(established) ||
-            established.ends_with(".") && domain.ends_with(&established[..established.len()-1])
-        })
-    }
-
     fn generate_salt(&self) -> [u8; 32] {
         use rand::RngCore;
         let mut salt = [0u8; 32];
@@ -758,7 +1422,127 @@ pub struct RealWitnessData {
 mod tests {
     use super::*;
 
+    /// The leaf fixture read as `domain`'s certificate on 2025-01-01
+    fn fixture_certificate(domain: &str) -> RealTlsCertificate {
+        let der = include_bytes!("../fixtures/leaf.der");
+        RealTlsCertificate::from_der(domain, der, CertificateProvenance::FromFile, true, 1735689600).unwrap()
+    }
+
+    /// Default verifier, the fixture certificate for example-project.com and a site where nothing was found
+    fn scoring_fixture() -> (RealZkTlsVerifier, RealTlsCertificate, RealTransparencyData) {
+        (RealZkTlsVerifier::new(None), fixture_certificate("example-project.com"), RealTransparencyData::unavailable("example-project.com"))
+    }
+
+    /// A registration `days_ago` days before the fixture certificate was read
+    fn registration(days_ago: u64, hosting_platform: bool) -> DomainRegistration {
+        DomainRegistration {
+            source: "fixture".to_string(),
+            registered_at: Some(1735689600 - days_ago * 24 * 60 * 60),
+            last_changed: None,
+            expires_at: None,
+            registrar: None,
+            status: Vec::new(),
+            hosting_platform,
+        }
+    }
+
+    /// The signed security.txt fixture with every contact reachable and its signature checked
+    fn signed_security_txt() -> SecurityTxt {
+        let signed = include_str!("../fixtures/security_txt_signed.txt");
+        let key = include_str!("../fixtures/security_txt_key.asc");
+        let mut file = SecurityTxt::parse("https://example-project.com/.well-known/security.txt", signed);
+        file.reachable_contacts = file.contact.clone();
+        file.signature = security_txt::verify_signature(signed, &[key.to_string()]);
+        file
+    }
+
+    /// The site's linked audit report, classified as `status`
+    fn audit(status: AuditStatus) -> AuditReportCheck {
+        AuditReportCheck { url: "https://example-project.com/audit.pdf".to_string(), sha256: None, status }
+    }
+
+    fn auditor_site(names_project: bool) -> AuditStatus {
+        AuditStatus::AuditorSiteUnregistered { auditor: "PoA Test Auditors".to_string(), names_project }
+    }
+
+    /// Grace and a ghost account from the team page, Grace verified to `grace` when given
+    fn team(grace: Option<VerificationLevel>) -> Vec<TeamMember> {
+        let page = r#"<div class="member"><h3>Grace Sample</h3><a href="https://github.com/gracesample">GitHub</a></div>
+            <div class="member"><h3>Ghost Account</h3><a href="https://github.com/ghost-account-404">GitHub</a></div>"#;
+        let mut members = team::extract_team("https://example-project.com/team", page);
+        if let Some(level) = grace {
+            members[0].level = level;
+        }
+        members
+    }
+
+    fn github_account(age_days: u64) -> VerificationLevel {
+        VerificationLevel::GitHubAccount { login: "gracesample".to_string(), age_days: Some(age_days) }
+    }
+
+    fn contributor(contributions: u32) -> VerificationLevel {
+        VerificationLevel::Contributor { login: "gracesample".to_string(), age_days: Some(20), contributions }
+    }
+
+    fn tokenomics(lines: &[&str]) -> TokenDistribution {
+        let lines: Vec<String> = lines.iter().map(|s| s.to_string()).collect();
+        TokenDistribution::extract("https://example-project.com/tokenomics", &lines)
+    }
+
+    /// A small example-project organization tied to the site by `link`
+    fn organization(link: OrganizationLink) -> OrganizationAnalysis {
+        OrganizationAnalysis {
+            login: "example-project".to_string(),
+            name: None,
+            website: Some("https://example-project.com".to_string()),
+            is_verified: false,
+            verified_domains: Some(Vec::new()),
+            link,
+            public_repos: 4,
+            public_members: Some(2),
+            repositories_analyzed: 4,
+            total_stars: 0,
+            total_forks: 0,
+            active_repos: 1,
+            archived_repos: 0,
+            languages: BTreeMap::new(),
+            pinned: None,
+            main_repository: None,
+        }
+    }
+
+    /// The site's public repository, scamswap/amm, found to be `origin`
+    fn repository_origin(transparency: &mut RealTransparencyData, origin: RepositoryOrigin) {
+        transparency.has_public_repository = true;
+        transparency.repository_origin = Some(OriginReport {
+            repository: "scamswap/amm".to_string(),
+            origin,
+            commits: Some(3),
+            files_compared: 10,
+        });
+    }
+
+    /// The site's public repository, example-project/protocol on `host`
+    fn source_repository(transparency: &mut RealTransparencyData, forge: ForgeKind, host: &str) {
+        transparency.has_public_repository = true;
+        transparency.source_repository = Some(SourceRepository {
+            forge,
+            host: host.to_string(),
+            path: "example-project/protocol".to_string(),
+        });
+    }
+
+    /// One finding about the site: `after` adds it on top of `before`, and the (score, risk) move by the deltas
+    struct ScoringCase {
+        signal: &'static str,
+        before: fn(&mut RealTransparencyData),
+        after: fn(&mut RealTransparencyData),
+        score: i32,
+        risk: i32,
+    }
+
     #[tokio::test]
+    #[ignore = "needs a TLS handshake with github.com; without network the fallback certificate is returned"]
     async fn test_fetch_tls_certificate() {
         let verifier = RealZkTlsVerifier::new(None);
         let result = verifier.fetch_tls_certificate("github.com").await;
@@ -766,10 +1550,418 @@ mod tests {
         
         let cert = result.unwrap();
         assert_eq!(cert.domain, "github.com");
+        assert_eq!(cert.provenance, CertificateProvenance::Observed);
         assert!(cert.is_valid);
         assert!(!cert.issuer.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_fallback_certificate_is_never_valid() {
+        let verifier = RealZkTlsVerifier::new(None);
+        let cert = verifier.fetch_tls_certificate("unreachable.invalid").await.unwrap();
+
+        assert_eq!(cert.provenance, CertificateProvenance::Fallback);
+        assert!(!cert.is_valid);
+        assert!(cert.presented_chain.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_strict_mode_rejects_unobserved_certificate() {
+        let verifier = RealZkTlsVerifier::new(None).with_strict_certificates(true);
+        let result = verifier.fetch_tls_certificate("https://unreachable.invalid/").await;
+
+        let message = format!("{:#}", result.unwrap_err());
+        assert!(message.contains("Strict mode"));
+        assert!(message.contains("unreachable.invalid"));
+    }
+
+    #[test]
+    fn test_certificate_from_der() {
+        let der = include_bytes!("../fixtures/leaf.der");
+        let cert = RealTlsCertificate::from_der(
+            "example-project.com",
+            der,
+            CertificateProvenance::FromFile,
+            true,
+            1704067200,
+        ).unwrap();
+
+        assert_eq!(cert.subject, "C=US, O=Example Project, CN=example-project.com");
+        assert_eq!(cert.issuer, "C=US, O=Proof of Anchor Test PKI, CN=PoA Test Intermediate CA");
+        assert_eq!(cert.serial_number, vec![0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f]);
+        assert_eq!(cert.not_before, 1704067200);
+        assert_eq!(cert.not_after, 4859740800);
+        assert_eq!(cert.subject_alt_names, vec!["example-project.com", "www.example-project.com"]);
+        // SubjectPublicKeyInfo is a DER SEQUENCE
+        assert_eq!(cert.public_key[0], 0x30);
+    }
+
+    #[tokio::test]
+    async fn test_witness_data_from_chain_file() {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+        let verifier = RealZkTlsVerifier::new(None)
+            .with_trust_store(&format!("{}/root.pem", fixtures))
+            .unwrap()
+            .with_offline(true);
+
+        let witness = verifier
+            .generate_witness_data_from_file(&format!("{}/chain.pem", fixtures), None)
+            .await
+            .unwrap();
+        let cert = &witness.real_certificate;
+        assert_eq!(cert.domain, "example-project.com");
+        assert_eq!(cert.provenance, CertificateProvenance::FromFile);
+        assert!(cert.is_valid);
+        assert_eq!(cert.presented_chain.len(), 2);
+        assert_eq!(cert.certificate_transparency.as_ref().unwrap().scts.len(), 1);
+        assert_eq!(witness.expiry_date, 4859740800);
+        assert_eq!(&witness.certificate_serial[..6], &[0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f]);
+        assert!(!witness.real_transparency.has_public_repository);
+    }
+
+    #[test]
+    fn test_certificate_file_without_issuer_is_untrusted() {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+        let verifier = RealZkTlsVerifier::new(None)
+            .with_trust_store(&format!("{}/root.pem", fixtures))
+            .unwrap();
+
+        let cert = verifier
+            .load_certificate_file(&format!("{}/leaf.der", fixtures), Some("www.example-project.com"))
+            .unwrap();
+        assert_eq!(cert.domain, "www.example-project.com");
+        assert_eq!(cert.provenance, CertificateProvenance::FromFile);
+        assert!(!cert.is_valid);
+    }
+
+    #[test]
+    fn test_wildcard_certificate_file_matches_its_own_name() {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+        let verifier = RealZkTlsVerifier::new(None);
+
+        let cert = verifier.load_certificate_file(&format!("{}/wildcard.pem", fixtures), None).unwrap();
+        assert_eq!(cert.domain, "*.example-project.com");
+        let failures = &cert.chain_validation.as_ref().unwrap().hops[0].failures;
+        assert!(!failures.iter().any(|f| matches!(f, ValidationFailure::NameMismatch { .. })), "{:?}", failures);
+    }
+
+    #[test]
+    fn test_revoked_certificate_file_is_scored_not_rejected() {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+        let verifier = RealZkTlsVerifier::new(None)
+            .with_trust_store(&format!("{}/root.pem", fixtures))
+            .unwrap()
+            .with_revocation_sources(Vec::new(), vec![format!("{}/ocsp_revoked.der", fixtures)]);
+
+        let cert = verifier.load_certificate_file(&format!("{}/chain.pem", fixtures), None).unwrap();
+        assert!(cert.revocation.as_ref().unwrap().revoked().is_some());
+        assert!(!cert.is_valid);
+        let transparency = RealTransparencyData::unavailable("example-project.com");
+        assert_eq!(verifier.calculate_real_scores(&transparency, &cert).1, 10);
+    }
+
+    #[test]
+    fn test_scoring_signals() {
+        let cases = [
+            ScoringCase {
+                signal: "scam language adds half its score to the risk",
+                before: |_| {},
+                after: |t| {
+                    let lexicon = ScamLexicon::parse("5 guaranteed returns").unwrap();
+                    t.scam_language = Some(lexicon.scan("https://example-project.com/", "Stake today for guaranteed returns."));
+                },
+                score: 0,
+                risk: 2,
+            },
+            ScoringCase {
+                signal: "a domain registered days ago and on hold is risky",
+                before: |_| {},
+                after: |t| t.registration = Some(DomainRegistration { status: vec!["client hold".to_string()], ..registration(10, false) }),
+                score: 0,
+                risk: 6,
+            },
+            // github.io itself is old; that earns alice's page nothing, and a young platform would cost it nothing
+            ScoringCase {
+                signal: "an old hosting platform is not an old site",
+                before: |t| t.domain = "alice.github.io".to_string(),
+                after: |t| t.registration = Some(registration(10 * 365, true)),
+                score: 0,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "a young hosting platform is not a young site",
+                before: |t| t.domain = "alice.github.io".to_string(),
+                after: |t| t.registration = Some(registration(10, true)),
+                score: 0,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "a signed security.txt earns full credit",
+                before: |_| {},
+                after: |t| t.security_txt = Some(signed_security_txt()),
+                score: 15,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "a security.txt whose signature fails earns nothing",
+                before: |_| {},
+                after: |t| t.security_txt = Some(SecurityTxt {
+                    signature: SignatureStatus::Invalid { reason: "tampered".to_string() },
+                    ..signed_security_txt()
+                }),
+                score: 0,
+                risk: 2,
+            },
+            ScoringCase {
+                signal: "an audit claimed without a report",
+                before: |t| t.has_audit_reports = true,
+                after: |t| t.audit_reports = vec![audit(AuditStatus::ClaimedButMissing)],
+                score: 0,
+                risk: 1,
+            },
+            ScoringCase {
+                signal: "a self-hosted report",
+                before: |t| t.has_audit_reports = true,
+                after: |t| t.audit_reports = vec![audit(AuditStatus::ClaimedButMissing), audit(AuditStatus::SelfHostedUnverified)],
+                score: 10,
+                risk: 0,
+            },
+            // Any page on a firm's site is not an audit of this project
+            ScoringCase {
+                signal: "an auditor's page that does not name the project",
+                before: |t| t.has_audit_reports = true,
+                after: |t| t.audit_reports = vec![audit(auditor_site(false))],
+                score: 0,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "an unregistered report on the auditor's site naming the project",
+                before: |t| t.has_audit_reports = true,
+                after: |t| t.audit_reports = vec![audit(AuditStatus::SelfHostedUnverified), audit(auditor_site(true))],
+                score: 15,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "a report in the auditor registry",
+                before: |t| t.has_audit_reports = true,
+                after: |t| t.audit_reports = vec![
+                    audit(AuditStatus::SelfHostedUnverified),
+                    audit(AuditStatus::Verified { auditor: "PoA Test Auditors".to_string(), date: "2024-03-01".to_string() }),
+                ],
+                score: 25,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "another project's report",
+                before: |t| t.has_audit_reports = true,
+                after: |t| t.audit_reports = vec![audit(AuditStatus::OtherProject {
+                    auditor: "PoA Test Auditors".to_string(),
+                    project: "another-protocol.org".to_string(),
+                })],
+                score: 0,
+                risk: 3,
+            },
+            // Only a history of commits to the repository counts for more than linked profiles
+            ScoringCase {
+                signal: "team profiles linked",
+                before: |t| t.has_team_verification = true,
+                after: |t| t.team_members = team(None),
+                score: 5,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "a young GitHub account",
+                before: |t| t.has_team_verification = true,
+                after: |t| t.team_members = team(Some(github_account(20))),
+                score: 5,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "an old GitHub account",
+                before: |t| t.has_team_verification = true,
+                after: |t| t.team_members = team(Some(github_account(4000))),
+                score: 5,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "a token contributor",
+                before: |t| t.has_team_verification = true,
+                after: |t| t.team_members = team(Some(contributor(4))),
+                score: 5,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "an established contributor",
+                before: |t| t.has_team_verification = true,
+                after: |t| t.team_members = team(Some(contributor(40))),
+                score: 15,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "a team profile linking a nonexistent account",
+                before: |t| t.has_team_verification = true,
+                after: |t| {
+                    t.team_members = team(Some(contributor(40)));
+                    t.team_members[1].level = VerificationLevel::GitHubNotFound { login: "ghost-account-404".to_string() };
+                },
+                score: 15,
+                risk: 2,
+            },
+            ScoringCase {
+                signal: "tokenomics mentioned",
+                before: |_| {},
+                after: |t| t.has_token_economics = true,
+                score: 3,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "a vested distribution",
+                before: |_| {},
+                after: |t| t.tokenomics = Some(tokenomics(&[
+                    "Total supply: 1,000,000,000",
+                    "Team 15% - 12-month cliff, 36-month vesting",
+                    "Community 60%",
+                    "Treasury 25%",
+                ])),
+                score: 15,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "unvested insiders holding most of the supply",
+                before: |_| {},
+                after: |t| t.tokenomics = Some(tokenomics(&["Total supply: 1,000,000,000", "Team 50%", "Investors 20%", "Community 30%"])),
+                score: 8,
+                risk: 4,
+            },
+            ScoringCase {
+                signal: "an organization the site does not confirm",
+                before: |t| t.has_public_repository = true,
+                after: |t| t.github_organization = Some(organization(OrganizationLink::Unconfirmed)),
+                score: 0,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "an organization linking to the site",
+                before: |t| t.has_public_repository = true,
+                after: |t| t.github_organization = Some(organization(OrganizationLink::Website)),
+                score: 5,
+                risk: 0,
+            },
+            ScoringCase {
+                signal: "an organization with the site as verified domain",
+                before: |t| t.has_public_repository = true,
+                after: |t| t.github_organization = Some(organization(OrganizationLink::VerifiedDomain)),
+                score: 10,
+                risk: 0,
+            },
+            // A public repository without stars costs 2 risk whatever its origin
+            ScoringCase {
+                signal: "original code",
+                before: |_| {},
+                after: |t| repository_origin(t, RepositoryOrigin::Original),
+                score: 25,
+                risk: 2,
+            },
+            ScoringCase {
+                signal: "a fork",
+                before: |_| {},
+                after: |t| repository_origin(t, RepositoryOrigin::Fork { parent: "example-upstream/amm-core".to_string() }),
+                score: 10,
+                risk: 2,
+            },
+            ScoringCase {
+                signal: "a copy earns no repository credit",
+                before: |_| {},
+                after: |t| repository_origin(t, RepositoryOrigin::Copy {
+                    upstream: "example-upstream/amm-core".to_string(),
+                    file_similarity: 0.97,
+                    tree_similarity: 1.0,
+                }),
+                score: 0,
+                risk: 5,
+            },
+            // Bitbucket has no stars, so a starless repository there is no warning sign
+            ScoringCase {
+                signal: "a starless repository on Bitbucket",
+                before: |t| source_repository(t, ForgeKind::GitLab, "gitlab.com"),
+                after: |t| source_repository(t, ForgeKind::Bitbucket, "bitbucket.org"),
+                score: 0,
+                risk: -2,
+            },
+        ];
+
+        for case in cases {
+            let (verifier, cert, mut transparency) = scoring_fixture();
+            (case.before)(&mut transparency);
+            let (score, risk) = verifier.calculate_real_scores(&transparency, &cert);
+            (case.after)(&mut transparency);
+            let expected = ((score as i32 + case.score).clamp(0, 100) as u32, (risk as i32 + case.risk).clamp(0, 10) as u8);
+            assert_eq!(verifier.calculate_real_scores(&transparency, &cert), expected, "{}", case.signal);
+        }
+    }
+
+    #[test]
+    fn test_signed_releases_outweigh_stars_for_supply_chain_projects() {
+        use crate::commit_signing::SignatureStats;
+
+        let cert = fixture_certificate("example-project.com");
+        let mut transparency = RealTransparencyData::unavailable("example-project.com");
+        transparency.has_public_repository = true;
+        transparency.github_stars = 2000;
+
+        let default_verifier = RealZkTlsVerifier::new(None);
+        let sensitive_verifier = RealZkTlsVerifier::new(None).with_supply_chain_sensitive(true);
+        let (default_unsigned, _) = default_verifier.calculate_real_scores(&transparency, &cert);
+        let (sensitive_unsigned, _) = sensitive_verifier.calculate_real_scores(&transparency, &cert);
+        assert_eq!(sensitive_unsigned, default_unsigned - 5);
+
+        let signed_tags = SignatureStats { checked: 3, signed: 3, verified: 3, ..Default::default() };
+        transparency.commit_signing = Some(SigningReport {
+            repository: "example-project/protocol".to_string(),
+            commits: SignatureStats { checked: 10, ..Default::default() },
+            release_tags: signed_tags,
+            maintainers: Vec::new(),
+            errors: Vec::new(),
+        });
+        assert_eq!(default_verifier.calculate_real_scores(&transparency, &cert).0, default_unsigned + 10);
+        assert_eq!(sensitive_verifier.calculate_real_scores(&transparency, &cert).0, sensitive_unsigned + 20);
+    }
+
+    #[test]
+    fn test_impersonating_domain_gets_no_established_baseline() {
+        let verifier = RealZkTlsVerifier::new(None);
+
+        let official = fixture_certificate("www.paypal.com");
+        let (score, risk) = verifier.calculate_real_scores(&RealTransparencyData::unavailable("www.paypal.com"), &official);
+        assert_eq!((score, risk), (85, 1));
+
+        let cert = fixture_certificate("example-project.com");
+        let (_, baseline_risk) = verifier.calculate_real_scores(&RealTransparencyData::unavailable("example-project.com"), &cert);
+
+        let cert = fixture_certificate("paypal-secure-login.xyz");
+        let witness = verifier.build_witness_data("paypal-secure-login.xyz", cert, RealTransparencyData::unavailable("paypal-secure-login.xyz"));
+        assert_eq!(witness.real_transparency.impersonation.len(), 1);
+        assert!(witness.transparency_score < 85);
+        assert_eq!(witness.risk_level, baseline_risk + 4);
+    }
+
+    #[test]
+    fn test_established_domain_without_scts_is_riskier() {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+        let chain = chain_validation::load_certificates(&format!("{}/chain.pem", fixtures)).unwrap();
+        let self_signed = chain_validation::load_certificates(&format!("{}/self_signed.pem", fixtures)).unwrap();
+        let verifier = RealZkTlsVerifier::new(None);
+        let transparency = RealTransparencyData::unavailable("www.paypal.com");
+
+        let mut logged = RealTlsCertificate::from_der("www.paypal.com", &chain[0], CertificateProvenance::Observed, true, 1735689600).unwrap();
+        logged.certificate_transparency = Some(certificate_transparency::verify_scts(&chain[0], Some(&chain[1]), &[], None, None).unwrap());
+        let mut unlogged = RealTlsCertificate::from_der("www.paypal.com", &self_signed[0], CertificateProvenance::Observed, true, 1735689600).unwrap();
+        unlogged.certificate_transparency = Some(certificate_transparency::verify_scts(&self_signed[0], None, &[], None, None).unwrap());
+
+        assert_eq!(verifier.calculate_real_scores(&transparency, &logged).1, 1);
+        assert_eq!(verifier.calculate_real_scores(&transparency, &unlogged).1, 3);
+    }
+
     #[tokio::test]
     async fn test_analyze_transparency() {
         let verifier = RealZkTlsVerifier::new(None);
@@ -0,0 +1,529 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::github::{GitHubClient, GitHubResponse};
+
+const DAY: u64 = 24 * 60 * 60;
+/// Requests repeated while GitHub answers 202 for statistics it is still computing
+const STATS_RETRIES: u32 = 3;
+/// Wait before the first repeat, doubled each time
+const STATS_RETRY_DELAY: Duration = if cfg!(test) { Duration::from_millis(10) } else { Duration::from_secs(2) };
+
+#[derive(Debug, Deserialize)]
+struct GitHubRepository {
+    full_name: String,
+    description: Option<String>,
+    stargazers_count: u32,
+    forks_count: u32,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    pushed_at: Option<String>,
+    license: Option<GitHubLicense>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    fork: bool,
+    #[serde(default)]
+    is_template: bool,
+    /// Present on forks
+    parent: Option<GitHubParent>,
+    /// Present on repositories generated from a template
+    template_repository: Option<GitHubParent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubLicense {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubParent {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeeklyCommits {
+    total: u32,
+    /// Unix time of the start of the week
+    week: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Contributor {
+    contributions: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueItem {
+    state: String,
+    created_at: String,
+    closed_at: Option<String>,
+    /// Present when the item is a pull request
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    published_at: Option<String>,
+    #[serde(default)]
+    draft: bool,
+}
+
+/// Opened versus closed counts for the most recent issues or pull requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemActivity {
+    pub total: u32,
+    pub closed: u32,
+}
+
+impl ItemActivity {
+    pub fn closed_ratio(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.closed as f64 / self.total as f64)
+    }
+}
+
+/// One explained part of the code review score; penalties have negative points
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreComponent {
+    pub metric: String,
+    pub points: i32,
+    pub max_points: i32,
+    pub reason: String,
+}
+
+impl std::fmt::Display for ScoreComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:+}/{}: {}", self.metric, self.points, self.max_points, self.reason)
+    }
+}
+
+/// Maintenance signals of a source repository; `None` where the forge had no answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepositoryHealth {
+    pub repository: String,
+    pub description: Option<String>,
+    pub stars: u32,
+    pub forks: u32,
+    pub license: Option<String>,
+    pub created_at: Option<u64>,
+    pub updated_at: Option<u64>,
+    /// Last push to any branch, the closest the repository metadata gets to the last commit
+    pub pushed_at: Option<u64>,
+    pub archived: bool,
+    pub is_fork: bool,
+    /// Upstream repository of a fork
+    pub parent: Option<String>,
+    pub is_template: bool,
+    /// Template this repository was generated from
+    pub template_repository: Option<String>,
+    pub commits_90d: Option<u32>,
+    pub commits_365d: Option<u32>,
+    /// Contributors on the first page of the contributor list (at most 100)
+    pub contributors: Option<u32>,
+    /// Fewest contributors who together made half of the commits
+    pub bus_factor: Option<u32>,
+    pub issues: Option<ItemActivity>,
+    pub pull_requests: Option<ItemActivity>,
+    /// Median days from opening to closing recent issues and pull requests
+    pub median_days_to_close: Option<u64>,
+    pub releases_365d: Option<u32>,
+    pub last_release: Option<u64>,
+    /// Score metrics the forge has no data for, e.g. stars on Bitbucket
+    #[serde(default)]
+    pub unmeasured: Vec<String>,
+    /// Timestamp the ages above are measured against
+    pub measured_at: u64,
+}
+
+impl RepositoryHealth {
+    /// A repository known only by name; every signal is absent
+    pub fn new(repository: &str, measured_at: u64) -> Self {
+        Self {
+            repository: repository.to_string(),
+            description: None,
+            stars: 0,
+            forks: 0,
+            license: None,
+            created_at: None,
+            updated_at: None,
+            pushed_at: None,
+            archived: false,
+            is_fork: false,
+            parent: None,
+            is_template: false,
+            template_repository: None,
+            commits_90d: None,
+            commits_365d: None,
+            contributors: None,
+            bus_factor: None,
+            issues: None,
+            pull_requests: None,
+            median_days_to_close: None,
+            releases_365d: None,
+            last_release: None,
+            unmeasured: Vec::new(),
+            measured_at,
+        }
+    }
+
+    fn days_since(&self, timestamp: Option<u64>) -> Option<u64> {
+        timestamp.map(|t| self.measured_at.saturating_sub(t) / DAY)
+    }
+
+    /// Points per metric; their sum, clamped to 0..=100, is the code review score
+    pub fn score_components(&self) -> Vec<ScoreComponent> {
+        let mut components = Vec::new();
+        let mut add = |metric: &str, points: i32, max_points: i32, reason: String| {
+            components.push(ScoreComponent { metric: metric.to_string(), points, max_points, reason });
+        };
+
+        add("repository", 10, 10, format!("public repository {}", self.repository));
+        add("stars", match self.stars {
+            s if s > 1000 => 10,
+            s if s > 100 => 7,
+            s if s > 10 => 4,
+            _ => 0,
+        }, 10, format!("{} stars", self.stars));
+        add("forks", match self.forks {
+            f if f > 100 => 5,
+            f if f > 10 => 3,
+            f if f > 1 => 1,
+            _ => 0,
+        }, 5, format!("{} forks", self.forks));
+        add("license", if self.license.is_some() { 5 } else { 0 }, 5,
+            self.license.clone().unwrap_or_else(|| "no license".to_string()));
+        let described = self.description.as_deref().is_some_and(|d| !d.trim().is_empty());
+        add("description", if described { 5 } else { 0 }, 5,
+            if described { "described" } else { "no description" }.to_string());
+
+        match self.days_since(self.pushed_at) {
+            Some(days) => add("recent activity", match days {
+                d if d < 30 => 10,
+                d if d < 90 => 7,
+                d if d < 365 => 3,
+                _ => 0,
+            }, 10, format!("last push {} days ago", days)),
+            None => add("recent activity", 0, 10, "push date unknown".to_string()),
+        }
+
+        match (self.commits_90d, self.commits_365d) {
+            (Some(recent), Some(year)) => {
+                let points = match recent {
+                    c if c >= 20 => 10,
+                    c if c >= 5 => 6,
+                    c if c >= 1 => 3,
+                    _ => 0,
+                } + match year {
+                    c if c >= 100 => 5,
+                    c if c >= 25 => 3,
+                    _ => 0,
+                };
+                add("commit cadence", points, 15, format!("{} commits in 90 days, {} in 365 days", recent, year));
+            }
+            _ => add("commit cadence", 0, 15, "commit statistics unavailable".to_string()),
+        }
+
+        match (self.contributors, self.bus_factor) {
+            (Some(contributors), Some(bus_factor)) => {
+                let points = match contributors {
+                    c if c >= 10 => 8,
+                    c if c >= 3 => 5,
+                    c if c >= 2 => 2,
+                    _ => 0,
+                } + match bus_factor {
+                    b if b >= 3 => 7,
+                    2 => 4,
+                    _ => 0,
+                };
+                add("contributors", points, 15, format!("{} contributors, bus factor {}", contributors, bus_factor));
+            }
+            _ => add("contributors", 0, 15, "contributor list unavailable".to_string()),
+        }
+
+        let ratio_points = |activity: Option<ItemActivity>| match activity.and_then(|a| a.closed_ratio()) {
+            Some(r) if r >= 0.7 => 5,
+            Some(r) if r >= 0.4 => 3,
+            _ => 0,
+        };
+        let describe = |activity: Option<ItemActivity>, kind: &str| match activity {
+            Some(a) if a.total > 0 => format!("{} of {} recent {} closed", a.closed, a.total, kind),
+            _ => format!("no {}", kind),
+        };
+        add("issues", ratio_points(self.issues), 5, describe(self.issues, "issues"));
+        add("pull requests", ratio_points(self.pull_requests), 5, describe(self.pull_requests, "pull requests"));
+        match self.median_days_to_close {
+            Some(days) => add("response time", match days {
+                d if d <= 7 => 5,
+                d if d <= 30 => 3,
+                _ => 0,
+            }, 5, format!("median {} days to close", days)),
+            None => add("response time", 0, 5, "nothing closed recently".to_string()),
+        }
+
+        match self.releases_365d {
+            Some(releases) => add("releases", match releases {
+                r if r >= 4 => 8,
+                r if r >= 1 => 5,
+                _ => 0,
+            }, 8, match self.days_since(self.last_release) {
+                Some(days) => format!("{} releases in 365 days, last {} days ago", releases, days),
+                None => "no releases".to_string(),
+            }),
+            None => add("releases", 0, 8, "release list unavailable".to_string()),
+        }
+
+        if self.archived {
+            add("archived", -30, 0, "archived; no longer maintained".to_string());
+        }
+        if self.is_fork {
+            add("fork", -15, 0, format!("fork of {}", self.parent.as_deref().unwrap_or("another repository")));
+        }
+        if self.is_template {
+            add("template", -10, 0, "template repository".to_string());
+        }
+        for component in components.iter_mut().filter(|c| self.unmeasured.contains(&c.metric)) {
+            component.points = 0;
+            component.reason = "not reported by this forge".to_string();
+        }
+        components
+    }
+
+    /// Sum of the components, with the points earned scaled up to make up for metrics the forge cannot report
+    pub fn code_review_score(&self) -> u8 {
+        let components = self.score_components();
+        let earned: i32 = components.iter().map(|c| c.points.max(0)).sum();
+        let penalties: i32 = components.iter().map(|c| c.points.min(0)).sum();
+        let possible: i32 = components.iter().map(|c| c.max_points).sum();
+        let measurable: i32 = components.iter()
+            .filter(|c| !self.unmeasured.contains(&c.metric))
+            .map(|c| c.max_points)
+            .sum();
+        let earned = if measurable > 0 { earned * possible / measurable } else { earned };
+        (earned + penalties).clamp(0, 100) as u8
+    }
+}
+
+/// ISO 8601 timestamp as returned by the forge APIs
+pub fn parse_timestamp(value: Option<&str>) -> Option<u64> {
+    let date = chrono::DateTime::parse_from_rfc3339(value?).ok()?;
+    u64::try_from(date.timestamp()).ok()
+}
+
+/// Fewest contributors whose commits add up to half of all commits
+pub fn bus_factor(mut contributions: Vec<u32>) -> u32 {
+    contributions.sort_unstable_by(|a, b| b.cmp(a));
+    let total: u64 = contributions.iter().map(|&c| c as u64).sum();
+    let mut covered = 0u64;
+    let mut people = 0;
+    for count in contributions {
+        if covered * 2 >= total {
+            break;
+        }
+        covered += count as u64;
+        people += 1;
+    }
+    people
+}
+
+/// Repository metadata plus commit, contributor, issue and release activity for `repo` (`owner/name`)
+pub async fn fetch_repository_health(github: &GitHubClient, repo: &str, now: u64) -> Result<RepositoryHealth> {
+    let response = github.get(&format!("/repos/{}", repo)).await?;
+    if !response.is_success() {
+        return Err(anyhow::anyhow!("Failed to fetch GitHub repo data: status {}", response.status));
+    }
+    let info: GitHubRepository = response.json()?;
+    let mut health = RepositoryHealth {
+        description: info.description,
+        stars: info.stargazers_count,
+        forks: info.forks_count,
+        license: info.license.map(|l| l.name),
+        created_at: parse_timestamp(info.created_at.as_deref()),
+        updated_at: parse_timestamp(info.updated_at.as_deref()),
+        pushed_at: parse_timestamp(info.pushed_at.as_deref()),
+        archived: info.archived,
+        is_fork: info.fork,
+        parent: info.parent.map(|p| p.full_name),
+        is_template: info.is_template,
+        template_repository: info.template_repository.map(|t| t.full_name),
+        ..RepositoryHealth::new(&info.full_name, now)
+    };
+
+    // Activity endpoints are best effort
+    let response = fetch_statistics(github, &format!("/repos/{}/stats/commit_activity", repo)).await;
+    if let Some(weeks) = response.filter(|r| r.status == 200).and_then(|r| r.json::<Vec<WeeklyCommits>>().ok()) {
+        let since = |days: u64| weeks.iter().filter(|w| w.week + 7 * DAY > now.saturating_sub(days * DAY)).map(|w| w.total).sum();
+        health.commits_90d = Some(since(90));
+        health.commits_365d = Some(since(365));
+    }
+
+    let response = github.get(&format!("/repos/{}/contributors?per_page=100", repo)).await;
+    if let Some(contributors) = response.ok().filter(|r| r.is_success()).and_then(|r| r.json::<Vec<Contributor>>().ok()) {
+        health.contributors = Some(contributors.len() as u32);
+        health.bus_factor = Some(bus_factor(contributors.iter().map(|c| c.contributions).collect()));
+    }
+
+    let response = github.get(&format!("/repos/{}/issues?state=all&per_page=100", repo)).await;
+    if let Some(items) = response.ok().filter(|r| r.is_success()).and_then(|r| r.json::<Vec<IssueItem>>().ok()) {
+        let mut issues = ItemActivity { total: 0, closed: 0 };
+        let mut pull_requests = ItemActivity { total: 0, closed: 0 };
+        let mut days_to_close = Vec::new();
+        for item in &items {
+            let activity = if item.pull_request.is_some() { &mut pull_requests } else { &mut issues };
+            activity.total += 1;
+            if item.state == "closed" {
+                activity.closed += 1;
+            }
+            if let (Some(opened), Some(closed)) = (parse_timestamp(Some(&item.created_at)), parse_timestamp(item.closed_at.as_deref())) {
+                days_to_close.push(closed.saturating_sub(opened) / DAY);
+            }
+        }
+        days_to_close.sort_unstable();
+        health.median_days_to_close = days_to_close.get(days_to_close.len() / 2).copied();
+        health.issues = Some(issues);
+        health.pull_requests = Some(pull_requests);
+    }
+
+    let response = github.get(&format!("/repos/{}/releases?per_page=100", repo)).await;
+    if let Some(releases) = response.ok().filter(|r| r.is_success()).and_then(|r| r.json::<Vec<Release>>().ok()) {
+        let published: Vec<u64> = releases.iter()
+            .filter(|r| !r.draft)
+            .filter_map(|r| parse_timestamp(r.published_at.as_deref()))
+            .collect();
+        health.releases_365d = Some(published.iter().filter(|&&t| t + 365 * DAY > now).count() as u32);
+        health.last_release = published.iter().max().copied();
+    }
+
+    Ok(health)
+}
+
+/// GET a statistics endpoint, backing off while GitHub answers 202 because it is still computing them
+async fn fetch_statistics(github: &GitHubClient, path: &str) -> Option<GitHubResponse> {
+    let mut delay = STATS_RETRY_DELAY;
+    for attempt in 0..=STATS_RETRIES {
+        let response = github.get(path).await.ok()?;
+        if response.status != 202 {
+            return Some(response);
+        }
+        if attempt < STATS_RETRIES {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const NOW: u64 = 1735689600; // 2025-01-01
+
+    #[test]
+    fn test_bus_factor() {
+        assert_eq!(bus_factor(vec![900, 50, 50]), 1);
+        assert_eq!(bus_factor(vec![30, 30, 30, 10]), 2);
+        assert_eq!(bus_factor(vec![10; 10]), 5);
+        assert_eq!(bus_factor(Vec::new()), 0);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_repository_health() {
+        let weeks: Vec<String> = (0..52)
+            .map(|i| format!(r#"{{"total": {}, "week": {}, "days": [0,0,0,0,0,0,0]}}"#, if i >= 40 { 3 } else { 1 }, NOW - (52 - i) * 7 * DAY))
+            .collect();
+        let server = test_http::serve(vec![
+            ("/repos/example-project/protocol", MockResponse::ok(r#"{
+                "full_name": "example-project/protocol", "description": "Protocol contracts",
+                "stargazers_count": 420, "forks_count": 37,
+                "created_at": "2021-03-04T10:00:00Z", "updated_at": "2024-12-30T08:00:00Z", "pushed_at": "2024-12-20T08:00:00Z",
+                "license": {"name": "Apache License 2.0"}, "archived": false, "fork": false, "is_template": false}"#)),
+            // Statistics still being computed on the first request
+            ("/repos/example-project/protocol/stats/commit_activity", MockResponse::status(202)),
+            ("/repos/example-project/protocol/stats/commit_activity", MockResponse::ok(format!("[{}]", weeks.join(",")))),
+            ("/repos/example-project/protocol/contributors", MockResponse::ok(
+                r#"[{"contributions": 300}, {"contributions": 250}, {"contributions": 120}, {"contributions": 40}]"#,
+            )),
+            ("/repos/example-project/protocol/issues", MockResponse::ok(r#"[
+                {"state": "closed", "created_at": "2024-12-01T00:00:00Z", "closed_at": "2024-12-03T00:00:00Z"},
+                {"state": "open", "created_at": "2024-12-10T00:00:00Z", "closed_at": null},
+                {"state": "closed", "created_at": "2024-11-01T00:00:00Z", "closed_at": "2024-11-02T00:00:00Z", "pull_request": {}},
+                {"state": "closed", "created_at": "2024-10-01T00:00:00Z", "closed_at": "2024-11-15T00:00:00Z", "pull_request": {}}]"#)),
+            ("/repos/example-project/protocol/releases", MockResponse::ok(r#"[
+                {"published_at": "2024-11-01T00:00:00Z", "draft": false},
+                {"published_at": null, "draft": true},
+                {"published_at": "2023-06-01T00:00:00Z", "draft": false}]"#)),
+        ]).await;
+        let github = GitHubClient::new(&server.base_url, None);
+
+        let health = fetch_repository_health(&github, "example-project/protocol", NOW).await.unwrap();
+        assert_eq!(health.pushed_at, Some(1734681600));
+        assert_eq!(health.updated_at, Some(1735545600));
+        assert_eq!(health.commits_90d, Some(37));
+        assert_eq!(health.commits_365d, Some(76));
+        assert_eq!((health.contributors, health.bus_factor), (Some(4), Some(2)));
+        assert_eq!(health.issues, Some(ItemActivity { total: 2, closed: 1 }));
+        assert_eq!(health.pull_requests, Some(ItemActivity { total: 2, closed: 2 }));
+        assert_eq!(health.median_days_to_close, Some(2));
+        assert_eq!(health.releases_365d, Some(1));
+
+        let components = health.score_components();
+        let points = |metric: &str| components.iter().find(|c| c.metric == metric).unwrap().points;
+        assert_eq!(points("recent activity"), 10);
+        assert_eq!(points("commit cadence"), 13);
+        assert_eq!(points("contributors"), 9);
+        assert_eq!(points("issues"), 3);
+        assert_eq!(points("pull requests"), 5);
+        assert_eq!(points("response time"), 5);
+        assert_eq!(points("releases"), 5);
+        assert_eq!(health.code_review_score(), 10 + 7 + 3 + 5 + 5 + 10 + 13 + 9 + 3 + 5 + 5 + 5);
+    }
+
+    #[tokio::test]
+    async fn test_archived_fork_is_penalized() {
+        let server = test_http::serve(vec![
+            ("/repos/copycat/protocol", MockResponse::ok(r#"{
+                "full_name": "copycat/protocol", "description": null, "stargazers_count": 0, "forks_count": 0,
+                "pushed_at": "2022-01-01T00:00:00Z", "license": null, "archived": true, "fork": true,
+                "parent": {"full_name": "example-project/protocol"}}"#)),
+            ("/repos/copycat/protocol/stats/commit_activity", MockResponse::status(202)),
+        ]).await;
+        let github = GitHubClient::new(&server.base_url, None);
+
+        let health = fetch_repository_health(&github, "copycat/protocol", NOW).await.unwrap();
+        assert_eq!(health.commits_90d, None);
+        let requests = server.requests.lock().unwrap().iter().filter(|r| r.contains("/stats/commit_activity")).count();
+        assert_eq!(requests, 1 + STATS_RETRIES as usize);
+        assert_eq!(health.parent.as_deref(), Some("example-project/protocol"));
+        let reasons: Vec<String> = health.score_components().iter().filter(|c| c.points < 0).map(|c| c.to_string()).collect();
+        assert_eq!(reasons, vec!["archived -30/0: archived; no longer maintained", "fork -15/0: fork of example-project/protocol"]);
+        assert_eq!(health.code_review_score(), 0);
+    }
+
+    #[test]
+    fn test_unmeasured_metrics_are_not_penalties() {
+        let health = RepositoryHealth {
+            description: Some("Protocol contracts".to_string()),
+            license: Some("MIT License".to_string()),
+            pushed_at: Some(NOW - 5 * DAY),
+            commits_90d: Some(30),
+            commits_365d: Some(120),
+            contributors: Some(12),
+            bus_factor: Some(3),
+            issues: Some(ItemActivity { total: 10, closed: 8 }),
+            pull_requests: Some(ItemActivity { total: 10, closed: 9 }),
+            median_days_to_close: Some(2),
+            forks: 40,
+            ..RepositoryHealth::new("example-project/contracts", NOW)
+        };
+        // Zero stars and no releases cost 18 points where the forge reports them
+        assert_eq!(health.code_review_score(), 98 - 10 - 8 - 2);
+
+        let health = RepositoryHealth { unmeasured: vec!["stars".to_string(), "releases".to_string()], ..health };
+        let stars = health.score_components().into_iter().find(|c| c.metric == "stars").unwrap();
+        assert_eq!(stars.to_string(), "stars +0/10: not reported by this forge");
+        // 78 of the 80 measurable points, scaled to the full 98
+        assert_eq!(health.code_review_score(), 95);
+    }
+}
@@ -0,0 +1,346 @@
+use anyhow::{Context, Result};
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use x509_parser::pem::Pem;
+use x509_parser::prelude::{FromDer, X509Certificate};
+use x509_parser::revocation_list::CertificateRevocationList;
+
+use crate::ocsp::{self, CertStatus, OcspResponse, OID_SHA1, OID_SHA256};
+
+/// Returned instead of any fallback certificate when revocation was observed
+#[derive(Debug, thiserror::Error)]
+#[error("certificate for {domain} has been revoked: {detail}")]
+pub struct CertificateRevokedError {
+    pub domain: String,
+    pub detail: String,
+}
+
+/// Local revocation data for offline checking
+#[derive(Debug, Clone, Default)]
+pub struct RevocationSources {
+    pub crl_files: Vec<String>,
+    pub ocsp_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RevocationStatus {
+    Good,
+    Revoked {
+        revoked_at: Option<u64>,
+        reason: Option<String>,
+    },
+    /// No source covered this certificate
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RevocationSource {
+    OcspStaple,
+    OcspFile(String),
+    Crl(String),
+}
+
+/// Outcome of one revocation source for one certificate in the chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationCheck {
+    pub subject: String,
+    pub source: RevocationSource,
+    pub status: RevocationStatus,
+    /// Whether the OCSP/CRL signature verified against the issuing CA
+    pub signature_verified: bool,
+    pub this_update: Option<u64>,
+    pub next_update: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationReport {
+    pub checks: Vec<RevocationCheck>,
+}
+
+impl RevocationReport {
+    /// The first check whose signature verified against the issuer and that reported a revoked certificate
+    pub fn revoked(&self) -> Option<&RevocationCheck> {
+        self.checks.iter().find(|c| c.signature_verified && matches!(c.status, RevocationStatus::Revoked { .. }))
+    }
+
+    /// Revocations claimed by responses whose signature did not verify; unsigned or forged data proves nothing
+    pub fn unverified_revocations(&self) -> impl Iterator<Item = &RevocationCheck> {
+        self.checks.iter().filter(|c| !c.signature_verified && matches!(c.status, RevocationStatus::Revoked { .. }))
+    }
+
+    /// Overall status: revoked wins, otherwise good only if a verified source vouched for the leaf
+    pub fn status(&self) -> RevocationStatus {
+        if let Some(check) = self.revoked() {
+            return check.status.clone();
+        }
+        let leaf_good = self.checks.iter()
+            .any(|c| c.signature_verified && c.status == RevocationStatus::Good);
+        if leaf_good { RevocationStatus::Good } else { RevocationStatus::Unknown }
+    }
+}
+
+/// Check every certificate of a chain (leaf first) against the stapled OCSP response and local files
+pub fn check_revocation(
+    chain: &[Vec<u8>],
+    ocsp_staple: Option<&[u8]>,
+    sources: &RevocationSources,
+    now: u64,
+) -> Result<RevocationReport> {
+    let mut ocsp_files = Vec::new();
+    for path in &sources.ocsp_files {
+        let data = fs::read(path).with_context(|| format!("Failed to read OCSP response: {}", path))?;
+        ocsp_files.push((path.clone(), data));
+    }
+    let mut crl_files = Vec::new();
+    for path in &sources.crl_files {
+        crl_files.push((path.clone(), load_crl(path)?));
+    }
+
+    let certs: Vec<X509Certificate> = chain.iter()
+        .filter_map(|der| X509Certificate::from_der(der).ok().map(|(_, c)| c))
+        .collect();
+
+    let mut checks = Vec::new();
+    for (i, cert) in certs.iter().enumerate() {
+        let issuer = certs.get(i + 1).filter(|c| c.subject().as_raw() == cert.issuer().as_raw());
+
+        if i == 0 {
+            if let Some(staple) = ocsp_staple {
+                match ocsp::parse_ocsp_response(staple) {
+                    Ok(response) => checks.extend(ocsp_check(cert, issuer, &response, RevocationSource::OcspStaple, now)),
+                    Err(e) => println!("⚠️ Ignoring unparsable stapled OCSP response: {}", e),
+                }
+            }
+        }
+
+        for (path, data) in &ocsp_files {
+            let response = ocsp::parse_ocsp_response(data)
+                .with_context(|| format!("Failed to parse OCSP response: {}", path))?;
+            checks.extend(ocsp_check(cert, issuer, &response, RevocationSource::OcspFile(path.clone()), now));
+        }
+
+        for (path, crl_der) in &crl_files {
+            let (_, crl) = CertificateRevocationList::from_der(crl_der)
+                .map_err(|e| anyhow::anyhow!("Failed to parse CRL {}: {}", path, e))?;
+            if crl.issuer().as_raw() != cert.issuer().as_raw() {
+                continue;
+            }
+            let revoked = crl.iter_revoked_certificates().find(|r| r.raw_serial() == cert.raw_serial());
+            let status = match revoked {
+                Some(entry) => RevocationStatus::Revoked {
+                    revoked_at: u64::try_from(entry.revocation_date.timestamp()).ok(),
+                    reason: entry.reason_code().map(|(_, code)| code.to_string()),
+                },
+                None => RevocationStatus::Good,
+            };
+            let next_update = crl.next_update().and_then(|t| u64::try_from(t.timestamp()).ok());
+            let expired = next_update.map(|t| t < now).unwrap_or(false);
+            checks.push(RevocationCheck {
+                subject: cert.subject().to_string(),
+                source: RevocationSource::Crl(path.clone()),
+                status: if expired && revoked.is_none() { RevocationStatus::Unknown } else { status },
+                signature_verified: issuer.map(|i| crl.verify_signature(i.public_key()).is_ok()).unwrap_or(false),
+                this_update: u64::try_from(crl.last_update().timestamp()).ok(),
+                next_update,
+            });
+        }
+    }
+
+    Ok(RevocationReport { checks })
+}
+
+fn ocsp_check(
+    cert: &X509Certificate,
+    issuer: Option<&X509Certificate>,
+    response: &OcspResponse,
+    source: RevocationSource,
+    now: u64,
+) -> Vec<RevocationCheck> {
+    let signature_verified = match issuer {
+        Some(issuer) => {
+            // Either the CA signed directly or it delegated to a responder certificate it issued
+            // for OCSP signing (RFC 6960 §4.2.2.2); any other certificate of the CA proves nothing
+            response.verify_signature(issuer.public_key())
+                || response.certs.iter().any(|der| {
+                    X509Certificate::from_der(der)
+                        .map(|(_, responder)| {
+                            is_ocsp_signer(&responder)
+                                && responder.verify_signature(Some(issuer.public_key())).is_ok()
+                                && response.verify_signature(responder.public_key())
+                        })
+                        .unwrap_or(false)
+                })
+        }
+        None => false,
+    };
+
+    response.responses.iter()
+        .filter(|single| single.serial_number == cert.raw_serial())
+        .filter(|single| issuer.map(|i| cert_id_matches_issuer(single, i)).unwrap_or(true))
+        .map(|single| {
+            // A stale response no longer vouches for the certificate, but a revocation stays final
+            let expired = single.next_update.map(|t| t < now).unwrap_or(false);
+            RevocationCheck {
+                subject: cert.subject().to_string(),
+                source: source.clone(),
+                status: match &single.cert_status {
+                    CertStatus::Good if expired => RevocationStatus::Unknown,
+                    CertStatus::Good => RevocationStatus::Good,
+                    CertStatus::Revoked { revocation_time, reason } => RevocationStatus::Revoked {
+                        revoked_at: *revocation_time,
+                        reason: reason.map(crl_reason_name),
+                    },
+                    CertStatus::Unknown => RevocationStatus::Unknown,
+                },
+                signature_verified,
+                this_update: single.this_update,
+                next_update: single.next_update,
+            }
+        })
+        .collect()
+}
+
+/// The certificate carries the id-kp-OCSPSigning extended key usage
+fn is_ocsp_signer(cert: &X509Certificate) -> bool {
+    matches!(cert.extended_key_usage(), Ok(Some(eku)) if eku.value.ocsp_signing)
+}
+
+/// CertID carries hashes of the issuer's DN and public key; both must match the issuing certificate
+fn cert_id_matches_issuer(single: &ocsp::SingleResponse, issuer: &X509Certificate) -> bool {
+    let key = &issuer.public_key().subject_public_key.data;
+    let algorithm = if single.hash_algorithm == OID_SHA1 {
+        &digest::SHA1_FOR_LEGACY_USE_ONLY
+    } else if single.hash_algorithm == OID_SHA256 {
+        &digest::SHA256
+    } else {
+        // Unknown hash: fall back to matching on serial only
+        return true;
+    };
+    digest::digest(algorithm, key).as_ref() == single.issuer_key_hash
+        && digest::digest(algorithm, issuer.subject().as_raw()).as_ref() == single.issuer_name_hash
+}
+
+fn crl_reason_name(code: u8) -> String {
+    match code {
+        0 => "unspecified",
+        1 => "keyCompromise",
+        2 => "cACompromise",
+        3 => "affiliationChanged",
+        4 => "superseded",
+        5 => "cessationOfOperation",
+        6 => "certificateHold",
+        8 => "removeFromCRL",
+        9 => "privilegeWithdrawn",
+        10 => "aACompromise",
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+fn load_crl(path: &str) -> Result<Vec<u8>> {
+    let data = fs::read(path).with_context(|| format!("Failed to read CRL: {}", path))?;
+    if data.starts_with(b"-----") {
+        for pem in Pem::iter_from_buffer(&data) {
+            let pem = pem.map_err(|e| anyhow::anyhow!("Invalid PEM in {}: {}", path, e))?;
+            if pem.label == "X509 CRL" {
+                return Ok(pem.contents);
+            }
+        }
+        return Err(anyhow::anyhow!("No X509 CRL block in {}", path));
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_validation::load_certificates;
+
+    const NOW: u64 = 1735689600;
+
+    fn fixture(name: &str) -> String {
+        format!("{}/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    #[test]
+    fn test_stapled_ocsp_good() {
+        let chain = load_certificates(&fixture("chain.pem")).unwrap();
+        let staple = fs::read(fixture("ocsp_good.der")).unwrap();
+
+        let report = check_revocation(&chain, Some(&staple), &RevocationSources::default(), NOW).unwrap();
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(report.checks[0].source, RevocationSource::OcspStaple);
+        assert!(report.checks[0].signature_verified);
+        assert_eq!(report.status(), RevocationStatus::Good);
+    }
+
+    #[test]
+    fn test_offline_ocsp_file_reports_revoked() {
+        let chain = load_certificates(&fixture("chain.pem")).unwrap();
+        let sources = RevocationSources {
+            crl_files: Vec::new(),
+            ocsp_files: vec![fixture("ocsp_revoked.der")],
+        };
+
+        let report = check_revocation(&chain, None, &sources, NOW).unwrap();
+        let revoked = report.revoked().expect("leaf should be revoked");
+        assert!(revoked.signature_verified);
+        assert_eq!(revoked.status, RevocationStatus::Revoked {
+            revoked_at: Some(1717200000),
+            reason: Some("keyCompromise".to_string()),
+        });
+    }
+
+    #[test]
+    fn test_unverified_revocation_is_not_acted_on() {
+        // Without the issuer in the chain the response's signature cannot be checked
+        let chain = load_certificates(&fixture("chain.pem")).unwrap();
+        let sources = RevocationSources { crl_files: Vec::new(), ocsp_files: vec![fixture("ocsp_revoked.der")] };
+
+        let report = check_revocation(&chain[..1], None, &sources, NOW).unwrap();
+        assert!(report.revoked().is_none());
+        assert_eq!(report.unverified_revocations().count(), 1);
+        assert_eq!(report.status(), RevocationStatus::Unknown);
+    }
+
+    #[test]
+    fn test_delegated_responder_needs_ocsp_signing_usage() {
+        let chain = load_certificates(&fixture("delegated_chain.pem")).unwrap();
+
+        let delegated = fs::read(fixture("ocsp_delegated_good.der")).unwrap();
+        let report = check_revocation(&chain, Some(&delegated), &RevocationSources::default(), NOW).unwrap();
+        assert!(report.checks[0].signature_verified);
+        assert_eq!(report.status(), RevocationStatus::Good);
+
+        // Signed by another certificate of the same CA that was never made an OCSP responder
+        let forged = fs::read(fixture("ocsp_forged_good.der")).unwrap();
+        let report = check_revocation(&chain, Some(&forged), &RevocationSources::default(), NOW).unwrap();
+        assert!(!report.checks[0].signature_verified);
+        assert_ne!(report.status(), RevocationStatus::Good);
+    }
+
+    #[test]
+    fn test_stale_ocsp_staple_is_unknown() {
+        let chain = load_certificates(&fixture("chain.pem")).unwrap();
+        let staple = fs::read(fixture("ocsp_good.der")).unwrap();
+
+        // The fixture responses are valid until 2124
+        let report = check_revocation(&chain, Some(&staple), &RevocationSources::default(), 4891363200).unwrap();
+        assert!(report.checks[0].next_update.is_some_and(|t| t < 4891363200));
+        assert_eq!(report.checks[0].status, RevocationStatus::Unknown);
+        assert_eq!(report.status(), RevocationStatus::Unknown);
+    }
+
+    #[test]
+    fn test_crl_checks() {
+        let chain = load_certificates(&fixture("chain.pem")).unwrap();
+        let clean = RevocationSources { crl_files: vec![fixture("intermediate.crl.pem")], ocsp_files: Vec::new() };
+        let report = check_revocation(&chain, None, &clean, NOW).unwrap();
+        assert_eq!(report.status(), RevocationStatus::Good);
+
+        let revoked = RevocationSources { crl_files: vec![fixture("revoked.crl.pem")], ocsp_files: Vec::new() };
+        let report = check_revocation(&chain, None, &revoked, NOW).unwrap();
+        assert!(matches!(report.status(), RevocationStatus::Revoked { .. }));
+    }
+}
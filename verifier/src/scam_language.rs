@@ -0,0 +1,218 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Lexicon used when no `--scam-lexicon` file is given
+const DEFAULT_LEXICON: &str = include_str!("../lexicon/scam_phrases.txt");
+
+/// Upper bound of a page's scam-language score, however many phrases match
+pub const MAX_SCAM_SCORE: u8 = 10;
+
+/// Characters of context kept on each side of a match
+const SNIPPET_CONTEXT: usize = 40;
+const MAX_SNIPPETS_PER_PHRASE: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexiconEntry {
+    /// Lowercased phrase with single spaces
+    pub phrase: String,
+    pub weight: u8,
+}
+
+/// Weighted high-risk phrases, one `<weight> <phrase>` per line
+#[derive(Debug, Clone)]
+pub struct ScamLexicon {
+    entries: Vec<LexiconEntry>,
+}
+
+impl Default for ScamLexicon {
+    fn default() -> Self {
+        Self::parse(DEFAULT_LEXICON).expect("built-in scam lexicon is valid")
+    }
+}
+
+impl ScamLexicon {
+    pub fn from_file(path: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read scam lexicon {}", path))?;
+        Self::parse(&contents).with_context(|| format!("Invalid scam lexicon {}", path))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (weight, phrase) = line.split_once(char::is_whitespace)
+                .ok_or_else(|| anyhow::anyhow!("line {}: expected `<weight> <phrase>`", number + 1))?;
+            let weight: u8 = weight.parse()
+                .ok()
+                .filter(|w| (1..=MAX_SCAM_SCORE).contains(w))
+                .ok_or_else(|| anyhow::anyhow!("line {}: weight must be 1-{}, got {}", number + 1, MAX_SCAM_SCORE, weight))?;
+            entries.push(LexiconEntry { phrase: normalize_text(phrase), weight });
+        }
+        Ok(Self { entries })
+    }
+
+    /// Find every lexicon phrase in already-extracted page text
+    pub fn scan(&self, url: &str, text: &str) -> ScamLanguageReport {
+        let text = normalize_text(text);
+        let matches: Vec<PhraseMatch> = self.entries.iter()
+            .filter_map(|entry| {
+                let positions = find_phrase(&text, &entry.phrase);
+                if positions.is_empty() {
+                    return None;
+                }
+                Some(PhraseMatch {
+                    phrase: entry.phrase.clone(),
+                    weight: entry.weight,
+                    occurrences: positions.len(),
+                    snippets: positions.iter()
+                        .take(MAX_SNIPPETS_PER_PHRASE)
+                        .map(|&start| snippet(&text, start, entry.phrase.len()))
+                        .collect(),
+                })
+            })
+            .collect();
+
+        // Each phrase counts once so a page repeating one slogan cannot saturate the score
+        let total: u32 = matches.iter().map(|m| m.weight as u32).sum();
+        ScamLanguageReport {
+            url: url.to_string(),
+            score: total.min(MAX_SCAM_SCORE as u32) as u8,
+            matches,
+        }
+    }
+}
+
+/// A lexicon phrase found in the page text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhraseMatch {
+    pub phrase: String,
+    pub weight: u8,
+    pub occurrences: usize,
+    /// Surrounding text of the first few occurrences
+    pub snippets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScamLanguageReport {
+    pub url: String,
+    pub matches: Vec<PhraseMatch>,
+    /// Sum of matched phrase weights, capped at `MAX_SCAM_SCORE`
+    pub score: u8,
+}
+
+impl ScamLanguageReport {
+    pub fn phrases(&self) -> Vec<String> {
+        self.matches.iter().map(|m| m.phrase.clone()).collect()
+    }
+}
+
+/// Lowercase, unify apostrophes and collapse whitespace
+fn normalize_text(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| word.to_lowercase().replace(['\u{2018}', '\u{2019}'], "'"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Byte offsets where `phrase` occurs as whole words
+fn find_phrase(text: &str, phrase: &str) -> Vec<usize> {
+    let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    text.match_indices(phrase)
+        .map(|(start, _)| start)
+        .filter(|&start| {
+            !is_word(text[..start].chars().next_back()) && !is_word(text[start + phrase.len()..].chars().next())
+        })
+        .collect()
+}
+
+fn snippet(text: &str, start: usize, len: usize) -> String {
+    let mut from = start.saturating_sub(SNIPPET_CONTEXT);
+    while !text.is_char_boundary(from) {
+        from -= 1;
+    }
+    let mut to = (start + len + SNIPPET_CONTEXT).min(text.len());
+    while !text.is_char_boundary(to) {
+        to += 1;
+    }
+    format!(
+        "{}{}{}",
+        if from > 0 { "…" } else { "" },
+        text[from..to].trim(),
+        if to < text.len() { "…" } else { "" },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page_analysis::{self, PageAnalysis};
+    use crate::test_http::{self, MockResponse};
+
+    const PAGE: &str = r#"<html>
+        <head><title>Guaranteed returns!</title><script>var riskFree = "risk-free";</script></head>
+        <body>
+            <h1>Earn <b>guaranteed</b> returns</h1>
+            <p>Our arbitrage bot is 100% <em>risk-free</em>. Double your deposit in a week.</p>
+            <style>.risk-free { color: red }</style>
+            <p>Yes, GUARANTEED RETURNS &mdash; every single day.</p>
+        </body>
+    </html>"#;
+
+    #[test]
+    fn test_scan_reports_phrases_with_context() {
+        let lexicon = ScamLexicon::parse("5 guaranteed returns\n4 risk-free\n4 double your\n3 passive income\n").unwrap();
+        let report = lexicon.scan("https://example-project.com/", &PageAnalysis::parse("https://example-project.com/", PAGE).visible_text);
+
+        assert_eq!(report.phrases(), vec!["guaranteed returns", "risk-free", "double your"]);
+        let guaranteed = &report.matches[0];
+        assert_eq!(guaranteed.occurrences, 2);
+        assert_eq!(guaranteed.snippets[0], "earn guaranteed returns our arbitrage bot is 100% risk-free. do…");
+        assert_eq!(report.score, MAX_SCAM_SCORE);
+
+        // Whole words only: "no risk" must not match "no riskier"
+        let lexicon = ScamLexicon::parse("3 no risk").unwrap();
+        assert!(lexicon.scan("inline", "There is no riskier bet.").matches.is_empty());
+        assert_eq!(lexicon.scan("inline", "No risk, no fuss.").score, 3);
+    }
+
+    #[test]
+    fn test_scan_skips_markup_and_scripts() {
+        let lexicon = ScamLexicon::parse("5 guaranteed returns\n4 risk-free\n").unwrap();
+        let report = lexicon.scan("https://example-project.com/", &PageAnalysis::parse("https://example-project.com/", PAGE).visible_text);
+
+        // The title, the script and the style sheet are never shown as page text
+        let risk_free = report.matches.iter().find(|m| m.phrase == "risk-free").unwrap();
+        assert_eq!(risk_free.occurrences, 1);
+        assert!(report.matches.iter().all(|m| m.snippets.iter().all(|s| !s.contains("var riskfree") && !s.contains("color"))));
+    }
+
+    #[tokio::test]
+    async fn test_scan_fetched_page() {
+        let server = test_http::serve(vec![
+            ("/", MockResponse::ok(PAGE).with_header("Content-Type", "text/html")),
+        ]).await;
+
+        let response = reqwest::get(format!("{}/", server.base_url)).await.unwrap();
+        let page = page_analysis::parse_response(response).await.unwrap();
+        let report = ScamLexicon::default().scan(&page.url, &page.visible_text);
+        assert!(report.phrases().contains(&"guaranteed returns".to_string()));
+        assert!(report.score > 0 && report.score <= MAX_SCAM_SCORE);
+    }
+
+    #[test]
+    fn test_parse_lexicon() {
+        assert!(ScamLexicon::default().entries.iter().any(|e| e.phrase == "guaranteed returns"));
+
+        let lexicon = ScamLexicon::parse("# comment\n\n2   Act  Now\n").unwrap();
+        assert_eq!(lexicon.entries, vec![LexiconEntry { phrase: "act now".to_string(), weight: 2 }]);
+
+        let err = ScamLexicon::parse("5 fine\nguaranteed\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        let err = ScamLexicon::parse("11 too heavy\n").unwrap_err();
+        assert!(err.to_string().contains("weight must be 1-10"));
+    }
+}
@@ -0,0 +1,336 @@
+use anyhow::Result;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+/// HSTS max-age below which the policy is considered too short (180 days)
+const MIN_HSTS_MAX_AGE: u64 = 180 * 24 * 60 * 60;
+
+/// Headers whose absence counts against a site
+const REQUIRED_HEADERS: [&str; 3] = ["Strict-Transport-Security", "Content-Security-Policy", "X-Frame-Options"];
+
+/// Newer opt-in hardening most legitimate sites do not send yet; reported but not scored
+const INFORMATIONAL_HEADERS: [&str; 3] = ["Permissions-Policy", "Cross-Origin-Opener-Policy", "Cross-Origin-Embedder-Policy"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeaderStatus {
+    Good,
+    /// Present, but configured in a way that defeats its purpose
+    Weak,
+    Missing,
+}
+
+/// Verdict for a single security header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderFinding {
+    pub header: String,
+    pub status: HeaderStatus,
+    pub value: Option<String>,
+    pub notes: Vec<String>,
+}
+
+impl HeaderFinding {
+    pub fn is_informational(&self) -> bool {
+        INFORMATIONAL_HEADERS.contains(&self.header.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityHeaderReport {
+    /// Final URL after redirects
+    pub url: String,
+    pub findings: Vec<HeaderFinding>,
+}
+
+impl SecurityHeaderReport {
+    /// Required headers the site does not send
+    pub fn missing(&self) -> Vec<String> {
+        self.headers(|f| f.status == HeaderStatus::Missing && REQUIRED_HEADERS.contains(&f.header.as_str()))
+    }
+
+    /// Scored headers that are present but misconfigured
+    pub fn weak(&self) -> Vec<String> {
+        self.headers(|f| f.status == HeaderStatus::Weak && !f.is_informational())
+    }
+
+    /// Informational headers that are missing or provide no protection
+    pub fn informational(&self) -> Vec<String> {
+        self.headers(|f| f.status != HeaderStatus::Good && f.is_informational())
+    }
+
+    fn headers(&self, filter: impl Fn(&HeaderFinding) -> bool) -> Vec<String> {
+        self.findings.iter()
+            .filter(|f| filter(f))
+            .map(|f| f.header.clone())
+            .collect()
+    }
+}
+
+/// Fetch `url` and evaluate the security headers of the final response
+pub async fn fetch_security_headers(url: &str) -> Result<SecurityHeaderReport> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+    let response = client.get(url).send().await?;
+    Ok(SecurityHeaderReport {
+        url: response.url().to_string(),
+        findings: evaluate_headers(response.headers()),
+    })
+}
+
+/// Evaluate HSTS, CSP, X-Frame-Options, Referrer-Policy, Permissions-Policy, COOP and COEP
+pub fn evaluate_headers(headers: &HeaderMap) -> Vec<HeaderFinding> {
+    let csp = header_value(headers, "content-security-policy");
+    vec![
+        evaluate_hsts(header_value(headers, "strict-transport-security")),
+        evaluate_csp(csp.clone()),
+        evaluate_frame_options(header_value(headers, "x-frame-options"), csp.as_deref()),
+        evaluate_referrer_policy(header_value(headers, "referrer-policy")),
+        finding("Permissions-Policy", header_value(headers, "permissions-policy"), |_| Vec::new()),
+        evaluate_isolation(
+            "Cross-Origin-Opener-Policy",
+            header_value(headers, "cross-origin-opener-policy"),
+            &["same-origin", "same-origin-allow-popups"],
+        ),
+        evaluate_isolation(
+            "Cross-Origin-Embedder-Policy",
+            header_value(headers, "cross-origin-embedder-policy"),
+            &["require-corp", "credentialless"],
+        ),
+    ]
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers.get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_string())
+}
+
+/// Build a finding; `check` returns weakness notes for a present header
+fn finding(header: &str, value: Option<String>, check: impl Fn(&str) -> Vec<String>) -> HeaderFinding {
+    let (status, notes) = match &value {
+        None => (HeaderStatus::Missing, Vec::new()),
+        Some(v) => {
+            let notes = check(v);
+            (if notes.is_empty() { HeaderStatus::Good } else { HeaderStatus::Weak }, notes)
+        }
+    };
+    HeaderFinding { header: header.to_string(), status, value, notes }
+}
+
+fn evaluate_hsts(value: Option<String>) -> HeaderFinding {
+    let mut result = finding("Strict-Transport-Security", value, |v| {
+        let max_age = v.split(';')
+            .find_map(|d| d.trim().to_ascii_lowercase().strip_prefix("max-age=").map(|a| a.trim_matches('"').to_string()))
+            .and_then(|a| a.parse::<u64>().ok());
+        match max_age {
+            None => vec!["no valid max-age".to_string()],
+            Some(age) if age < MIN_HSTS_MAX_AGE => vec![format!("max-age {} is shorter than 180 days", age)],
+            Some(_) => Vec::new(),
+        }
+    });
+    // Missing subdomain coverage or preload is informational, not a weakness
+    if let Some(v) = &result.value {
+        let directives: Vec<String> = v.split(';').map(|d| d.trim().to_ascii_lowercase()).collect();
+        if !directives.iter().any(|d| d == "includesubdomains") {
+            result.notes.push("includeSubDomains not set".to_string());
+        }
+        if directives.iter().any(|d| d == "preload") {
+            result.notes.push("preload requested".to_string());
+        }
+    }
+    result
+}
+
+fn evaluate_csp(value: Option<String>) -> HeaderFinding {
+    finding("Content-Security-Policy", value, |v| {
+        let directives: Vec<(String, Vec<String>)> = v.split(';')
+            .filter_map(|directive| {
+                let mut parts = directive.split_whitespace();
+                let name = parts.next()?.to_ascii_lowercase();
+                Some((name, parts.map(|p| p.to_ascii_lowercase()).collect()))
+            })
+            .collect();
+        // script-src replaces default-src for scripts, so only the effective policy matters
+        let Some((name, sources)) = directives.iter().find(|(n, _)| n == "script-src")
+            .or_else(|| directives.iter().find(|(n, _)| n == "default-src")) else {
+            return vec!["no script-src or default-src restricts scripts".to_string()];
+        };
+        // Browsers ignore 'unsafe-inline' next to a nonce or hash, and 'strict-dynamic' also drops the
+        // host and scheme allowlist in favour of trust propagated from nonced scripts
+        let strict_dynamic = sources.iter().any(|source| source == "'strict-dynamic'");
+        let inline_ignored = strict_dynamic || sources.iter()
+            .any(|source| ["'nonce-", "'sha256-", "'sha384-", "'sha512-"].iter().any(|p| source.starts_with(p)));
+        sources.iter()
+            .filter_map(|source| match source.as_str() {
+                "'unsafe-inline'" if !inline_ignored => Some(format!("{} allows 'unsafe-inline'", name)),
+                "'unsafe-eval'" => Some(format!("{} allows 'unsafe-eval'", name)),
+                "*" if !strict_dynamic => Some(format!("{} allows any origin", name)),
+                scheme if !strict_dynamic && is_scheme_source(scheme) => {
+                    Some(format!("{} allows any {} URL", name, scheme))
+                }
+                _ => None,
+            })
+            .collect()
+    })
+}
+
+/// A bare scheme like `https:` or `data:`, which admits scripts from anywhere using it
+fn is_scheme_source(source: &str) -> bool {
+    source.strip_suffix(':').is_some_and(|scheme| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+fn evaluate_frame_options(value: Option<String>, csp: Option<&str>) -> HeaderFinding {
+    let frame_ancestors = csp.and_then(|csp| {
+        csp.split(';')
+            .map(str::trim)
+            .find(|d| d.to_ascii_lowercase().starts_with("frame-ancestors"))
+            .map(str::to_string)
+    });
+    let mut result = finding("X-Frame-Options", value, |v| {
+        match v.to_ascii_uppercase().as_str() {
+            "DENY" | "SAMEORIGIN" => Vec::new(),
+            other => vec![format!("unsupported value {}", other)],
+        }
+    });
+    // CSP frame-ancestors supersedes X-Frame-Options
+    if result.status != HeaderStatus::Good {
+        if let Some(directive) = frame_ancestors {
+            result.status = HeaderStatus::Good;
+            result.notes.push(format!("framing controlled by CSP {}", directive));
+        }
+    }
+    result
+}
+
+fn evaluate_referrer_policy(value: Option<String>) -> HeaderFinding {
+    finding("Referrer-Policy", value, |v| {
+        // The last recognized policy in a comma-separated list wins
+        let policy = v.split(',').map(|p| p.trim().to_ascii_lowercase()).next_back().unwrap_or_default();
+        match policy.as_str() {
+            "unsafe-url" | "no-referrer-when-downgrade" => vec![format!("{} leaks full URLs", policy)],
+            _ => Vec::new(),
+        }
+    })
+}
+
+fn evaluate_isolation(header: &str, value: Option<String>, good: &[&str]) -> HeaderFinding {
+    finding(header, value, |v| {
+        let policy = v.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        if good.contains(&policy.as_str()) {
+            Vec::new()
+        } else {
+            vec![format!("{} provides no isolation", policy)]
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    fn status(findings: &[HeaderFinding], header: &str) -> HeaderStatus {
+        findings.iter().find(|f| f.header == header).unwrap().status
+    }
+
+    #[test]
+    fn test_hardened_site() {
+        let findings = evaluate_headers(&headers(&[
+            ("strict-transport-security", "max-age=63072000; includeSubDomains; preload"),
+            ("content-security-policy", "default-src 'self'; frame-ancestors 'none'"),
+            ("referrer-policy", "strict-origin-when-cross-origin"),
+            ("permissions-policy", "camera=()"),
+            ("cross-origin-opener-policy", "same-origin"),
+            ("cross-origin-embedder-policy", "require-corp"),
+        ]));
+        assert!(findings.iter().all(|f| f.status == HeaderStatus::Good), "{:?}", findings);
+        // X-Frame-Options is satisfied by frame-ancestors
+        let xfo = findings.iter().find(|f| f.header == "X-Frame-Options").unwrap();
+        assert!(xfo.value.is_none());
+        assert_eq!(xfo.notes, vec!["framing controlled by CSP frame-ancestors 'none'"]);
+    }
+
+    #[test]
+    fn test_weak_headers() {
+        let findings = evaluate_headers(&headers(&[
+            ("strict-transport-security", "max-age=300"),
+            ("content-security-policy", "script-src 'self' 'unsafe-inline' 'unsafe-eval'"),
+            ("x-frame-options", "ALLOW-FROM https://evil.example"),
+            ("referrer-policy", "unsafe-url"),
+            ("cross-origin-opener-policy", "unsafe-none"),
+        ]));
+        let hsts = findings.iter().find(|f| f.header == "Strict-Transport-Security").unwrap();
+        assert_eq!(hsts.status, HeaderStatus::Weak);
+        assert!(hsts.notes.contains(&"includeSubDomains not set".to_string()));
+
+        let csp = findings.iter().find(|f| f.header == "Content-Security-Policy").unwrap();
+        assert_eq!(csp.notes, vec!["script-src allows 'unsafe-inline'", "script-src allows 'unsafe-eval'"]);
+
+        assert_eq!(status(&findings, "X-Frame-Options"), HeaderStatus::Weak);
+        assert_eq!(status(&findings, "Referrer-Policy"), HeaderStatus::Weak);
+        assert_eq!(status(&findings, "Cross-Origin-Opener-Policy"), HeaderStatus::Weak);
+        assert_eq!(status(&findings, "Cross-Origin-Embedder-Policy"), HeaderStatus::Missing);
+        assert_eq!(status(&findings, "Permissions-Policy"), HeaderStatus::Missing);
+
+        let report = SecurityHeaderReport { url: "https://example.com/".to_string(), findings };
+        assert_eq!(report.missing(), Vec::<String>::new());
+        assert_eq!(report.weak(), vec!["Strict-Transport-Security", "Content-Security-Policy", "X-Frame-Options", "Referrer-Policy"]);
+        assert_eq!(report.informational(), vec!["Permissions-Policy", "Cross-Origin-Opener-Policy", "Cross-Origin-Embedder-Policy"]);
+    }
+
+    #[test]
+    fn test_csp_script_src_overrides_default_src() {
+        let overridden = evaluate_csp(Some("default-src 'self' 'unsafe-inline'; script-src 'self'".to_string()));
+        assert_eq!(overridden.status, HeaderStatus::Good, "{:?}", overridden.notes);
+
+        let inherited = evaluate_csp(Some("default-src 'self' 'unsafe-inline'; img-src *".to_string()));
+        assert_eq!(inherited.notes, vec!["default-src allows 'unsafe-inline'"]);
+
+        let unrestricted = evaluate_csp(Some("frame-ancestors 'none'".to_string()));
+        assert_eq!(unrestricted.status, HeaderStatus::Weak);
+    }
+
+    #[test]
+    fn test_csp_nonces_and_scheme_sources() {
+        // 'unsafe-inline' is the CSP2 fallback next to a nonce, hash or 'strict-dynamic'
+        for policy in [
+            "script-src 'nonce-r4nd0m' 'unsafe-inline'",
+            "script-src 'sha256-B2yPHKaXnvFWtRChIbabYmUBFZdVfKKXHbWtWidDVF8=' 'unsafe-inline'",
+            "script-src 'nonce-r4nd0m' 'strict-dynamic' 'unsafe-inline' https:",
+        ] {
+            let csp = evaluate_csp(Some(policy.to_string()));
+            assert_eq!(csp.status, HeaderStatus::Good, "{}: {:?}", policy, csp.notes);
+        }
+
+        let csp = evaluate_csp(Some("default-src 'self' https: data:; img-src *".to_string()));
+        assert_eq!(csp.notes, vec!["default-src allows any https: URL", "default-src allows any data: URL"]);
+        let csp = evaluate_csp(Some("script-src 'self' http: https://cdn.example-project.com".to_string()));
+        assert_eq!(csp.notes, vec!["script-src allows any http: URL"]);
+        let csp = evaluate_csp(Some("script-src * 'nonce-r4nd0m' 'unsafe-inline'".to_string()));
+        assert_eq!(csp.notes, vec!["script-src allows any origin"]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_security_headers() {
+        let server = test_http::serve(vec![
+            ("/", MockResponse::ok("").with_header("Strict-Transport-Security", "max-age=31536000")),
+        ]).await;
+
+        let report = fetch_security_headers(&format!("{}/", server.base_url)).await.unwrap();
+        assert_eq!(report.missing(), vec!["Content-Security-Policy", "X-Frame-Options"]);
+        assert!(report.informational().contains(&"Permissions-Policy".to_string()));
+        assert!(report.weak().is_empty());
+    }
+}
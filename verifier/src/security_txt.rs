@@ -0,0 +1,373 @@
+use anyhow::Result;
+use pgp::types::KeyTrait;
+use pgp::{Deserializable, SignedPublicKey, StandaloneSignature};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use url::Url;
+
+/// Where RFC 9116 places the file; `/security.txt` is the legacy location
+pub const SECURITY_TXT_PATH: &str = "/.well-known/security.txt";
+const LEGACY_SECURITY_TXT_PATH: &str = "/security.txt";
+
+const SIGNED_MESSAGE_HEADER: &str = "-----BEGIN PGP SIGNED MESSAGE-----";
+const SIGNATURE_HEADER: &str = "-----BEGIN PGP SIGNATURE-----";
+
+/// Outcome of checking a cleartext-signed security.txt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignatureStatus {
+    Unsigned,
+    /// Signed by the key with this fingerprint, fetched from an Encryption URL
+    Verified { fingerprint: String },
+    /// Signed, but the signing key is not among the keys retrieved from the Encryption field;
+    /// RFC 9116 advertises encryption keys there, which need not be the signing key
+    KeyUnavailable,
+    /// Signed by an advertised key, but the signature does not match the file
+    Invalid { reason: String },
+}
+
+/// Parsed security.txt (RFC 9116)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityTxt {
+    /// URL the file was served from
+    pub url: String,
+    pub contact: Vec<String>,
+    pub expires: Option<u64>,
+    pub encryption: Vec<String>,
+    pub policy: Vec<String>,
+    pub acknowledgments: Vec<String>,
+    pub canonical: Vec<String>,
+    pub preferred_languages: Option<String>,
+    pub signature: SignatureStatus,
+    /// Contacts that look usable: well-formed addresses, or https pages that answered
+    pub reachable_contacts: Vec<String>,
+    /// Deviations from RFC 9116 found while parsing
+    pub problems: Vec<String>,
+}
+
+impl SecurityTxt {
+    /// Parse `text` served from `url`, unwrapping a cleartext signature without checking it
+    pub fn parse(url: &str, text: &str) -> Self {
+        let (body, signed) = match split_cleartext(text) {
+            Some((body, _)) => (body, true),
+            None => (text.to_string(), false),
+        };
+        let mut file = SecurityTxt {
+            url: url.to_string(),
+            contact: Vec::new(),
+            expires: None,
+            encryption: Vec::new(),
+            policy: Vec::new(),
+            acknowledgments: Vec::new(),
+            canonical: Vec::new(),
+            preferred_languages: None,
+            signature: if signed {
+                SignatureStatus::KeyUnavailable
+            } else {
+                SignatureStatus::Unsigned
+            },
+            reachable_contacts: Vec::new(),
+            problems: Vec::new(),
+        };
+
+        let mut expires_fields = 0;
+        for line in body.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, value)) = line.split_once(':') else {
+                file.problems.push(format!("malformed line \"{}\"", line));
+                continue;
+            };
+            let value = value.trim().to_string();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "contact" => {
+                    if !["mailto:", "tel:", "https://"].iter().any(|scheme| value.to_ascii_lowercase().starts_with(scheme)) {
+                        file.problems.push(format!("Contact {} is not a mailto:, tel: or https: URI", value));
+                    }
+                    file.contact.push(value);
+                }
+                "expires" => {
+                    expires_fields += 1;
+                    match chrono::DateTime::parse_from_rfc3339(&value) {
+                        Ok(date) => file.expires = u64::try_from(date.timestamp()).ok(),
+                        Err(_) => file.problems.push(format!("Expires \"{}\" is not an RFC 3339 date", value)),
+                    }
+                }
+                "encryption" => file.encryption.push(value),
+                "policy" => file.policy.push(value),
+                "acknowledgments" | "acknowledgements" => file.acknowledgments.push(value),
+                "canonical" => file.canonical.push(value),
+                "preferred-languages" => file.preferred_languages = Some(value),
+                _ => {}
+            }
+        }
+
+        if file.contact.is_empty() {
+            file.problems.push("no Contact field".to_string());
+        }
+        match expires_fields {
+            0 => file.problems.push("no Expires field".to_string()),
+            1 => {}
+            n => file.problems.push(format!("Expires appears {} times", n)),
+        }
+        if !file.canonical.is_empty() && !file.canonical.iter().any(|c| c == url) {
+            file.problems.push(format!("{} is not listed as Canonical", url));
+        }
+        file
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    /// Has a contact and an Expires date that has not passed
+    pub fn is_valid(&self, now: u64) -> bool {
+        !self.contact.is_empty() && self.expires.is_some() && !self.is_expired(now)
+    }
+
+    /// A maintained disclosure channel: valid, reachable and not carrying a bad signature
+    pub fn is_disclosure_channel(&self, now: u64) -> bool {
+        self.is_valid(now)
+            && !self.reachable_contacts.is_empty()
+            && !matches!(self.signature, SignatureStatus::Invalid { .. })
+    }
+}
+
+/// Signed text and armored signature of a cleartext-signed message (RFC 4880 section 7)
+fn split_cleartext(text: &str) -> Option<(String, String)> {
+    let rest = text.trim_start().strip_prefix(SIGNED_MESSAGE_HEADER)?;
+    // Armor headers such as `Hash: SHA256` end at the first blank line
+    let (_, rest) = rest.split_once("\n\n").or_else(|| rest.split_once("\r\n\r\n"))?;
+    let (body, signature) = rest.split_once(SIGNATURE_HEADER)?;
+    let body: Vec<&str> = body.lines()
+        .map(|line| line.strip_prefix("- ").unwrap_or(line))
+        .collect();
+    Some((body.join("\n"), format!("{}{}", SIGNATURE_HEADER, signature)))
+}
+
+/// Check the cleartext signature of `text` against the armored public keys in `keys`
+pub fn verify_signature(text: &str, keys: &[String]) -> SignatureStatus {
+    let Some((body, armored)) = split_cleartext(text) else {
+        return SignatureStatus::Unsigned;
+    };
+    let signature = match StandaloneSignature::from_string(&armored) {
+        Ok((signature, _)) => signature,
+        Err(e) => return SignatureStatus::Invalid { reason: format!("unreadable signature: {}", e) },
+    };
+    let keys: Vec<SignedPublicKey> = keys.iter()
+        .filter_map(|armored| SignedPublicKey::from_string(armored).ok().map(|(key, _)| key))
+        .collect();
+    if keys.is_empty() {
+        return SignatureStatus::KeyUnavailable;
+    }
+
+    // Signed text uses CRLF line endings without trailing whitespace or a final line break
+    let canonical = body.split('\n')
+        .map(|line| line.trim_end_matches([' ', '\t', '\r']))
+        .collect::<Vec<_>>()
+        .join("\r\n");
+    let issuer = signature.signature.issuer();
+    let mut issuer_found = false;
+    for key in &keys {
+        // Without an issuer subpacket every advertised key is a candidate
+        let primary_is_issuer = issuer.is_none_or(|id| *id == key.key_id());
+        let subkeys: Vec<_> = key.public_subkeys.iter().filter(|subkey| issuer.is_none_or(|id| *id == subkey.key_id())).collect();
+        if !primary_is_issuer && subkeys.is_empty() {
+            continue;
+        }
+        issuer_found = true;
+        let primary_ok = primary_is_issuer && signature.verify(key, canonical.as_bytes()).is_ok();
+        if primary_ok || subkeys.iter().any(|subkey| signature.verify(*subkey, canonical.as_bytes()).is_ok()) {
+            return SignatureStatus::Verified { fingerprint: hex::encode_upper(key.fingerprint()) };
+        }
+    }
+    match issuer {
+        Some(id) if issuer_found => SignatureStatus::Invalid {
+            reason: format!("signature by key {} does not match the file", hex::encode_upper(id.as_ref())),
+        },
+        // Signed by a key the file does not advertise; that proves nothing either way
+        _ => SignatureStatus::KeyUnavailable,
+    }
+}
+
+/// Fetch security.txt from `base_url`, check its contacts and, when signed, its signature
+pub async fn fetch_security_txt(base_url: &str) -> Result<SecurityTxt> {
+    let base = Url::parse(base_url)?;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let mut last_error = None;
+    for path in [SECURITY_TXT_PATH, LEGACY_SECURITY_TXT_PATH] {
+        let url = base.join(path)?;
+        match fetch_plain_text(&client, url.as_str()).await {
+            Ok((final_url, text)) => {
+                let mut file = SecurityTxt::parse(&final_url, &text);
+                file.reachable_contacts = reachable_contacts(&client, &file.contact).await;
+                if file.signature != SignatureStatus::Unsigned {
+                    let keys = fetch_keys(&client, &file.encryption).await;
+                    file.signature = verify_signature(&text, &keys);
+                }
+                return Ok(file);
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("no security.txt at {}", base_url)))
+}
+
+/// Body of a `text/plain` response; catch-all HTML pages answering 200 are rejected
+async fn fetch_plain_text(client: &reqwest::Client, url: &str) -> Result<(String, String)> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("{} returned {}", url, response.status()));
+    }
+    let content_type = response.headers().get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if !content_type.starts_with("text/plain") {
+        return Err(anyhow::anyhow!("{} is {}, not text/plain", url, if content_type.is_empty() { "untyped" } else { &content_type }));
+    }
+    let final_url = response.url().to_string();
+    Ok((final_url, response.text().await?))
+}
+
+/// Contacts a researcher could use: mailto/tel with an address, https pages that load
+async fn reachable_contacts(client: &reqwest::Client, contacts: &[String]) -> Vec<String> {
+    let mut reachable = Vec::new();
+    for contact in contacts {
+        let lower = contact.to_ascii_lowercase();
+        let usable = if let Some(address) = lower.strip_prefix("mailto:") {
+            address.split_once('@').is_some_and(|(user, host)| !user.is_empty() && host.contains('.'))
+        } else if let Some(number) = lower.strip_prefix("tel:") {
+            number.chars().filter(char::is_ascii_digit).count() >= 5
+        } else if lower.starts_with("https://") {
+            client.get(contact).send().await.is_ok_and(|r| r.status().is_success())
+        } else {
+            false
+        };
+        if usable {
+            reachable.push(contact.clone());
+        }
+    }
+    reachable
+}
+
+/// Armored keys behind the https Encryption URIs; fingerprint and dns: URIs are not resolved
+async fn fetch_keys(client: &reqwest::Client, uris: &[String]) -> Vec<String> {
+    let mut keys = Vec::new();
+    for uri in uris.iter().filter(|uri| uri.to_ascii_lowercase().starts_with("https://")) {
+        if let Ok(response) = client.get(uri).send().await {
+            if response.status().is_success() {
+                if let Ok(text) = response.text().await {
+                    keys.push(text);
+                }
+            }
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const SIGNED: &str = include_str!("../fixtures/security_txt_signed.txt");
+    const KEY: &str = include_str!("../fixtures/security_txt_key.asc");
+    const OTHER_KEY: &str = include_str!("../fixtures/security_txt_other_key.asc");
+    /// 2025-01-01T00:00:00Z
+    const NOW: u64 = 1735689600;
+
+    #[test]
+    fn test_parse_signed_security_txt() {
+        let file = SecurityTxt::parse("https://example-project.com/.well-known/security.txt", SIGNED);
+        assert_eq!(file.contact, vec!["mailto:security@example-project.com", "https://example-project.com/security/report"]);
+        assert_eq!(file.expires, Some(4859740800));
+        assert_eq!(file.encryption, vec!["https://example-project.com/.well-known/pgp-key.asc"]);
+        assert_eq!(file.policy, vec!["https://example-project.com/security/policy"]);
+        assert_eq!(file.acknowledgments, vec!["https://example-project.com/security/hall-of-fame"]);
+        assert_eq!(file.preferred_languages.as_deref(), Some("en, de"));
+        assert_eq!(file.signature, SignatureStatus::KeyUnavailable);
+        assert!(file.problems.is_empty(), "{:?}", file.problems);
+        assert!(file.is_valid(NOW));
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        match verify_signature(SIGNED, &[KEY.to_string()]) {
+            SignatureStatus::Verified { fingerprint } => assert_eq!(fingerprint, "9C859D780FBCA97F0F722492A7FBE9F04BC317F5"),
+            other => panic!("expected a verified signature, got {:?}", other),
+        }
+        assert_eq!(verify_signature(SIGNED, &[]), SignatureStatus::KeyUnavailable);
+
+        let tampered = SIGNED.replace("mailto:security@", "mailto:payouts@");
+        assert!(matches!(verify_signature(&tampered, &[KEY.to_string()]), SignatureStatus::Invalid { .. }));
+
+        // An Encryption key need not be the signing key; a signer outside the advertised keys is unknown, not invalid
+        assert_eq!(verify_signature(SIGNED, &[OTHER_KEY.to_string()]), SignatureStatus::KeyUnavailable);
+        assert!(matches!(
+            verify_signature(SIGNED, &[OTHER_KEY.to_string(), KEY.to_string()]),
+            SignatureStatus::Verified { .. }
+        ));
+        assert_eq!(verify_signature("Contact: mailto:a@b.example\n", &[KEY.to_string()]), SignatureStatus::Unsigned);
+    }
+
+    #[test]
+    fn test_rfc_violations() {
+        let file = SecurityTxt::parse(
+            "https://example-project.com/security.txt",
+            "Contact: http://example-project.com/report\nExpires: soon\nExpires: 2020-01-01T00:00:00Z\n\
+             Canonical: https://example-project.com/.well-known/security.txt\n",
+        );
+        assert_eq!(file.problems, vec![
+            "Contact http://example-project.com/report is not a mailto:, tel: or https: URI",
+            "Expires \"soon\" is not an RFC 3339 date",
+            "Expires appears 2 times",
+            "https://example-project.com/security.txt is not listed as Canonical",
+        ]);
+        assert!(file.is_expired(NOW));
+        assert!(!file.is_valid(NOW));
+        assert!(!SecurityTxt::parse("fixture", "# nothing here\n").is_valid(NOW));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_security_txt() {
+        let server = test_http::serve(vec![
+            (SECURITY_TXT_PATH, MockResponse::ok(
+                "Contact: {base_url}/report\nContact: mailto:security@example-project.com\n\
+                 Expires: 2124-01-01T00:00:00Z\n",
+            ).with_header("Content-Type", "text/plain; charset=utf-8")),
+            ("/report", MockResponse::ok("Report a vulnerability")),
+        ]).await;
+
+        // The mock's plain-http contact breaks RFC 9116 and never counts as reachable
+        let file = fetch_security_txt(&server.base_url).await.unwrap();
+        assert_eq!(file.url, format!("{}{}", server.base_url, SECURITY_TXT_PATH));
+        assert_eq!(file.problems.len(), 1);
+        assert_eq!(file.reachable_contacts, vec!["mailto:security@example-project.com"]);
+        assert!(file.is_disclosure_channel(NOW));
+        assert_eq!(file.signature, SignatureStatus::Unsigned);
+    }
+
+    #[tokio::test]
+    async fn test_html_catch_all_is_not_security_txt() {
+        let server = test_http::serve(vec![
+            (SECURITY_TXT_PATH, MockResponse::ok("<html><body>Contact: us</body></html>").with_header("Content-Type", "text/html")),
+            (LEGACY_SECURITY_TXT_PATH, MockResponse::ok(SIGNED).with_header("Content-Type", "text/plain")),
+        ]).await;
+
+        // Falls back to the legacy path; the advertised key is not on this host
+        let file = fetch_security_txt(&server.base_url).await.unwrap();
+        assert_eq!(file.url, format!("{}{}", server.base_url, LEGACY_SECURITY_TXT_PATH));
+        assert_eq!(file.signature, SignatureStatus::KeyUnavailable);
+
+        let server = test_http::serve(vec![
+            (SECURITY_TXT_PATH, MockResponse::ok("<html></html>").with_header("Content-Type", "text/html")),
+        ]).await;
+        let error = fetch_security_txt(&server.base_url).await.unwrap_err();
+        assert!(error.to_string().contains("404"));
+    }
+}
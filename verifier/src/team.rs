@@ -0,0 +1,490 @@
+use anyhow::Result;
+use scraper::{ElementRef, Html};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use url::Url;
+
+use crate::crawler::CRAWLER_AGENT;
+use crate::github::GitHubClient;
+use crate::page_analysis::{collapse_whitespace, same_site, selector};
+
+/// Team members whose GitHub accounts are looked up, to bound API usage
+const MAX_CHECKED_MEMBERS: usize = 20;
+/// GitHub accounts younger than this are treated as created for the occasion
+pub const MIN_ACCOUNT_AGE_DAYS: u64 = 180;
+/// Commits that make a young account's contribution a history rather than a token commit
+const MIN_CONTRIBUTIONS: u32 = 20;
+/// Pages of 100 contributors read from the repository, to bound API usage
+const MAX_CONTRIBUTOR_PAGES: u32 = 5;
+/// Ancestors climbed from a profile link looking for the card that names its owner
+const MAX_CARD_DEPTH: usize = 5;
+
+/// Elements that name a person, most specific first; a card must have exactly one in the first tier present
+const NAME_TIERS: &[&str] = &[
+    "[itemprop=name]", "[class*=name]", "h2", "h3", "h4", "h5", "h6", "strong", "b",
+];
+const ROLE_SELECTOR: &str = "[itemprop=jobTitle], [class*=role], [class*=position], [class*=title]";
+/// Containers sites use for one person
+const CARD_SELECTOR: &str = r#"[class*=member], [class*=person], [class*=founder], [class*=profile], [itemtype*="schema.org/Person"]"#;
+
+/// Words that make a capitalized phrase a heading or a job title rather than a name
+const NOT_NAME_WORDS: &[&str] = &[
+    "team", "our", "meet", "the", "core", "contributors", "advisors", "advisory", "founders", "founding",
+    "leadership", "board", "members", "people", "about", "us", "ceo", "cto", "coo", "cfo", "founder",
+    "co-founder", "cofounder", "engineer", "engineering", "developer", "lead", "head", "chief", "officer",
+    "director", "manager", "advisor", "designer", "researcher", "marketing", "operations", "product",
+    "community", "partners", "investors", "join", "careers", "contact",
+];
+
+/// Path segments of github.com that are site pages rather than accounts
+const GITHUB_RESERVED: &[&str] = &[
+    "about", "features", "pricing", "orgs", "sponsors", "topics", "collections", "marketplace",
+    "login", "join", "settings", "enterprise", "explore", "trending", "apps",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProfileKind {
+    GitHub,
+    Twitter,
+    LinkedIn,
+    /// Personal site or any other external page in the person's card
+    Website,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub kind: ProfileKind,
+    pub url: String,
+    /// Account name on GitHub, Twitter/X or LinkedIn
+    pub handle: Option<String>,
+}
+
+impl Profile {
+    pub fn from_url(url: &str) -> Option<Self> {
+        let parsed = Url::parse(url).ok()?;
+        let host = parsed.host_str()?.trim_start_matches("www.").to_ascii_lowercase();
+        let segments: Vec<&str> = parsed.path_segments()
+            .map(|s| s.filter(|segment| !segment.is_empty()).collect())
+            .unwrap_or_default();
+        let (kind, handle) = match (host.as_str(), segments.as_slice()) {
+            ("github.com", [login]) if !GITHUB_RESERVED.contains(&login.to_ascii_lowercase().as_str()) => {
+                (ProfileKind::GitHub, Some(login.to_string()))
+            }
+            // Repository and organization pages do not identify a person
+            ("github.com", _) => return None,
+            ("twitter.com" | "x.com", [handle, ..]) if !["intent", "share", "home", "i"].contains(handle) => {
+                (ProfileKind::Twitter, Some(handle.trim_start_matches('@').to_string()))
+            }
+            ("linkedin.com", ["in", handle, ..]) => (ProfileKind::LinkedIn, Some(handle.to_string())),
+            ("twitter.com" | "x.com" | "linkedin.com", _) => return None,
+            _ => (ProfileKind::Website, None),
+        };
+        Some(Self { kind, url: url.to_string(), handle })
+    }
+}
+
+/// A linked GitHub account as the API reports it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitHubAccount {
+    pub login: String,
+    pub created_at: Option<u64>,
+    pub public_repos: u32,
+    /// Commits to the project's repository
+    pub contributions: u32,
+}
+
+/// How far a team member's identity could be confirmed, weakest first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationLevel {
+    /// The linked GitHub account does not exist
+    GitHubNotFound { login: String },
+    /// A name and nothing to check it against
+    NameOnly,
+    /// Social or personal profiles that cannot be checked automatically
+    ProfilesLinked,
+    /// The GitHub account exists but has not contributed to the project's repository
+    GitHubAccount { login: String, age_days: Option<u64> },
+    /// The GitHub account contributed to the project's repository
+    Contributor { login: String, age_days: Option<u64>, contributions: u32 },
+}
+
+impl VerificationLevel {
+    /// A contributor whose account or commit history predates the project's need for a team page
+    pub fn is_established_contributor(&self) -> bool {
+        match self {
+            VerificationLevel::Contributor { age_days, contributions, .. } => {
+                age_days.is_some_and(|days| days >= MIN_ACCOUNT_AGE_DAYS) || *contributions >= MIN_CONTRIBUTIONS
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for VerificationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let age = |days: &Option<u64>| days.map(|d| format!(", account {} days old", d)).unwrap_or_default();
+        match self {
+            VerificationLevel::GitHubNotFound { login } => write!(f, "GitHub account @{} does not exist", login),
+            VerificationLevel::NameOnly => f.write_str("name only"),
+            VerificationLevel::ProfilesLinked => f.write_str("unchecked profiles"),
+            VerificationLevel::GitHubAccount { login, age_days } => write!(f, "GitHub @{}{}", login, age(age_days)),
+            VerificationLevel::Contributor { login, age_days, contributions } => {
+                write!(f, "GitHub @{} with {} commits to the project{}", login, contributions, age(age_days))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamMember {
+    pub name: String,
+    pub role: Option<String>,
+    pub profiles: Vec<Profile>,
+    pub github: Option<GitHubAccount>,
+    pub level: VerificationLevel,
+}
+
+impl TeamMember {
+    fn new(name: String, role: Option<String>, profiles: Vec<Profile>) -> Self {
+        let level = unchecked_level(&profiles);
+        Self { name, role, profiles, github: None, level }
+    }
+
+    pub fn github_login(&self) -> Option<&str> {
+        self.profiles.iter()
+            .find(|p| p.kind == ProfileKind::GitHub)
+            .and_then(|p| p.handle.as_deref())
+    }
+}
+
+impl std::fmt::Display for TeamMember {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.role {
+            Some(role) => write!(f, "{} ({}): {}", self.name, role, self.level),
+            None => write!(f, "{}: {}", self.name, self.level),
+        }
+    }
+}
+
+/// Named people on a team page, from JSON-LD `Person` entities and from per-person cards
+pub fn extract_team(url: &str, html: &str) -> Vec<TeamMember> {
+    let document = Html::parse_document(html);
+    let base = Url::parse(url).ok();
+    let mut members: Vec<TeamMember> = Vec::new();
+    let mut add = |member: TeamMember| {
+        match members.iter_mut().find(|m| m.name == member.name) {
+            Some(existing) => {
+                for profile in member.profiles {
+                    if !existing.profiles.iter().any(|p| p.url == profile.url) {
+                        existing.profiles.push(profile);
+                    }
+                }
+                existing.role = existing.role.take().or(member.role);
+                existing.level = unchecked_level(&existing.profiles);
+            }
+            None => members.push(member),
+        }
+    };
+
+    for script in document.select(&selector(r#"script[type="application/ld+json"]"#)) {
+        if let Ok(value) = serde_json::from_str::<Value>(&script.text().collect::<String>()) {
+            json_ld_people(&value, &mut add);
+        }
+    }
+
+    // Cards marked up as such, and the nearest named ancestor of every profile link
+    let mut cards: Vec<ElementRef> = document.select(&selector(CARD_SELECTOR)).collect();
+    for anchor in document.select(&selector("a[href]")) {
+        let href = anchor.value().attr("href").unwrap_or_default();
+        let is_profile = base.as_ref()
+            .and_then(|base| base.join(href).ok())
+            .and_then(|target| Profile::from_url(target.as_str()))
+            .is_some_and(|profile| profile.kind != ProfileKind::Website);
+        if !is_profile {
+            continue;
+        }
+        let card = anchor.ancestors()
+            .filter_map(ElementRef::wrap)
+            .take(MAX_CARD_DEPTH)
+            .find(|element| card_name(*element).is_some());
+        if let Some(card) = card {
+            cards.push(card);
+        }
+    }
+
+    let mut seen = Vec::new();
+    for card in cards {
+        if seen.contains(&card.id()) {
+            continue;
+        }
+        seen.push(card.id());
+        let Some(name) = card_name(card) else { continue };
+        let role = card.select(&selector(ROLE_SELECTOR))
+            .map(|element| collapse_whitespace(&element.text().collect::<String>()))
+            .find(|role| !role.is_empty() && *role != name && role.len() <= 60);
+        let profiles = card.select(&selector("a[href]"))
+            .filter_map(|anchor| base.as_ref()?.join(anchor.value().attr("href")?).ok())
+            .filter(|target| !same_site(base.as_ref(), target))
+            .filter_map(|target| Profile::from_url(target.as_str()))
+            .fold(Vec::new(), |mut profiles: Vec<Profile>, profile| {
+                if !profiles.iter().any(|p| p.url == profile.url) {
+                    profiles.push(profile);
+                }
+                profiles
+            });
+        add(TeamMember::new(name, role, profiles));
+    }
+    members
+}
+
+/// Level of a member before any profile has been looked up
+fn unchecked_level(profiles: &[Profile]) -> VerificationLevel {
+    if profiles.is_empty() { VerificationLevel::NameOnly } else { VerificationLevel::ProfilesLinked }
+}
+
+/// The single person named in `card`, if it names exactly one
+fn card_name(card: ElementRef) -> Option<String> {
+    for tier in NAME_TIERS {
+        let mut names: Vec<String> = card.select(&selector(tier))
+            .map(|element| collapse_whitespace(&element.text().collect::<String>()))
+            .filter(|text| looks_like_name(text))
+            .collect();
+        names.dedup();
+        match names.len() {
+            0 => continue,
+            1 => return names.pop(),
+            // A container holding several people
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Two to four capitalized words, none of them a heading or job-title word
+fn looks_like_name(text: &str) -> bool {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    (2..=4).contains(&words.len())
+        && text.chars().count() <= 50
+        && words.iter().all(|word| {
+            word.chars().next().is_some_and(char::is_uppercase)
+                && word.chars().all(|c| c.is_alphabetic() || matches!(c, '.' | '-' | '\''))
+                && !NOT_NAME_WORDS.contains(&word.to_lowercase().as_str())
+        })
+}
+
+fn json_ld_people(value: &Value, add: &mut impl FnMut(TeamMember)) {
+    match value {
+        Value::Object(map) => {
+            let is_person = match map.get("@type") {
+                Some(Value::String(t)) => t == "Person",
+                Some(Value::Array(types)) => types.iter().any(|t| t == "Person"),
+                _ => false,
+            };
+            let name = map.get("name").and_then(Value::as_str).map(collapse_whitespace);
+            if let (true, Some(name)) = (is_person, name) {
+                let links = match map.get("sameAs") {
+                    Some(Value::String(url)) => vec![url.clone()],
+                    Some(Value::Array(urls)) => urls.iter().filter_map(|u| u.as_str().map(str::to_string)).collect(),
+                    _ => Vec::new(),
+                };
+                let profiles = links.iter()
+                    .chain(map.get("url").and_then(Value::as_str).map(str::to_string).as_ref())
+                    .filter_map(|url| Profile::from_url(url))
+                    .collect();
+                let role = map.get("jobTitle").and_then(Value::as_str).map(str::to_string);
+                add(TeamMember::new(name, role, profiles));
+            }
+            map.values().for_each(|v| json_ld_people(v, add));
+        }
+        Value::Array(items) => items.iter().for_each(|v| json_ld_people(v, add)),
+        _ => {}
+    }
+}
+
+/// Fetch the team page at `url` and extract its members
+pub async fn fetch_team(url: &str) -> Result<Vec<TeamMember>> {
+    let url = url.split('#').next().unwrap_or_default();
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .user_agent(format!("{}/{}", CRAWLER_AGENT, env!("CARGO_PKG_VERSION")))
+        .build()?;
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("{} returned {}", url, response.status()));
+    }
+    let final_url = response.url().to_string();
+    Ok(extract_team(&final_url, &response.text().await?))
+}
+
+#[derive(Deserialize)]
+struct GitHubUser {
+    login: String,
+    created_at: Option<String>,
+    #[serde(default)]
+    public_repos: u32,
+}
+
+#[derive(Deserialize)]
+struct GitHubContributor {
+    login: String,
+    contributions: u32,
+}
+
+/// Look up each member's GitHub account and whether it contributed to `repo` (`owner/name`);
+/// members checked before an error keep their new level
+pub async fn verify_members(
+    github: &GitHubClient,
+    repo: Option<&str>,
+    members: &mut [TeamMember],
+    now: u64,
+) -> Result<()> {
+    let mut contributors: HashMap<String, u32> = HashMap::new();
+    if let Some(repo) = repo {
+        for page in 1..=MAX_CONTRIBUTOR_PAGES {
+            let response = github.get(&format!("/repos/{}/contributors?per_page=100&page={}", repo, page)).await?;
+            if !response.is_success() {
+                break;
+            }
+            let listed = response.json::<Vec<GitHubContributor>>()?;
+            let last_page = listed.len() < 100;
+            for contributor in listed {
+                contributors.insert(contributor.login.to_ascii_lowercase(), contributor.contributions);
+            }
+            if last_page {
+                break;
+            }
+        }
+    }
+
+    for member in members.iter_mut().take(MAX_CHECKED_MEMBERS) {
+        let Some(login) = member.github_login().map(str::to_string) else { continue };
+        let response = github.get(&format!("/users/{}", login)).await?;
+        if response.status == 404 {
+            member.level = VerificationLevel::GitHubNotFound { login };
+            continue;
+        }
+        if !response.is_success() {
+            // Rate limited or unavailable; the member keeps the unchecked level
+            continue;
+        }
+        let user: GitHubUser = response.json()?;
+        let created_at = user.created_at.as_deref()
+            .and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok())
+            .and_then(|date| u64::try_from(date.timestamp()).ok());
+        let age_days = created_at.map(|created| now.saturating_sub(created) / (24 * 60 * 60));
+        let contributions = contributors.get(&user.login.to_ascii_lowercase()).copied().unwrap_or(0);
+        member.level = if contributions > 0 {
+            VerificationLevel::Contributor { login: user.login.clone(), age_days, contributions }
+        } else {
+            VerificationLevel::GitHubAccount { login: user.login.clone(), age_days }
+        };
+        member.github = Some(GitHubAccount { login: user.login, created_at, public_repos: user.public_repos, contributions });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const TEAM_PAGE: &str = r#"<html><head>
+        <script type="application/ld+json">{"@context": "https://schema.org", "@type": "Organization",
+            "founder": {"@type": "Person", "name": "Ada Example", "jobTitle": "Founder",
+                        "sameAs": ["https://github.com/ada-example", "https://ada.example.org/"]}}</script>
+        </head><body>
+        <h2>Meet the Team</h2>
+        <div class="team-grid">
+          <div class="team-member">
+            <h3>Ada Example</h3><p class="role">CEO</p>
+            <a href="https://twitter.com/ada_example">Twitter</a>
+          </div>
+          <div class="team-member">
+            <h3>Grace Sample</h3><h4>Lead Engineer</h4>
+            <a href="https://github.com/gracesample">GitHub</a>
+            <a href="/blog/grace">Posts</a>
+          </div>
+          <article><strong>Linus Demo</strong> <a href="https://www.linkedin.com/in/linus-demo/">LinkedIn</a></article>
+          <div class="person"><h3>Alan Nobody</h3></div>
+          <div class="team-member"><h3>Ghost Account</h3><a href="https://github.com/ghost-account-404">GitHub</a></div>
+        </div>
+        <a href="https://github.com/example-project/protocol">Source code</a>
+        </body></html>"#;
+
+    #[test]
+    fn test_extract_team() {
+        let members = extract_team("https://example-project.com/team", TEAM_PAGE);
+        let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["Ada Example", "Grace Sample", "Alan Nobody", "Ghost Account", "Linus Demo"]);
+
+        // JSON-LD and the card for the same person are merged
+        let ada = &members[0];
+        assert_eq!(ada.role.as_deref(), Some("Founder"));
+        assert_eq!(ada.github_login(), Some("ada-example"));
+        let kinds: Vec<ProfileKind> = ada.profiles.iter().map(|p| p.kind).collect();
+        assert_eq!(kinds, vec![ProfileKind::GitHub, ProfileKind::Website, ProfileKind::Twitter]);
+
+        assert_eq!(members[1].role, None);
+        assert_eq!(members[1].profiles.len(), 1);
+        assert_eq!(members[2].level, VerificationLevel::NameOnly);
+        assert_eq!(members[4].profiles[0].handle.as_deref(), Some("linus-demo"));
+        assert_eq!(members[4].level, VerificationLevel::ProfilesLinked);
+    }
+
+    #[test]
+    fn test_profile_from_url() {
+        assert_eq!(Profile::from_url("https://github.com/ada-example/").unwrap().handle.as_deref(), Some("ada-example"));
+        assert!(Profile::from_url("https://github.com/example-project/protocol").is_none());
+        assert!(Profile::from_url("https://github.com/sponsors").is_none());
+        assert_eq!(Profile::from_url("https://x.com/ada_example").unwrap().kind, ProfileKind::Twitter);
+        assert!(Profile::from_url("https://twitter.com/intent/tweet?text=hi").is_none());
+        assert_eq!(Profile::from_url("https://ada.example.org/").unwrap().kind, ProfileKind::Website);
+        assert!(!looks_like_name("Chief Technology Officer"));
+        assert!(!looks_like_name("Our Team"));
+        assert!(looks_like_name("Jean-Luc O'Neill"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_members() {
+        let first_page: Vec<String> = (0..100).map(|i| format!(r#"{{"login": "contributor-{}", "contributions": 300}}"#, i)).collect();
+        let server = test_http::serve(vec![
+            // Grace is past the first page of contributors
+            ("/repos/example-project/protocol/contributors", MockResponse::ok(format!("[{}]", first_page.join(", ")))),
+            ("/repos/example-project/protocol/contributors", MockResponse::ok(
+                r#"[{"login": "GraceSample", "contributions": 212}, {"login": "someone-else", "contributions": 3}]"#,
+            )),
+            ("/users/ada-example", MockResponse::ok(r#"{"login": "ada-example", "created_at": "2024-12-01T00:00:00Z", "public_repos": 2}"#)),
+            ("/users/gracesample", MockResponse::ok(r#"{"login": "GraceSample", "created_at": "2015-01-01T00:00:00Z", "public_repos": 40}"#)),
+        ]).await;
+        let mut members = extract_team("https://example-project.com/team", TEAM_PAGE);
+        let now = 1735689600;
+
+        let github = GitHubClient::new(&server.base_url, Some("test-token".to_string()));
+        verify_members(&github, Some("example-project/protocol"), &mut members, now).await.unwrap();
+        assert_eq!(members[0].level, VerificationLevel::GitHubAccount { login: "ada-example".to_string(), age_days: Some(31) });
+        assert_eq!(members[1].level, VerificationLevel::Contributor {
+            login: "GraceSample".to_string(),
+            age_days: Some(3653),
+            contributions: 212,
+        });
+        assert_eq!(members[1].github.as_ref().unwrap().public_repos, 40);
+        assert_eq!(members[3].level, VerificationLevel::GitHubNotFound { login: "ghost-account-404".to_string() });
+        assert_eq!(members[4].level, VerificationLevel::ProfilesLinked);
+
+        let requests = server.requests.lock().unwrap();
+        assert!(requests[0].to_ascii_lowercase().contains("authorization: bearer test-token"));
+        assert!(requests[0].contains(CRAWLER_AGENT));
+        assert_eq!(requests.iter().filter(|r| r.contains("/contributors")).count(), 2);
+    }
+
+    #[test]
+    fn test_established_contributor_needs_age_or_history() {
+        let contributor = |age_days, contributions| VerificationLevel::Contributor { login: "gracesample".to_string(), age_days: Some(age_days), contributions };
+        assert!(!contributor(20, 4).is_established_contributor());
+        assert!(contributor(20, 25).is_established_contributor());
+        assert!(contributor(400, 1).is_established_contributor());
+        assert!(!VerificationLevel::GitHubAccount { login: "gracesample".to_string(), age_days: Some(400) }.is_established_contributor());
+    }
+}
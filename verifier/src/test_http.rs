@@ -0,0 +1,95 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self { status: 200, headers: Vec::new(), body: body.into() }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self { status, headers: Vec::new(), body: Vec::new() }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+pub struct MockServer {
+    /// `http://127.0.0.1:<port>` without a trailing slash
+    pub base_url: String,
+    /// Raw request heads in the order they arrived
+    pub requests: Arc<Mutex<Vec<String>>>,
+}
+
+/// Serve `routes` keyed by path (optionally with query). Repeated paths are answered in order,
+/// the last response repeating; unknown paths get a 404. `{base_url}` in a body is replaced
+/// with the server's own base URL.
+pub async fn serve(routes: Vec<(&str, MockResponse)>) -> MockServer {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let mut table: HashMap<String, VecDeque<MockResponse>> = HashMap::new();
+    for (path, mut response) in routes {
+        if let Ok(body) = std::str::from_utf8(&response.body) {
+            response.body = body.replace("{base_url}", &base_url).into_bytes();
+        }
+        table.entry(path.to_string()).or_default().push_back(response);
+    }
+    let table = Arc::new(Mutex::new(table));
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let log = requests.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let table = table.clone();
+            let log = log.clone();
+            tokio::spawn(async move {
+                let mut head = Vec::new();
+                let mut buf = [0u8; 4096];
+                while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => head.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let head = String::from_utf8_lossy(&head).into_owned();
+                let target = head.split_whitespace().nth(1).unwrap_or("/").to_string();
+                log.lock().unwrap().push(head);
+
+                let response = {
+                    let mut table = table.lock().unwrap();
+                    let path = target.split('?').next().unwrap_or_default().to_string();
+                    let key = if table.contains_key(&target) { target } else { path };
+                    match table.get_mut(&key) {
+                        Some(queue) if queue.len() > 1 => queue.pop_front(),
+                        Some(queue) => queue.front().cloned(),
+                        None => None,
+                    }
+                    .unwrap_or_else(|| MockResponse::status(404))
+                };
+
+                let mut out = format!("HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n", response.status, response.body.len());
+                for (name, value) in &response.headers {
+                    out.push_str(&format!("{}: {}\r\n", name, value));
+                }
+                out.push_str("\r\n");
+                let mut bytes = out.into_bytes();
+                bytes.extend_from_slice(&response.body);
+                let _ = stream.write_all(&bytes).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+
+    MockServer { base_url, requests }
+}
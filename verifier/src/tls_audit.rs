@@ -0,0 +1,318 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use crate::chain_validation;
+use crate::tls_handshake::HandshakeCapture;
+
+const TLS1_0: u16 = 0x0301;
+const TLS1_1: u16 = 0x0302;
+const TLS1_2: u16 = 0x0303;
+
+/// CBC suites available before TLS 1.2, with and without ECDHE
+const LEGACY_SUITES: &[u16] = &[0xc009, 0xc00a, 0xc013, 0xc014, 0x002f, 0x0035, 0x000a];
+/// TLS 1.2 suites using static RSA key transport, which has no forward secrecy
+const STATIC_RSA_SUITES: &[u16] = &[0x009c, 0x009d, 0x003c, 0x003d, 0x002f, 0x0035, 0x000a];
+
+/// Minimum RSA modulus size considered acceptable
+const MIN_RSA_BITS: usize = 2048;
+
+/// Negotiated TLS parameters plus what the server still accepts from older clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsConfigurationAudit {
+    pub protocol_version: Option<String>,
+    pub cipher_suite: Option<String>,
+    pub alpn_protocol: Option<String>,
+    pub key_exchange_group: Option<String>,
+    pub leaf_key_type: String,
+    pub leaf_key_bits: usize,
+    /// Legacy protocol versions the server completed a ServerHello for
+    pub legacy_versions_accepted: Vec<String>,
+    /// Whether the server agreed to a static RSA key exchange suite
+    pub static_rsa_accepted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TlsWeakness {
+    LegacyProtocol(String),
+    WeakRsaKey(usize),
+    /// The negotiated suite has no ephemeral key exchange
+    NoForwardSecrecy,
+    /// Clients that offer only static RSA key transport are still served
+    StaticRsaAccepted,
+}
+
+impl std::fmt::Display for TlsWeakness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TlsWeakness::LegacyProtocol(version) => write!(f, "{} offered", version),
+            TlsWeakness::WeakRsaKey(bits) => write!(f, "RSA key of only {} bits", bits),
+            TlsWeakness::NoForwardSecrecy => f.write_str("negotiated key exchange lacks forward secrecy"),
+            TlsWeakness::StaticRsaAccepted => f.write_str("RSA key exchange still accepted"),
+        }
+    }
+}
+
+impl TlsConfigurationAudit {
+    /// The suite the server chose for a modern client uses an ephemeral key exchange
+    pub fn forward_secrecy(&self) -> bool {
+        self.cipher_suite.as_deref()
+            .map(|suite| suite.starts_with("TLS13_") || suite.contains("DHE_"))
+            .unwrap_or(false)
+    }
+
+    pub fn weaknesses(&self) -> Vec<TlsWeakness> {
+        let mut weaknesses: Vec<TlsWeakness> = self.legacy_versions_accepted.iter()
+            .cloned()
+            .map(TlsWeakness::LegacyProtocol)
+            .collect();
+        if self.leaf_key_type == "RSA" && self.leaf_key_bits < MIN_RSA_BITS {
+            weaknesses.push(TlsWeakness::WeakRsaKey(self.leaf_key_bits));
+        }
+        if !self.forward_secrecy() {
+            weaknesses.push(TlsWeakness::NoForwardSecrecy);
+        } else if self.static_rsa_accepted {
+            // Only clients that ask for it lose forward secrecy; common for compatibility
+            weaknesses.push(TlsWeakness::StaticRsaAccepted);
+        }
+        weaknesses
+    }
+}
+
+/// Combine the negotiated handshake parameters with probes for legacy protocol support
+pub async fn audit_configuration(domain: &str, port: u16, timeout: Duration, capture: &HandshakeCapture) -> TlsConfigurationAudit {
+    let (leaf_key_type, leaf_key_bits) = capture.peer_certificates.first()
+        .and_then(|der| X509Certificate::from_der(der).ok())
+        .map(|(_, cert)| chain_validation::key_description(&cert))
+        .unwrap_or_else(|| ("unknown".to_string(), 0));
+
+    let host = domain.to_string();
+    let (legacy_versions_accepted, static_rsa_accepted) = tokio::task::spawn_blocking(move || {
+        let mut legacy = Vec::new();
+        for version in [TLS1_0, TLS1_1] {
+            if let Ok(Some((accepted, _))) = probe_server_hello(&host, port, timeout, version, LEGACY_SUITES) {
+                if accepted == version {
+                    legacy.push(protocol_name(version));
+                }
+            }
+        }
+        let static_rsa = matches!(
+            probe_server_hello(&host, port, timeout, TLS1_2, STATIC_RSA_SUITES),
+            Ok(Some((_, suite))) if STATIC_RSA_SUITES.contains(&suite)
+        );
+        (legacy, static_rsa)
+    })
+    .await
+    .unwrap_or_default();
+
+    TlsConfigurationAudit {
+        protocol_version: capture.protocol_version.map(protocol_name),
+        cipher_suite: capture.cipher_suite.clone(),
+        alpn_protocol: capture.alpn_protocol.clone(),
+        key_exchange_group: capture.key_exchange_group.map(group_name),
+        leaf_key_type,
+        leaf_key_bits,
+        legacy_versions_accepted,
+        static_rsa_accepted,
+    }
+}
+
+/// Send a bare ClientHello and return the (version, cipher suite) of the ServerHello, if any
+fn probe_server_hello(domain: &str, port: u16, timeout: Duration, version: u16, suites: &[u16]) -> Result<Option<(u16, u16)>> {
+    let addr = (domain, port)
+        .to_socket_addrs()
+        .with_context(|| format!("Failed to resolve {}", domain))?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No addresses found for {}", domain))?;
+    let mut sock = TcpStream::connect_timeout(&addr, timeout)?;
+    sock.set_read_timeout(Some(timeout))?;
+    sock.set_write_timeout(Some(timeout))?;
+    sock.write_all(&client_hello(domain, version, suites))?;
+
+    let mut response = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        if response.len() >= 5 {
+            let len = u16::from_be_bytes([response[3], response[4]]) as usize;
+            if response.len() >= 5 + len {
+                break;
+            }
+        }
+        let n = sock.read(&mut buf)?;
+        if n == 0 {
+            // Dropping the connection is how many servers refuse a version
+            return Ok(None);
+        }
+        response.extend_from_slice(&buf[..n]);
+    }
+    Ok(parse_server_hello(&response))
+}
+
+fn parse_server_hello(response: &[u8]) -> Option<(u16, u16)> {
+    // handshake record carrying a ServerHello: version(2) random(32) session_id<1> cipher_suite(2)
+    if response.first() != Some(&0x16) || response.get(5) != Some(&2) {
+        return None;
+    }
+    let body = response.get(9..)?;
+    let version = u16::from_be_bytes([*body.first()?, *body.get(1)?]);
+    let session_id_len = *body.get(34)? as usize;
+    let suite = u16::from_be_bytes([*body.get(35 + session_id_len)?, *body.get(36 + session_id_len)?]);
+    Some((version, suite))
+}
+
+fn client_hello(domain: &str, version: u16, suites: &[u16]) -> Vec<u8> {
+    use rand::RngCore;
+
+    let mut body = version.to_be_bytes().to_vec();
+    let mut random = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut random);
+    body.extend_from_slice(&random);
+    body.push(0);
+    body.extend_from_slice(&((suites.len() * 2) as u16).to_be_bytes());
+    for suite in suites {
+        body.extend_from_slice(&suite.to_be_bytes());
+    }
+    body.extend_from_slice(&[0x01, 0x00]);
+
+    let mut extensions = Vec::new();
+    if domain.parse::<IpAddr>().is_err() {
+        let name = domain.as_bytes();
+        let mut sni = ((name.len() + 3) as u16).to_be_bytes().to_vec();
+        sni.push(0);
+        sni.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        sni.extend_from_slice(name);
+        push_extension(&mut extensions, 0x0000, &sni);
+    }
+    // supported_groups: secp256r1, secp384r1, x25519
+    push_extension(&mut extensions, 0x000a, &[0x00, 0x06, 0x00, 0x17, 0x00, 0x18, 0x00, 0x1d]);
+    push_extension(&mut extensions, 0x000b, &[0x01, 0x00]);
+    if version >= TLS1_2 {
+        push_extension(&mut extensions, 0x000d, &[0x00, 0x08, 0x04, 0x01, 0x04, 0x03, 0x08, 0x04, 0x05, 0x01]);
+    }
+    push_extension(&mut extensions, 0xff01, &[0x00]);
+    body.extend_from_slice(&(extensions.len() as u16).to_be_bytes());
+    body.extend_from_slice(&extensions);
+
+    let mut handshake = vec![0x01];
+    handshake.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+    handshake.extend_from_slice(&body);
+
+    let mut record = vec![0x16, 0x03, 0x01];
+    record.extend_from_slice(&(handshake.len() as u16).to_be_bytes());
+    record.extend_from_slice(&handshake);
+    record
+}
+
+fn push_extension(out: &mut Vec<u8>, ext_type: u16, data: &[u8]) {
+    out.extend_from_slice(&ext_type.to_be_bytes());
+    out.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    out.extend_from_slice(data);
+}
+
+fn protocol_name(version: u16) -> String {
+    match version {
+        TLS1_0 => "TLS 1.0".to_string(),
+        TLS1_1 => "TLS 1.1".to_string(),
+        TLS1_2 => "TLS 1.2".to_string(),
+        0x0304 => "TLS 1.3".to_string(),
+        other => format!("0x{:04x}", other),
+    }
+}
+
+fn group_name(group: u16) -> String {
+    match group {
+        0x0017 => "secp256r1".to_string(),
+        0x0018 => "secp384r1".to_string(),
+        0x0019 => "secp521r1".to_string(),
+        0x001d => "x25519".to_string(),
+        0x001e => "x448".to_string(),
+        0x0100 => "ffdhe2048".to_string(),
+        0x0101 => "ffdhe3072".to_string(),
+        other => format!("0x{:04x}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    /// Answer one connection with a ServerHello for `version` and `suite`, or with an alert
+    fn mock_server(reply: Option<(u16, u16)>) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            let response = match reply {
+                Some((version, suite)) => {
+                    let mut body = version.to_be_bytes().to_vec();
+                    body.extend_from_slice(&[0u8; 32]);
+                    body.push(0);
+                    body.extend_from_slice(&suite.to_be_bytes());
+                    body.extend_from_slice(&[0x00, 0x00, 0x00]);
+                    let mut record = vec![0x16, 0x03, 0x01, 0x00, (body.len() + 4) as u8, 0x02, 0x00, 0x00, body.len() as u8];
+                    record.extend_from_slice(&body);
+                    record
+                }
+                // handshake_failure alert
+                None => vec![0x15, 0x03, 0x01, 0x00, 0x02, 0x02, 0x28],
+            };
+            let _ = stream.write_all(&response);
+        });
+        port
+    }
+
+    fn audit(cipher_suite: &str, key_type: &str, bits: usize) -> TlsConfigurationAudit {
+        TlsConfigurationAudit {
+            protocol_version: Some("TLS 1.3".to_string()),
+            cipher_suite: Some(cipher_suite.to_string()),
+            alpn_protocol: Some("h2".to_string()),
+            key_exchange_group: Some("x25519".to_string()),
+            leaf_key_type: key_type.to_string(),
+            leaf_key_bits: bits,
+            legacy_versions_accepted: Vec::new(),
+            static_rsa_accepted: false,
+        }
+    }
+
+    #[test]
+    fn test_probe_detects_legacy_version() {
+        let timeout = Duration::from_secs(5);
+        let port = mock_server(Some((TLS1_0, 0xc013)));
+        assert_eq!(probe_server_hello("127.0.0.1", port, timeout, TLS1_0, LEGACY_SUITES).unwrap(), Some((TLS1_0, 0xc013)));
+
+        let port = mock_server(None);
+        assert_eq!(probe_server_hello("127.0.0.1", port, timeout, TLS1_0, LEGACY_SUITES).unwrap(), None);
+    }
+
+    #[test]
+    fn test_modern_configuration_has_no_weaknesses() {
+        let modern = audit("TLS13_AES_128_GCM_SHA256", "EC P-256", 256);
+        assert!(modern.forward_secrecy());
+        assert!(modern.weaknesses().is_empty());
+    }
+
+    #[test]
+    fn test_weak_configuration() {
+        let mut weak = audit("TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256", "RSA", 1024);
+        weak.legacy_versions_accepted = vec!["TLS 1.0".to_string(), "TLS 1.1".to_string()];
+        weak.static_rsa_accepted = true;
+
+        assert_eq!(weak.weaknesses(), vec![
+            TlsWeakness::LegacyProtocol("TLS 1.0".to_string()),
+            TlsWeakness::LegacyProtocol("TLS 1.1".to_string()),
+            TlsWeakness::WeakRsaKey(1024),
+            TlsWeakness::StaticRsaAccepted,
+        ]);
+
+        // Static RSA chosen for a modern client means nobody gets forward secrecy
+        weak.cipher_suite = Some("TLS_RSA_WITH_AES_128_GCM_SHA256".to_string());
+        assert!(!weak.forward_secrecy());
+        assert_eq!(weak.weaknesses().last(), Some(&TlsWeakness::NoForwardSecrecy));
+    }
+}
@@ -0,0 +1,286 @@
+use anyhow::{Context, Result};
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{Certificate, ClientConfig, ClientConnection, RootCertStore, ServerName};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// Everything we observed from the server during a single TLS handshake
+#[derive(Debug, Clone)]
+pub struct HandshakeCapture {
+    /// DER-encoded certificates exactly as presented by the server, leaf first
+    pub peer_certificates: Vec<Vec<u8>>,
+    /// Error reported by the webpki verifier, if the presented chain was rejected
+    pub verification_error: Option<String>,
+    /// Serialized SCTs delivered through the TLS `signed_certificate_timestamp` extension
+    pub scts: Vec<Vec<u8>>,
+    /// Stapled OCSP response, if the server sent one
+    pub ocsp_response: Option<Vec<u8>>,
+    /// Negotiated protocol version as its wire value (0x0304 is TLS 1.3)
+    pub protocol_version: Option<u16>,
+    pub cipher_suite: Option<String>,
+    pub alpn_protocol: Option<String>,
+    /// Named group from the server's key share or ServerKeyExchange
+    pub key_exchange_group: Option<u16>,
+}
+
+impl HandshakeCapture {
+    /// A capture holding only certificates, for chains that did not come from a handshake
+    pub fn from_chain(peer_certificates: Vec<Vec<u8>>) -> Self {
+        Self {
+            peer_certificates,
+            verification_error: None,
+            scts: Vec::new(),
+            ocsp_response: None,
+            protocol_version: None,
+            cipher_suite: None,
+            alpn_protocol: None,
+            key_exchange_group: None,
+        }
+    }
+}
+
+/// Socket wrapper keeping a copy of everything the server sent
+struct RecordingStream<'a> {
+    inner: &'a mut TcpStream,
+    received: Vec<u8>,
+}
+
+impl Read for RecordingStream<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.received.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl Write for RecordingStream<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Certificate verifier that records the webpki verdict instead of aborting
+/// the handshake, so that untrusted certificates can still be inspected.
+struct CapturingVerifier {
+    inner: WebPkiVerifier,
+    outcome: Mutex<Option<Result<(), rustls::Error>>>,
+    scts: Mutex<Vec<Vec<u8>>>,
+    ocsp_response: Mutex<Option<Vec<u8>>>,
+}
+
+impl ServerCertVerifier for CapturingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let scts: Vec<Vec<u8>> = scts.map(|sct| sct.to_vec()).collect();
+        let verdict = self
+            .inner
+            .verify_server_cert(
+                end_entity,
+                intermediates,
+                server_name,
+                &mut scts.iter().map(|sct| sct.as_slice()),
+                ocsp_response,
+                now,
+            )
+            .map(|_| ());
+        *self.outcome.lock().unwrap() = Some(verdict);
+        *self.scts.lock().unwrap() = scts;
+        if !ocsp_response.is_empty() {
+            *self.ocsp_response.lock().unwrap() = Some(ocsp_response.to_vec());
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn request_scts(&self) -> bool {
+        true
+    }
+}
+
+/// Perform a real TLS handshake with `domain:port` and capture the peer certificates
+pub async fn capture_handshake(domain: &str, port: u16, timeout: Duration, roots: RootCertStore) -> Result<HandshakeCapture> {
+    let domain = domain.to_string();
+    tokio::task::spawn_blocking(move || capture_handshake_blocking(&domain, port, timeout, roots))
+        .await
+        .context("TLS handshake task panicked")?
+}
+
+fn capture_handshake_blocking(domain: &str, port: u16, timeout: Duration, roots: RootCertStore) -> Result<HandshakeCapture> {
+    let verifier = Arc::new(CapturingVerifier {
+        inner: WebPkiVerifier::new(roots, None),
+        outcome: Mutex::new(None),
+        scts: Mutex::new(Vec::new()),
+        ocsp_response: Mutex::new(None),
+    });
+
+    let mut config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    let server_name = ServerName::try_from(domain)
+        .with_context(|| format!("Invalid server name: {}", domain))?;
+    let mut conn = ClientConnection::new(Arc::new(config), server_name)?;
+
+    let addr = (domain, port)
+        .to_socket_addrs()
+        .with_context(|| format!("Failed to resolve {}", domain))?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No addresses found for {}", domain))?;
+    let mut sock = TcpStream::connect_timeout(&addr, timeout)
+        .with_context(|| format!("Failed to connect to {}", addr))?;
+    sock.set_read_timeout(Some(timeout))?;
+    sock.set_write_timeout(Some(timeout))?;
+
+    let mut recording = RecordingStream { inner: &mut sock, received: Vec::new() };
+    while conn.is_handshaking() {
+        conn.complete_io(&mut recording)
+            .with_context(|| format!("TLS handshake with {} failed", domain))?;
+    }
+    let key_exchange_group = key_exchange_group(&recording.received);
+    conn.send_close_notify();
+    let _ = conn.complete_io(&mut sock);
+    let _ = sock.flush();
+
+    let peer_certificates: Vec<Vec<u8>> = conn
+        .peer_certificates()
+        .map(|certs| certs.iter().map(|c| c.0.clone()).collect())
+        .unwrap_or_default();
+    if peer_certificates.is_empty() {
+        return Err(anyhow::anyhow!("Server {} presented no certificates", domain));
+    }
+
+    let verification_error = match verifier.outcome.lock().unwrap().take() {
+        Some(Ok(())) => None,
+        Some(Err(e)) => Some(e.to_string()),
+        None => Some("certificate was never verified".to_string()),
+    };
+
+    let scts = std::mem::take(&mut *verifier.scts.lock().unwrap());
+    let ocsp_response = verifier.ocsp_response.lock().unwrap().take();
+
+    Ok(HandshakeCapture {
+        peer_certificates,
+        verification_error,
+        scts,
+        ocsp_response,
+        protocol_version: conn.protocol_version().map(|v| v.get_u16()),
+        cipher_suite: conn.negotiated_cipher_suite().map(|s| format!("{:?}", s.suite())),
+        alpn_protocol: conn.alpn_protocol().map(|p| String::from_utf8_lossy(p).into_owned()),
+        key_exchange_group,
+    })
+}
+
+/// Find the key exchange group in the server's plaintext handshake flight.
+/// TLS 1.3 carries it in the ServerHello `key_share`, TLS 1.2 in the ECDHE ServerKeyExchange.
+fn key_exchange_group(server_flight: &[u8]) -> Option<u16> {
+    // Concatenate the leading handshake records; anything after them is encrypted
+    let mut handshake = Vec::new();
+    let mut rest = server_flight;
+    while rest.len() >= 5 && rest[0] == 0x16 {
+        let len = u16::from_be_bytes([rest[3], rest[4]]) as usize;
+        let end = (5 + len).min(rest.len());
+        handshake.extend_from_slice(&rest[5..end]);
+        rest = &rest[end..];
+    }
+
+    let mut group = None;
+    let mut messages = handshake.as_slice();
+    while messages.len() >= 4 {
+        let len = u32::from_be_bytes([0, messages[1], messages[2], messages[3]]) as usize;
+        let Some(body) = messages.get(4..4 + len) else { break };
+        match messages[0] {
+            // ServerHello (a HelloRetryRequest names the group the same way)
+            2 => group = server_hello_key_share(body).or(group),
+            // ServerKeyExchange with curve_type named_curve
+            12 if body.len() >= 3 && body[0] == 3 => group = Some(u16::from_be_bytes([body[1], body[2]])),
+            _ => {}
+        }
+        messages = &messages[4 + len..];
+    }
+    group
+}
+
+fn server_hello_key_share(body: &[u8]) -> Option<u16> {
+    // legacy_version(2) random(32) session_id<1> cipher_suite(2) compression(1) extensions<2>
+    let session_id_len = *body.get(34)? as usize;
+    let mut pos = 35 + session_id_len + 3;
+    let extensions_len = u16::from_be_bytes([*body.get(pos)?, *body.get(pos + 1)?]) as usize;
+    pos += 2;
+    let end = (pos + extensions_len).min(body.len());
+    while pos + 4 <= end {
+        let ext_type = u16::from_be_bytes([body[pos], body[pos + 1]]);
+        let ext_len = u16::from_be_bytes([body[pos + 2], body[pos + 3]]) as usize;
+        if ext_type == 0x0033 && ext_len >= 2 {
+            return Some(u16::from_be_bytes([*body.get(pos + 4)?, *body.get(pos + 5)?]));
+        }
+        pos += 4 + ext_len;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(content: &[u8]) -> Vec<u8> {
+        let mut out = vec![0x16, 0x03, 0x03];
+        out.extend_from_slice(&(content.len() as u16).to_be_bytes());
+        out.extend_from_slice(content);
+        out
+    }
+
+    fn handshake(msg_type: u8, body: &[u8]) -> Vec<u8> {
+        let mut out = vec![msg_type];
+        out.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        out.extend_from_slice(body);
+        out
+    }
+
+    #[test]
+    fn test_key_exchange_group_from_tls13_server_hello() {
+        let mut body = vec![0x03, 0x03];
+        body.extend_from_slice(&[0u8; 32]);
+        body.push(0); // empty session id
+        body.extend_from_slice(&[0x13, 0x01, 0x00]);
+        // supported_versions(0x002b) = TLS 1.3, key_share(0x0033) = x25519 with a 32-byte key
+        let mut extensions = vec![0x00, 0x2b, 0x00, 0x02, 0x03, 0x04, 0x00, 0x33, 0x00, 0x24, 0x00, 0x1d, 0x00, 0x20];
+        extensions.extend_from_slice(&[7u8; 32]);
+        body.extend_from_slice(&(extensions.len() as u16).to_be_bytes());
+        body.extend_from_slice(&extensions);
+
+        let mut flight = record(&handshake(2, &body));
+        // Encrypted records that follow must be ignored
+        flight.extend_from_slice(&[0x17, 0x03, 0x03, 0x00, 0x02, 0xff, 0xff]);
+        assert_eq!(key_exchange_group(&flight), Some(0x001d));
+    }
+
+    #[test]
+    fn test_key_exchange_group_from_tls12_server_key_exchange() {
+        let mut body = vec![0x03, 0x03];
+        body.extend_from_slice(&[0u8; 32]);
+        body.push(0);
+        body.extend_from_slice(&[0xc0, 0x2f, 0x00, 0x00, 0x00]);
+        let server_hello = handshake(2, &body);
+        let server_key_exchange = handshake(12, &[0x03, 0x00, 0x17, 0x41, 0x04]);
+
+        // Messages may be split across records
+        let mut flight = record(&server_hello);
+        flight.extend_from_slice(&record(&server_key_exchange));
+        assert_eq!(key_exchange_group(&flight), Some(0x0017));
+        assert_eq!(key_exchange_group(&[0x15, 0x03, 0x03, 0x00, 0x02, 0x02, 0x28]), None);
+    }
+}
@@ -0,0 +1,617 @@
+use anyhow::Result;
+use regex::Regex;
+use scraper::{ElementRef, Html};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::crawler::CRAWLER_AGENT;
+use crate::page_analysis::{collapse_whitespace, mentions, selector};
+
+/// Team, advisor and investor allocations above this share of supply leave holders exposed to insiders
+pub const MAX_INSIDER_SHARE: f64 = 40.0;
+/// Allocations summing further than this from 100% are inconsistent
+const ALLOCATION_TOLERANCE: f64 = 1.0;
+/// Whitepapers larger than this are abandoned
+const MAX_DOCUMENT_BYTES: usize = 25 * 1024 * 1024;
+/// Vesting sentences are kept up to this many characters
+const MAX_TERM_LENGTH: usize = 200;
+
+/// Allocation categories held by people with inside information
+const INSIDER_CATEGORIES: &[&str] = &[
+    "team", "founder", "founders", "core contributors", "employees", "advisor", "advisors", "investor",
+    "investors", "seed", "private sale", "private round", "strategic", "early backers", "insiders",
+];
+/// Allocation categories for everyone else
+const PUBLIC_CATEGORIES: &[&str] = &[
+    "community", "ecosystem", "treasury", "liquidity", "public sale", "ido", "ico", "marketing",
+    "foundation", "reserve", "development", "airdrop", "staking", "rewards", "partners", "grants",
+    "incentives", "dao",
+];
+/// Percentages on these lines are rates, fees or discounts rather than shares of supply
+const RATE_WORDS: &[&str] = &["apy", "apr", "yield", "interest", "fee", "fees", "tax", "discount", "bonus", "returns", "per transaction"];
+const VESTING_WORDS: &[&str] = &["vest", "vesting", "vested", "cliff", "locked", "lock-up", "lockup", "unlock", "unlocks", "linear release"];
+/// Only these lines are searched for Solana addresses, whose alphabet matches ordinary words too
+const ADDRESS_WORDS: &[&str] = &["contract", "address", "mint", "token"];
+const LINE_ELEMENTS: &str = "h1, h2, h3, h4, h5, h6, p, li, tr, dt, dd, caption, figcaption, blockquote";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Allocation {
+    pub label: String,
+    /// Share of total supply in percent
+    pub percent: f64,
+    pub insider: bool,
+    /// Vesting terms stated for this allocation
+    pub vesting: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContractChain {
+    Evm,
+    Solana,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractAddress {
+    pub chain: ContractChain,
+    pub address: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TokenomicsProblem {
+    /// Insiders hold more than `MAX_INSIDER_SHARE` percent of supply
+    InsiderShareAboveLimit { share: f64 },
+    /// Insider allocations that no vesting or lock-up term refers to
+    UnvestedInsiders { labels: Vec<String> },
+    /// Allocation percentages do not add up to 100
+    AllocationsDoNotSum { total: f64 },
+}
+
+impl std::fmt::Display for TokenomicsProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenomicsProblem::InsiderShareAboveLimit { share } => {
+                write!(f, "insiders hold {:.1}% of supply (limit {:.0}%)", share, MAX_INSIDER_SHARE)
+            }
+            TokenomicsProblem::UnvestedInsiders { labels } => write!(f, "no vesting for {}", labels.join(", ")),
+            TokenomicsProblem::AllocationsDoNotSum { total } => write!(f, "allocations add up to {:.1}%", total),
+        }
+    }
+}
+
+/// Token distribution as stated by a whitepaper or tokenomics page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenDistribution {
+    pub source: String,
+    pub total_supply: Option<u128>,
+    pub allocations: Vec<Allocation>,
+    /// Every sentence about vesting, cliffs or lock-ups
+    pub vesting_terms: Vec<String>,
+    pub contract_addresses: Vec<ContractAddress>,
+}
+
+impl TokenDistribution {
+    /// Extract the distribution from a document's text, one line per table row, list item or paragraph
+    pub fn extract(source: &str, lines: &[String]) -> Self {
+        static PERCENT: OnceLock<Regex> = OnceLock::new();
+        static EVM: OnceLock<Regex> = OnceLock::new();
+        static SOLANA: OnceLock<Regex> = OnceLock::new();
+        let percent = PERCENT.get_or_init(|| Regex::new(r"(\d{1,3}(?:\.\d+)?)\s?%").expect("static regex is valid"));
+        let evm = EVM.get_or_init(|| Regex::new(r"\b0x[0-9a-fA-F]{40}\b").expect("static regex is valid"));
+        let solana = SOLANA.get_or_init(|| Regex::new(r"\b[1-9A-HJ-NP-Za-km-z]{32,44}\b").expect("static regex is valid"));
+
+        let mut distribution = Self {
+            source: source.to_string(),
+            total_supply: None,
+            allocations: Vec::new(),
+            vesting_terms: Vec::new(),
+            contract_addresses: Vec::new(),
+        };
+        for line in lines {
+            let line = collapse_whitespace(line);
+            if distribution.total_supply.is_none() {
+                distribution.total_supply = total_supply(&line);
+            }
+            // Durations distinguish vesting terms from a table header saying "Vesting"
+            let vesting = mentions(&line, VESTING_WORDS) && line.chars().any(|c| c.is_ascii_digit());
+            if vesting {
+                distribution.vesting_terms.push(line.chars().take(MAX_TERM_LENGTH).collect());
+            }
+
+            let found: Vec<(usize, usize, f64)> = percent.captures_iter(&line)
+                .filter(|_| !mentions(&line, RATE_WORDS))
+                .map(|c| {
+                    let whole = c.get(0).unwrap();
+                    (whole.start(), whole.end(), c[1].parse().unwrap_or(0.0))
+                })
+                .collect();
+            // "Team 15%, Community 45%" names each share before it, "15% Team, 45% Community" after it
+            let labels_lead = found.first().is_some_and(|&(start, _, _)| allocation_label(&line[..start]).is_some());
+            for (i, &(start, end, value)) in found.iter().enumerate() {
+                let segment_start = if i == 0 { 0 } else { found[i - 1].1 };
+                let segment_end = found.get(i + 1).map_or(line.len(), |next| next.0);
+                let (near, far) = if labels_lead {
+                    (&line[segment_start..start], &line[end..segment_end])
+                } else {
+                    (&line[end..segment_end], &line[segment_start..start])
+                };
+                // A lone percentage belongs to the whole line; several each belong to their own segment
+                let (label, context) = match allocation_label(near) {
+                    Some(label) => (label, if found.len() == 1 { line.as_str() } else { near }),
+                    None if found.len() == 1 => (allocation_label(far).unwrap_or_else(|| line.clone()), line.as_str()),
+                    None => continue,
+                };
+                let category = INSIDER_CATEGORIES.iter().map(|c| (c, true))
+                    .chain(PUBLIC_CATEGORIES.iter().map(|c| (c, false)))
+                    .find(|(c, _)| mentions(context, &[**c]));
+                let Some((_, insider)) = category.filter(|_| value > 0.0 && value <= 100.0) else { continue };
+                // Terms follow their share up to the next comma: "Advisors 5% (12-month cliff), ..."
+                let terms = line[end..segment_end].split([',', ';']).next().unwrap_or_default();
+                let vested = vesting && (found.len() == 1 || mentions(terms, VESTING_WORDS));
+                if !distribution.allocations.iter().any(|a| a.label.eq_ignore_ascii_case(&label)) {
+                    distribution.allocations.push(Allocation {
+                        label,
+                        percent: value,
+                        insider,
+                        vesting: vested.then(|| line.chars().take(MAX_TERM_LENGTH).collect()),
+                    });
+                }
+            }
+
+            let mut addresses: Vec<ContractAddress> = evm.find_iter(&line)
+                .map(|m| ContractAddress { chain: ContractChain::Evm, address: m.as_str().to_string() })
+                .collect();
+            if mentions(&line, ADDRESS_WORDS) {
+                addresses.extend(solana.find_iter(&line)
+                    // Real base58 keys mix digits and both cases
+                    .filter(|m| m.as_str().chars().any(|c| c.is_ascii_digit()))
+                    .map(|m| ContractAddress { chain: ContractChain::Solana, address: m.as_str().to_string() }));
+            }
+            for address in addresses {
+                if !distribution.contract_addresses.contains(&address) {
+                    distribution.contract_addresses.push(address);
+                }
+            }
+        }
+
+        // Vesting is often described below the table, one sentence per category: "Founders and team"
+        // is covered by a sentence about the team, and every insider by one about insiders
+        for allocation in distribution.allocations.iter_mut().filter(|a| a.vesting.is_none()) {
+            let mut names: Vec<&str> = vec![allocation.label.as_str()];
+            names.extend(INSIDER_CATEGORIES.iter().chain(PUBLIC_CATEGORIES).filter(|c| mentions(&allocation.label, &[c])));
+            if allocation.insider {
+                names.extend(["insider", "insiders"]);
+            }
+            allocation.vesting = distribution.vesting_terms.iter()
+                // Lines listing several shares were already split between their allocations
+                .filter(|term| percent.find_iter(term).count() < 2)
+                .find(|term| mentions(term, &names))
+                .cloned();
+        }
+        distribution
+    }
+
+    /// Combined share of insider allocations, if any allocation was found
+    pub fn insider_share(&self) -> Option<f64> {
+        if self.allocations.is_empty() {
+            return None;
+        }
+        Some(self.allocations.iter().filter(|a| a.insider).map(|a| a.percent).sum())
+    }
+
+    pub fn allocated(&self) -> f64 {
+        self.allocations.iter().map(|a| a.percent).sum()
+    }
+
+    /// Supply, a distribution adding up to 100% and, where insiders hold tokens, vesting terms
+    pub fn is_complete(&self) -> bool {
+        self.total_supply.is_some() && !self.allocations.is_empty() && self.problems().is_empty()
+    }
+
+    pub fn problems(&self) -> Vec<TokenomicsProblem> {
+        let mut problems = Vec::new();
+        if self.allocations.is_empty() {
+            return problems;
+        }
+        let share = self.insider_share().unwrap_or(0.0);
+        if share > MAX_INSIDER_SHARE {
+            problems.push(TokenomicsProblem::InsiderShareAboveLimit { share });
+        }
+        // Insiders no vesting term refers to can sell on day one
+        let labels: Vec<String> = self.allocations.iter()
+            .filter(|a| a.insider && a.vesting.is_none())
+            .map(|a| a.label.clone())
+            .collect();
+        if !labels.is_empty() {
+            problems.push(TokenomicsProblem::UnvestedInsiders { labels });
+        }
+        let total = self.allocated();
+        if (total - 100.0).abs() > ALLOCATION_TOLERANCE {
+            problems.push(TokenomicsProblem::AllocationsDoNotSum { total });
+        }
+        problems
+    }
+}
+
+impl std::fmt::Display for TokenDistribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.total_supply {
+            Some(supply) => write!(f, "supply {}", supply)?,
+            None => f.write_str("supply not stated")?,
+        }
+        write!(f, ", {} allocations", self.allocations.len())?;
+        if let Some(share) = self.insider_share() {
+            write!(f, ", insiders {:.1}%", share)?;
+        }
+        write!(f, ", {} vesting terms, {} contract addresses", self.vesting_terms.len(), self.contract_addresses.len())
+    }
+}
+
+/// "Max supply: 1,000,000,000" or "Total supply of 21 million tokens"
+fn total_supply(line: &str) -> Option<u128> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| Regex::new(
+        r"(?i)\b(?:total|max(?:imum)?|fixed|initial)\s+(?:token\s+)?supply\b[^0-9]{0,40}?(\d[\d,.' ]*\d|\d)\s*(billion|million|thousand|bn|b|m|k)?\b",
+    ).expect("static regex is valid"));
+    let captures = pattern.captures(line)?;
+    let digits: String = captures[1].chars().filter(|c| !matches!(c, ',' | '\'' | ' ')).collect();
+    let multiplier: f64 = match captures.get(2).map(|m| m.as_str().to_ascii_lowercase()).as_deref() {
+        Some("billion" | "bn" | "b") => 1e9,
+        Some("million" | "m") => 1e6,
+        Some("thousand" | "k") => 1e3,
+        _ => 1.0,
+    };
+    // Separators like "1.000.000.000" are grouping, not decimals
+    let value: f64 = if digits.matches('.').count() > 1 {
+        digits.replace('.', "").parse().ok()?
+    } else {
+        digits.parse().ok()?
+    };
+    let supply = (value * multiplier).round();
+    (supply >= 1.0).then_some(supply as u128)
+}
+
+/// Category name from the text beside a percentage, e.g. "Team & Advisors:" or "— Community rewards"
+fn allocation_label(text: &str) -> Option<String> {
+    let label = text.split(['|', '(', ')']).map(str::trim).rfind(|part| !part.is_empty()).unwrap_or_default();
+    let label = label.trim_matches(|c: char| !c.is_alphanumeric()).trim();
+    let is_category = INSIDER_CATEGORIES.iter().chain(PUBLIC_CATEGORIES).any(|c| mentions(label, &[c]));
+    (is_category && label.len() <= 60).then(|| label.to_string())
+}
+
+/// Table rows, list items and paragraphs of an HTML page as separate lines
+pub fn html_lines(html: &str) -> Vec<String> {
+    let document = Html::parse_document(html);
+    let blocks = selector(LINE_ELEMENTS);
+    let cells = selector("th, td");
+    document.select(&blocks)
+        // Nested blocks are read as part of their outermost block
+        .filter(|element| !element.ancestors().filter_map(ElementRef::wrap).any(|a| blocks.matches(&a)))
+        .map(|element| match element.value().name() {
+            "tr" => element.select(&cells)
+                .map(|cell| collapse_whitespace(&cell.text().collect::<String>()))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            _ => collapse_whitespace(&element.text().collect::<Vec<_>>().join(" ")),
+        })
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Text drawn by a PDF's content streams, one line per text line
+///
+/// Handles uncompressed and Flate-compressed streams with simple font encodings;
+/// text in CID-keyed fonts comes out as noise and simply matches nothing.
+pub fn pdf_lines(pdf: &[u8]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut rest = pdf;
+    while let Some(start) = find(rest, b"stream") {
+        let after = &rest[start + b"stream".len()..];
+        let Some(end) = find(after, b"endstream") else { break };
+        let raw = after[..end].strip_prefix(b"\r\n").or_else(|| after[..end].strip_prefix(b"\n")).unwrap_or(&after[..end]);
+        rest = &after[end + b"endstream".len()..];
+        // A few kilobytes can inflate to gigabytes; a stream larger than any real document is unreadable
+        let mut inflated = Vec::new();
+        let limit = MAX_DOCUMENT_BYTES as u64 + 1;
+        let content = match flate2::read::ZlibDecoder::new(raw).take(limit).read_to_end(&mut inflated) {
+            Ok(_) if inflated.len() > MAX_DOCUMENT_BYTES => continue,
+            Ok(_) => inflated.as_slice(),
+            Err(_) => raw,
+        };
+        if find(content, b"BT").is_some() {
+            lines.extend(content_stream_lines(content));
+        }
+    }
+    lines
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+/// Strings shown by Tj, TJ, ' and "; a vertical move or the end of a text object starts a new line
+fn content_stream_lines(content: &[u8]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut strings: Vec<String> = Vec::new();
+    let mut numbers: Vec<f64> = Vec::new();
+    let mut i = 0;
+    let break_line = |line: &mut String, lines: &mut Vec<String>| {
+        let text = collapse_whitespace(line);
+        if !text.is_empty() {
+            lines.push(text);
+        }
+        line.clear();
+    };
+    while i < content.len() {
+        match content[i] {
+            b'(' => {
+                let (text, next) = literal_string(content, i + 1);
+                strings.push(text);
+                i = next;
+            }
+            b'<' if content.get(i + 1) != Some(&b'<') => {
+                let end = content[i..].iter().position(|&b| b == b'>').map_or(content.len(), |p| i + p);
+                let hex: Vec<u8> = content[i + 1..end].iter().copied().filter(u8::is_ascii_hexdigit).collect();
+                strings.push(hex.chunks(2)
+                    .filter_map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+                    .map(char::from)
+                    .collect());
+                i = end + 1;
+            }
+            // Large negative kerning inside a TJ array is a word gap
+            b'-' | b'.' | b'0'..=b'9' => {
+                let end = content[i..].iter().position(|b| !matches!(b, b'-' | b'.' | b'0'..=b'9')).map_or(content.len(), |p| i + p);
+                let value: f64 = std::str::from_utf8(&content[i..end]).ok().and_then(|s| s.parse().ok()).unwrap_or(0.0);
+                if value < -200.0 && !strings.is_empty() {
+                    strings.push(" ".to_string());
+                }
+                numbers.push(value);
+                i = end;
+            }
+            b'%' => {
+                i += content[i..].iter().position(|&b| b == b'\n').unwrap_or(content.len() - i);
+            }
+            c if c.is_ascii_alphabetic() || c == b'\'' || c == b'"' || c == b'*' => {
+                let end = content[i..].iter()
+                    .position(|&b| !(b.is_ascii_alphabetic() || b == b'\'' || b == b'"' || b == b'*'))
+                    .map_or(content.len(), |p| i + p);
+                match &content[i..end] {
+                    b"Tj" | b"TJ" => line.push_str(&strings.concat()),
+                    b"'" | b"\"" => {
+                        break_line(&mut line, &mut lines);
+                        line.push_str(&strings.concat());
+                    }
+                    b"Td" | b"TD" if numbers.last().is_some_and(|ty| *ty != 0.0) => break_line(&mut line, &mut lines),
+                    b"Td" | b"TD" => line.push(' '),
+                    b"T*" | b"ET" => break_line(&mut line, &mut lines),
+                    _ => {}
+                }
+                strings.clear();
+                numbers.clear();
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    break_line(&mut line, &mut lines);
+    lines
+}
+
+/// A `(...)` string starting after the opening parenthesis, and the index after its close
+fn literal_string(content: &[u8], mut i: usize) -> (String, usize) {
+    let mut text = String::new();
+    let mut depth = 0;
+    while i < content.len() {
+        match content[i] {
+            b'\\' if i + 1 < content.len() => {
+                i += 1;
+                match content[i] {
+                    b'n' | b'r' => text.push(' '),
+                    b't' => text.push('\t'),
+                    b'0'..=b'7' => {
+                        let end = (i..(i + 3).min(content.len())).take_while(|&j| matches!(content[j], b'0'..=b'7')).last().unwrap_or(i);
+                        let code = std::str::from_utf8(&content[i..=end]).ok().and_then(|s| u8::from_str_radix(s, 8).ok());
+                        text.extend(code.map(char::from));
+                        i = end;
+                    }
+                    b'\n' | b'\r' => {}
+                    other => text.push(char::from(other)),
+                }
+            }
+            b'(' => {
+                depth += 1;
+                text.push('(');
+            }
+            b')' if depth == 0 => return (text, i + 1),
+            b')' => {
+                depth -= 1;
+                text.push(')');
+            }
+            other => text.push(char::from(other)),
+        }
+        i += 1;
+    }
+    (text, i)
+}
+
+/// Download the whitepaper or tokenomics page at `url` and extract its token distribution
+pub async fn fetch_tokenomics(url: &str) -> Result<TokenDistribution> {
+    let url = url.split('#').next().unwrap_or_default();
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(20))
+        .user_agent(format!("{}/{}", CRAWLER_AGENT, env!("CARGO_PKG_VERSION")))
+        .build()?;
+    let mut response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("{} returned {}", url, response.status()));
+    }
+    let final_url = response.url().to_string();
+    let content_type = response.headers().get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        if body.len() > MAX_DOCUMENT_BYTES {
+            return Err(anyhow::anyhow!("{} is larger than {} bytes", url, MAX_DOCUMENT_BYTES));
+        }
+    }
+
+    let lines = if body.starts_with(b"%PDF-") {
+        pdf_lines(&body)
+    } else if content_type.contains("html") {
+        html_lines(&String::from_utf8_lossy(&body))
+    } else if content_type.starts_with("text/") {
+        String::from_utf8_lossy(&body).lines().map(str::to_string).collect()
+    } else {
+        return Err(anyhow::anyhow!("{} is {}, not a document", url, content_type));
+    };
+    Ok(TokenDistribution::extract(&final_url, &lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{self, MockResponse};
+
+    const WHITEPAPER: &[u8] = include_bytes!("../fixtures/whitepaper.pdf");
+
+    #[test]
+    fn test_extract_from_html_table() {
+        let html = r#"<html><body>
+            <h2>Tokenomics</h2>
+            <p>Total supply: 1,000,000,000 EXP</p>
+            <table>
+                <tr><th>Allocation</th><th>Share</th><th>Vesting</th></tr>
+                <tr><td>Team</td><td>15%</td><td>12-month cliff, 36-month linear vesting</td></tr>
+                <tr><td>Private sale</td><td>10%</td><td>6-month cliff</td></tr>
+                <tr><td>Community rewards</td><td>45%</td><td></td></tr>
+                <tr><td>Treasury</td><td>20%</td><td></td></tr>
+                <tr><td>Liquidity</td><td>10%</td><td></td></tr>
+            </table>
+            <p>Token contract: 0x1234567890abcDEF1234567890abcdef12345678</p>
+            <p>Staking APY up to 12% for early supporters.</p>
+        </body></html>"#;
+
+        let distribution = TokenDistribution::extract("https://example-project.com/tokenomics", &html_lines(html));
+        assert_eq!(distribution.total_supply, Some(1_000_000_000));
+        let labels: Vec<(&str, f64, bool)> = distribution.allocations.iter()
+            .map(|a| (a.label.as_str(), a.percent, a.insider))
+            .collect();
+        assert_eq!(labels, vec![
+            ("Team", 15.0, true),
+            ("Private sale", 10.0, true),
+            ("Community rewards", 45.0, false),
+            ("Treasury", 20.0, false),
+            ("Liquidity", 10.0, false),
+        ]);
+        assert_eq!(distribution.insider_share(), Some(25.0));
+        assert!(distribution.allocations[0].vesting.as_deref().unwrap().contains("36-month linear vesting"));
+        assert_eq!(distribution.contract_addresses, vec![ContractAddress {
+            chain: ContractChain::Evm,
+            address: "0x1234567890abcDEF1234567890abcdef12345678".to_string(),
+        }]);
+        assert!(distribution.problems().is_empty());
+        assert!(distribution.is_complete());
+    }
+
+    #[test]
+    fn test_insider_heavy_distribution_without_vesting() {
+        let lines: Vec<String> = [
+            "Max supply 100 million tokens",
+            "Founders and team: 35%",
+            "Advisors - 10%",
+            "Seed investors 20%",
+            "Public sale 25%",
+        ].iter().map(|s| s.to_string()).collect();
+
+        let distribution = TokenDistribution::extract("https://example-project.com/token", &lines);
+        assert_eq!(distribution.total_supply, Some(100_000_000));
+        assert_eq!(distribution.insider_share(), Some(65.0));
+        assert_eq!(distribution.problems(), vec![
+            TokenomicsProblem::InsiderShareAboveLimit { share: 65.0 },
+            TokenomicsProblem::UnvestedInsiders { labels: vec![
+                "Founders and team".to_string(), "Advisors".to_string(), "Seed investors".to_string(),
+            ] },
+            TokenomicsProblem::AllocationsDoNotSum { total: 90.0 },
+        ]);
+        assert!(!distribution.is_complete());
+    }
+
+    #[test]
+    fn test_several_allocations_on_one_line() {
+        let lines: Vec<String> = [
+            "Team 15%, Advisors 5% (12-month cliff), Community 80%",
+            "Allocation: 70% Ecosystem, 30% Treasury",
+        ].iter().map(|s| s.to_string()).collect();
+
+        let distribution = TokenDistribution::extract("https://example-project.com/token", &lines);
+        let labels: Vec<(&str, f64, bool)> = distribution.allocations.iter()
+            .map(|a| (a.label.as_str(), a.percent, a.vesting.is_some()))
+            .collect();
+        assert_eq!(labels, vec![
+            ("Team", 15.0, false),
+            ("Advisors", 5.0, true),
+            ("Community", 80.0, false),
+            ("Ecosystem", 70.0, false),
+            ("Treasury", 30.0, false),
+        ]);
+    }
+
+    #[test]
+    fn test_vesting_for_one_insider_does_not_cover_another() {
+        let lines: Vec<String> = [
+            "Founders and team: 20%",
+            "Advisors: 5%",
+            "Community: 75%",
+            "Team tokens unlock linearly over 24 months after a 6 month cliff",
+        ].iter().map(|s| s.to_string()).collect();
+
+        let distribution = TokenDistribution::extract("https://example-project.com/token", &lines);
+        assert!(distribution.allocations[0].vesting.is_some());
+        assert_eq!(distribution.problems(), vec![
+            TokenomicsProblem::UnvestedInsiders { labels: vec!["Advisors".to_string()] },
+        ]);
+    }
+
+    #[test]
+    fn test_total_supply_formats() {
+        assert_eq!(total_supply("Total supply: 21 million"), Some(21_000_000));
+        assert_eq!(total_supply("Maximum token supply is capped at 1.000.000.000"), Some(1_000_000_000));
+        assert_eq!(total_supply("Fixed supply of 2.5B EXP"), Some(2_500_000_000));
+        assert_eq!(total_supply("Circulating supply grows over time"), None);
+    }
+
+    #[test]
+    fn test_compressed_stream_beyond_the_size_limit_is_skipped() {
+        let text = b"BT (Total supply: 1,000,000) Tj ET ";
+        let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        std::io::Write::write_all(&mut encoder, text).unwrap();
+        std::io::Write::write_all(&mut encoder, &vec![b' '; MAX_DOCUMENT_BYTES]).unwrap();
+        let bomb = encoder.finish().unwrap();
+        assert!(bomb.len() < 100 * 1024);
+
+        let mut pdf = b"%PDF-1.4\nstream\n".to_vec();
+        pdf.extend_from_slice(&bomb);
+        pdf.extend_from_slice(b"\nendstream\nstream\nBT (Max supply: 21 million) Tj ET\nendstream\n%%EOF");
+        assert_eq!(pdf_lines(&pdf), vec!["Max supply: 21 million"]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_tokenomics_from_pdf() {
+        let server = test_http::serve(vec![
+            ("/whitepaper.pdf", MockResponse::ok(WHITEPAPER).with_header("Content-Type", "application/octet-stream")),
+        ]).await;
+
+        let distribution = fetch_tokenomics(&format!("{}/whitepaper.pdf#page=4", server.base_url)).await.unwrap();
+        assert_eq!(distribution.total_supply, Some(500_000_000));
+        assert_eq!(distribution.allocations.len(), 4);
+        assert_eq!(distribution.insider_share(), Some(30.0));
+        assert!(distribution.allocations.iter().filter(|a| a.insider).all(|a| a.vesting.is_some()));
+        assert_eq!(distribution.contract_addresses[0].chain, ContractChain::Solana);
+        assert!(distribution.is_complete());
+    }
+}